@@ -7,13 +7,15 @@ use contract::BuildContractCommand;
 use duct::cmd;
 use std::path::PathBuf;
 #[cfg(feature = "parachain")]
-use {parachain::BuildParachainCommand, spec::BuildSpecCommand};
+use {parachain::BuildParachainCommand, runtime::BuildRuntimeCommand, spec::BuildSpecCommand};
 
 #[cfg(feature = "contract")]
 pub(crate) mod contract;
 #[cfg(feature = "parachain")]
 pub(crate) mod parachain;
 #[cfg(feature = "parachain")]
+pub(crate) mod runtime;
+#[cfg(feature = "parachain")]
 pub(crate) mod spec;
 
 /// Arguments for building a project.
@@ -35,6 +37,12 @@ pub(crate) struct BuildArgs {
 	#[arg(short = 'i', long = "id")]
 	#[cfg(feature = "parachain")]
 	pub(crate) id: Option<u32>,
+	/// Build the runtime of a parachain deterministically, within a pinned container image when
+	/// docker or podman is available or otherwise with the local toolchain, and report its hash,
+	/// versions and the `authorize_upgrade` call hash. Always builds in release mode.
+	#[arg(long)]
+	#[cfg(feature = "parachain")]
+	pub(crate) deterministic: bool,
 }
 
 /// Build a parachain, smart contract or Rust package.
@@ -53,6 +61,10 @@ pub(crate) enum Command {
 	#[cfg(feature = "parachain")]
 	#[clap(alias = "s")]
 	Spec(BuildSpecCommand),
+	/// Build only the runtime of a parachain, reporting its size.
+	#[cfg(feature = "parachain")]
+	#[clap(alias = "r")]
+	Runtime(BuildRuntimeCommand),
 }
 
 impl Command {
@@ -76,6 +88,7 @@ impl Command {
 				package: args.package,
 				release: args.release,
 				id: args.id,
+				deterministic: args.deterministic,
 				valid: true,
 			}
 			.execute()?;
@@ -142,6 +155,7 @@ mod tests {
 							package: package.clone(),
 							release,
 							id: None,
+							deterministic: false,
 						},
 						&mut cli,
 					)?,
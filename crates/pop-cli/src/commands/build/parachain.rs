@@ -3,7 +3,7 @@
 use crate::{cli, style::style};
 use clap::Args;
 use pop_common::Profile;
-use pop_parachains::build_parachain;
+use pop_parachains::{build_parachain, BuildReport, Builder, Runtime};
 use std::path::PathBuf;
 #[cfg(not(test))]
 use std::{thread::sleep, time::Duration};
@@ -22,6 +22,11 @@ pub struct BuildParachainCommand {
 	/// Parachain ID to be used when generating the chain spec files.
 	#[arg(short = 'i', long = "id")]
 	pub(crate) id: Option<u32>,
+	/// Build the runtime deterministically, within a pinned container image when docker or podman
+	/// is available or otherwise with the local toolchain, and report its hash, versions and the
+	/// `authorize_upgrade` call hash. Always builds in release mode.
+	#[arg(long)]
+	pub(crate) deterministic: bool,
 	// Deprecation flag, used to specify whether the deprecation warning is shown.
 	#[clap(skip)]
 	pub(crate) valid: bool,
@@ -38,6 +43,9 @@ impl BuildParachainCommand {
 	/// # Arguments
 	/// * `cli` - The CLI implementation to be used.
 	fn build(self, cli: &mut impl cli::traits::Cli) -> anyhow::Result<&'static str> {
+		if self.deterministic {
+			return self.build_deterministic(cli);
+		}
 		let project = if self.package.is_some() { "package" } else { "parachain" };
 		cli.intro(format!("Building your {project}"))?;
 
@@ -72,6 +80,47 @@ impl BuildParachainCommand {
 
 		Ok(project)
 	}
+
+	/// Builds the runtime of a parachain deterministically, reporting the details required to
+	/// verify the build and to propose an upgrade to the runtime.
+	///
+	/// # Arguments
+	/// * `cli` - The CLI implementation to be used.
+	fn build_deterministic(self, cli: &mut impl cli::traits::Cli) -> anyhow::Result<&'static str> {
+		cli.intro("Building your runtime deterministically")?;
+		let project_path = self.path.unwrap_or_else(|| PathBuf::from("./"));
+		let runtime = Runtime::locate(&project_path, self.package.as_deref())?;
+		let package = &runtime.package;
+		let builder = Builder::detect(&project_path)?;
+		match &builder {
+			Builder::Container { engine, image } =>
+				cli.info(format!("Building `{package}` within {image} via {engine}."))?,
+			Builder::Toolchain { version } => cli.warning(format!(
+				"NOTE: neither docker nor podman is available, so `{package}` is built with the local toolchain ({version}). The build is only reproducible using the same toolchain."
+			))?,
+		}
+		cli.warning("NOTE: this may take some time...")?;
+		let wasm = builder.build(&project_path, &runtime)?;
+		let report = BuildReport::new(package, builder, wasm)?;
+		let report_path = report.wasm.with_file_name("build-report.json");
+		report.to_file(&report_path)?;
+		cli.info(serde_json::to_string_pretty(&report)?)?;
+
+		let generated_files = [
+			format!("Runtime generated at: {}", report.wasm.display()),
+			format!("Build report generated at: {}", report_path.display()),
+		];
+		let generated_files: Vec<_> = generated_files
+			.iter()
+			.map(|s| style(format!("{} {s}", console::Emoji("●", ">"))).dim().to_string())
+			.collect();
+		cli.success(format!("Generated files:\n{}", generated_files.join("\n")))?;
+		cli.outro(format!(
+			"Need help? Learn more at {}\n",
+			style("https://learn.onpop.io").magenta().underlined()
+		))?;
+		Ok("runtime")
+	}
 }
 
 #[cfg(test)]
@@ -134,6 +183,7 @@ mod tests {
 							package: package.clone(),
 							release,
 							id: None,
+							deterministic: false,
 							valid,
 						}
 						.build(&mut cli)?,
@@ -147,4 +197,23 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn build_deterministic_fails_without_runtime() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let mut cli = MockCli::new().expect_intro("Building your runtime deterministically");
+
+		assert!(BuildParachainCommand {
+			path: Some(temp_dir.path().to_path_buf()),
+			package: None,
+			release: true,
+			id: None,
+			deterministic: true,
+			valid: true,
+		}
+		.build(&mut cli)
+		.is_err());
+
+		cli.verify()
+	}
 }
@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli, style::style};
+use clap::Args;
+use pop_common::Profile;
+use pop_parachains::{max_code_size, Runtime, DEFAULT_MAX_CODE_SIZE};
+use std::path::PathBuf;
+use url::Url;
+
+/// The proportion of the maximum code size of the relay chain above which a warning is shown.
+const CODE_SIZE_WARNING_THRESHOLD: f64 = 0.8;
+
+#[derive(Args)]
+pub struct BuildRuntimeCommand {
+	/// Directory path for your project [default: current directory].
+	#[arg(long)]
+	pub(crate) path: Option<PathBuf>,
+	/// The runtime package to be built, when a project contains multiple runtimes.
+	#[arg(short = 'p', long)]
+	pub(crate) package: Option<String>,
+	/// For production, always build in release mode to exclude debug features.
+	#[clap(short, long)]
+	pub(crate) release: bool,
+	/// The maximum code size of the relay chain, in bytes, used to check the size of the runtime
+	/// [default: 3145728].
+	#[arg(long, conflicts_with = "relay_url")]
+	pub(crate) max_code_size: Option<u32>,
+	/// The websocket endpoint of a relay chain node, from which the maximum code size of the relay
+	/// chain is read.
+	#[arg(long = "relay-url")]
+	pub(crate) relay_url: Option<Url>,
+}
+
+impl BuildRuntimeCommand {
+	/// Executes the command.
+	pub(crate) async fn execute(self) -> anyhow::Result<&'static str> {
+		self.build(&mut cli::Cli).await
+	}
+
+	/// Builds only the runtime of a parachain.
+	///
+	/// # Arguments
+	/// * `cli` - The CLI implementation to be used.
+	async fn build(self, cli: &mut impl cli::traits::Cli) -> anyhow::Result<&'static str> {
+		cli.intro("Building your runtime")?;
+
+		let project_path = self.path.unwrap_or_else(|| PathBuf::from("./"));
+		let runtime = match Runtime::locate(&project_path, self.package.as_deref()) {
+			Ok(runtime) => runtime,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 {e}"))?;
+				return Ok("runtime");
+			},
+		};
+		cli.info(format!("Building `{}` at {}", runtime.package, runtime.path.display()))?;
+		cli.warning("NOTE: this may take some time...")?;
+		let mode: Profile = self.release.into();
+		let artifacts = runtime.build(&project_path, &mode)?;
+		cli.info(format!("The runtime was built in {mode} mode."))?;
+
+		// Check the size of the runtime against the limit of the relay chain
+		let max_code_size = match (self.max_code_size, &self.relay_url) {
+			(Some(max_code_size), _) => max_code_size,
+			(None, Some(url)) => max_code_size(url).await?,
+			(None, None) => DEFAULT_MAX_CODE_SIZE,
+		};
+		if let Some(warning) = code_size_warning(artifacts.compressed_size, max_code_size) {
+			cli.warning(warning)?;
+		}
+
+		let generated_files = [
+			format!(
+				"Compressed runtime ({}) generated at: {}",
+				format_size(artifacts.compressed_size),
+				artifacts.compressed.display()
+			),
+			format!(
+				"Uncompressed runtime ({}) generated at: {}",
+				format_size(artifacts.uncompressed_size),
+				artifacts.uncompressed.display()
+			),
+		];
+		let generated_files: Vec<_> = generated_files
+			.iter()
+			.map(|s| style(format!("{} {s}", console::Emoji("●", ">"))).dim().to_string())
+			.collect();
+		cli.success(format!("Generated files:\n{}", generated_files.join("\n")))?;
+		cli.outro(format!(
+			"Need help? Learn more at {}\n",
+			style("https://learn.onpop.io").magenta().underlined()
+		))?;
+		Ok("runtime")
+	}
+}
+
+/// A warning when the size of a compressed runtime is close to, or exceeds, the maximum code size
+/// of the relay chain.
+///
+/// # Arguments
+/// * `size` - The size of the compressed runtime, in bytes.
+/// * `max_code_size` - The maximum code size of the relay chain, in bytes.
+fn code_size_warning(size: u64, max_code_size: u32) -> Option<String> {
+	let usage = size as f64 / max_code_size as f64;
+	if usage > 1.0 {
+		Some(format!(
+			"⚠️ The compressed runtime ({}) exceeds the maximum code size of the relay chain ({}) and cannot be used.",
+			format_size(size),
+			format_size(max_code_size.into())
+		))
+	} else if usage >= CODE_SIZE_WARNING_THRESHOLD {
+		Some(format!(
+			"⚠️ The compressed runtime ({}) is {:.0}% of the maximum code size of the relay chain ({}).",
+			format_size(size),
+			usage * 100.0,
+			format_size(max_code_size.into())
+		))
+	} else {
+		None
+	}
+}
+
+/// Formats a size in bytes for display.
+///
+/// # Arguments
+/// * `size` - The size, in bytes.
+fn format_size(size: u64) -> String {
+	match size {
+		size if size >= 1024 * 1024 => format!("{:.2} MiB", size as f64 / (1024.0 * 1024.0)),
+		size if size >= 1024 => format!("{:.2} KiB", size as f64 / 1024.0),
+		size => format!("{size} bytes"),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cli::MockCli;
+
+	#[tokio::test]
+	async fn build_handles_missing_runtime() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let mut cli =
+			MockCli::new()
+				.expect_intro("Building your runtime")
+				.expect_outro_cancel(format!(
+					"🚫 Failed to get manifest path: {}",
+					temp_dir.path().join("runtime/Cargo.toml").display()
+				));
+
+		assert_eq!(
+			BuildRuntimeCommand {
+				path: Some(temp_dir.path().to_path_buf()),
+				package: None,
+				release: false,
+				max_code_size: None,
+				relay_url: None,
+			}
+			.build(&mut cli)
+			.await?,
+			"runtime"
+		);
+
+		cli.verify()
+	}
+
+	#[test]
+	fn code_size_warning_works() {
+		assert_eq!(code_size_warning(1024, DEFAULT_MAX_CODE_SIZE), None);
+		assert_eq!(
+			code_size_warning(2_831_155, DEFAULT_MAX_CODE_SIZE),
+			Some(
+				"⚠️ The compressed runtime (2.70 MiB) is 90% of the maximum code size of the relay chain (3.00 MiB)."
+					.into()
+			)
+		);
+		assert_eq!(
+			code_size_warning(4 * 1024 * 1024, DEFAULT_MAX_CODE_SIZE),
+			Some("⚠️ The compressed runtime (4.00 MiB) exceeds the maximum code size of the relay chain (3.00 MiB) and cannot be used.".into())
+		);
+	}
+
+	#[test]
+	fn format_size_works() {
+		assert_eq!(format_size(512), "512 bytes");
+		assert_eq!(format_size(1536), "1.50 KiB");
+		assert_eq!(format_size(3 * 1024 * 1024), "3.00 MiB");
+	}
+}
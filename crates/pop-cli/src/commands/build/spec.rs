@@ -9,9 +9,12 @@ use clap::{Args, ValueEnum};
 use cliclack::{confirm, input};
 use pop_common::Profile;
 use pop_parachains::{
-	binary_path, build_parachain, export_wasm_file, generate_genesis_state_file,
-	generate_plain_chain_spec, generate_raw_chain_spec, is_supported, ChainSpec,
+	binary_path, build_parachain, export_wasm_file, export_wasm_file_from_chain_spec,
+	generate_genesis_state_file, generate_genesis_state_file_from_chain_spec,
+	generate_plain_chain_spec, generate_plain_chain_spec_from_runtime, generate_raw_chain_spec,
+	generate_raw_chain_spec_from_runtime, is_supported, ChainSpec, Collator,
 };
+use serde_json::Value;
 use std::{
 	env::current_dir,
 	fs::create_dir_all,
@@ -158,23 +161,50 @@ pub struct BuildSpecCommand {
 	/// Whether the genesis code file should be generated [default: true].
 	#[clap(long = "genesis-code", default_value = "true")]
 	pub(crate) genesis_code: bool,
+	/// Generate the chain spec from a runtime (WASM) via its `GenesisBuilder` runtime API, rather
+	/// than via the node binary of the project. Neither a node binary nor a parachain project is
+	/// required.
+	#[arg(long)]
+	pub(crate) runtime: Option<PathBuf>,
+	/// The genesis preset of the runtime to be used, such as `development` or `local_testnet`
+	/// [default: the default genesis configuration of the runtime].
+	#[arg(long, requires = "runtime")]
+	pub(crate) preset: Option<String>,
+	/// Set a value within the chain spec, as `path=value`, where `path` is a JSON pointer such as
+	/// `/genesis/runtimeGenesis/patch/sudo/key` and `value` is JSON (otherwise a string).
+	#[arg(long = "set", value_name = "PATH=VALUE", value_parser = parse_set)]
+	pub(crate) set: Vec<(String, Value)>,
+	/// Endow an account with a balance at genesis, as `address=amount`.
+	#[arg(long = "endow", value_name = "ADDRESS=AMOUNT", value_parser = parse_endow)]
+	pub(crate) endow: Vec<(String, u128)>,
+	/// A JSON file containing the collators at genesis, replacing the invulnerables and their
+	/// session keys: `[{"account": "5Grw...", "sessionKeys": {"aura": "5Grw..."}}]`.
+	#[arg(long)]
+	pub(crate) collators: Option<PathBuf>,
+}
+
+/// The means by which chain specifications are generated.
+enum Generator {
+	/// The `build-spec` and related commands of a node binary.
+	Node(PathBuf),
+	/// The `GenesisBuilder` runtime API of a runtime.
+	Runtime(PathBuf),
 }
 
 impl BuildSpecCommand {
 	/// Executes the command.
 	pub(crate) async fn execute(self) -> anyhow::Result<&'static str> {
-		// Checks for appchain project in `./`.
-		if is_supported(None)? {
+		// Checks for appchain project in `./`, unless generating from a runtime.
+		if self.runtime.is_some() || is_supported(None)? {
 			// If para id has been provided we can build the spec
 			// otherwise, we need to guide the user.
-			let _ = match self.id {
+			match self.id {
 				Some(_) => self.build(&mut Cli),
 				None => {
 					let config = guide_user_to_generate_spec(self).await?;
 					config.build(&mut Cli)
 				},
-			};
-			Ok("spec")
+			}
 		} else {
 			Cli.intro("Building your chain spec")?;
 			Cli.outro_cancel(
@@ -191,10 +221,19 @@ impl BuildSpecCommand {
 	fn build(self, cli: &mut impl cli::traits::Cli) -> anyhow::Result<&'static str> {
 		cli.intro("Building your chain spec")?;
 
+		// Parse any collators up front, so that an invalid file is reported before generating.
+		let collators = match self.collators.as_deref().map(Collator::from_file).transpose() {
+			Ok(collators) => collators,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 Could not read the collators: {e}"))?;
+				return Ok("spec");
+			},
+		};
+
 		// Either a para id was already provided or user has been guided to provide one.
 		let para_id = self.id.unwrap_or(DEFAULT_PARA_ID);
 		// Notify user in case we need to build the parachain project.
-		if !self.release {
+		if !self.release && self.runtime.is_none() {
 			cli.warning("NOTE: this command defaults to DEBUG builds for development chain types. Please use `--release` (or simply `-r` for a release build...)")?;
 			#[cfg(not(test))]
 			sleep(Duration::from_secs(3))
@@ -222,22 +261,37 @@ impl BuildSpecCommand {
 		}
 		plain_chain_spec.set_extension("json");
 
-		// Locate binary, if it doesn't exist trigger build.
-		let mode: Profile = self.release.into();
-		let cwd = current_dir().unwrap_or(PathBuf::from("./"));
-		let binary_path = match binary_path(&mode.target_directory(&cwd), &cwd.join("node")) {
-			Ok(binary_path) => binary_path,
-			_ => {
-				cli.info("Node was not found. The project will be built locally.".to_string())?;
-				cli.warning("NOTE: this may take some time...")?;
-				build_parachain(&cwd, None, &mode, None)?
+		// Locate binary, if it doesn't exist trigger build. Not required for a runtime.
+		let generator = match self.runtime {
+			Some(runtime) => Generator::Runtime(runtime),
+			None => {
+				let mode: Profile = self.release.into();
+				let cwd = current_dir().unwrap_or(PathBuf::from("./"));
+				match binary_path(&mode.target_directory(&cwd), &cwd.join("node")) {
+					Ok(binary_path) => Generator::Node(binary_path),
+					_ => {
+						cli.info(
+							"Node was not found. The project will be built locally.".to_string(),
+						)?;
+						cli.warning("NOTE: this may take some time...")?;
+						Generator::Node(build_parachain(&cwd, None, &mode, None)?)
+					},
+				}
 			},
 		};
 
 		// Generate plain spec.
 		spinner.set_message("Generating plain chain specification...");
 		let mut generated_files = vec![];
-		generate_plain_chain_spec(&binary_path, &plain_chain_spec, self.default_bootnode)?;
+		match &generator {
+			Generator::Node(binary_path) =>
+				generate_plain_chain_spec(binary_path, &plain_chain_spec, self.default_bootnode)?,
+			Generator::Runtime(runtime) => generate_plain_chain_spec_from_runtime(
+				runtime,
+				&plain_chain_spec,
+				self.preset.as_deref(),
+			)?,
+		}
 		generated_files.push(format!(
 			"Plain text chain specification file generated at: {}",
 			plain_chain_spec.display()
@@ -254,6 +308,11 @@ impl BuildSpecCommand {
 			let protocol_id = self.protocol_id.unwrap_or(DEFAULT_PROTOCOL_ID.to_string());
 			chain_spec.replace_protocol_id(&protocol_id)?;
 		}
+		if let Err(e) = customize(&mut chain_spec, collators.as_deref(), &self.endow, self.set) {
+			spinner.error("Failed to customize the chain specification");
+			cli.outro_cancel(format!("🚫 {e}"))?;
+			return Ok("spec");
+		}
 		chain_spec.to_file(&plain_chain_spec)?;
 
 		// Generate raw spec.
@@ -264,8 +323,12 @@ impl BuildSpecCommand {
 			.unwrap_or(DEFAULT_SPEC_NAME)
 			.trim_end_matches(".json");
 		let raw_spec_name = format!("{spec_name}-raw.json");
-		let raw_chain_spec =
-			generate_raw_chain_spec(&binary_path, &plain_chain_spec, &raw_spec_name)?;
+		let raw_chain_spec = match &generator {
+			Generator::Node(binary_path) =>
+				generate_raw_chain_spec(binary_path, &plain_chain_spec, &raw_spec_name)?,
+			Generator::Runtime(_) =>
+				generate_raw_chain_spec_from_runtime(&plain_chain_spec, &raw_spec_name)?,
+		};
 		generated_files.push(format!(
 			"Raw chain specification file generated at: {}",
 			raw_chain_spec.display()
@@ -275,7 +338,12 @@ impl BuildSpecCommand {
 		if self.genesis_code {
 			spinner.set_message("Generating genesis code...");
 			let wasm_file_name = format!("para-{}.wasm", para_id);
-			let wasm_file = export_wasm_file(&binary_path, &raw_chain_spec, &wasm_file_name)?;
+			let wasm_file = match &generator {
+				Generator::Node(binary_path) =>
+					export_wasm_file(binary_path, &raw_chain_spec, &wasm_file_name)?,
+				Generator::Runtime(_) =>
+					export_wasm_file_from_chain_spec(&raw_chain_spec, &wasm_file_name)?,
+			};
 			generated_files
 				.push(format!("WebAssembly runtime file exported at: {}", wasm_file.display()));
 		}
@@ -283,8 +351,14 @@ impl BuildSpecCommand {
 		if self.genesis_state {
 			spinner.set_message("Generating genesis state...");
 			let genesis_file_name = format!("para-{}-genesis-state", para_id);
-			let genesis_state_file =
-				generate_genesis_state_file(&binary_path, &raw_chain_spec, &genesis_file_name)?;
+			let genesis_state_file = match &generator {
+				Generator::Node(binary_path) =>
+					generate_genesis_state_file(binary_path, &raw_chain_spec, &genesis_file_name)?,
+				Generator::Runtime(_) => generate_genesis_state_file_from_chain_spec(
+					&raw_chain_spec,
+					&genesis_file_name,
+				)?,
+			};
 			generated_files
 				.push(format!("Genesis State file exported at: {}", genesis_state_file.display()));
 		}
@@ -304,6 +378,31 @@ impl BuildSpecCommand {
 	}
 }
 
+/// Customizes a chain specification with any collators, endowments and values provided.
+///
+/// # Arguments
+/// * `chain_spec` - The chain specification to be customized.
+/// * `collators` - The collators at genesis, if provided.
+/// * `endow` - The accounts to be endowed, along with their balances.
+/// * `set` - The values to be set, keyed by JSON pointer.
+fn customize(
+	chain_spec: &mut ChainSpec,
+	collators: Option<&[Collator]>,
+	endow: &[(String, u128)],
+	set: Vec<(String, Value)>,
+) -> anyhow::Result<()> {
+	if let Some(collators) = collators {
+		chain_spec.replace_collators(collators)?;
+	}
+	for (account, amount) in endow {
+		chain_spec.replace_balance(account, *amount)?;
+	}
+	for (path, value) in set {
+		chain_spec.set(&path, value)?;
+	}
+	Ok(())
+}
+
 /// Guide the user to generate their chain specification.
 async fn guide_user_to_generate_spec(args: BuildSpecCommand) -> anyhow::Result<BuildSpecCommand> {
 	Cli.intro("Generate your chain spec")?;
@@ -432,7 +531,7 @@ async fn guide_user_to_generate_spec(args: BuildSpecCommand) -> anyhow::Result<B
 
 	// Only check user to check their profile selection if a live spec is being built on debug mode.
 	let profile =
-		if !args.release && matches!(chain_type, ChainType::Live) {
+		if !args.release && args.runtime.is_none() && matches!(chain_type, ChainType::Live) {
 			confirm("Using Debug profile to build a Live specification. Should Release be used instead ?")
     		.initial_value(true)
     		.interact()?
@@ -450,5 +549,118 @@ async fn guide_user_to_generate_spec(args: BuildSpecCommand) -> anyhow::Result<B
 		protocol_id: Some(protocol_id),
 		genesis_state,
 		genesis_code,
+		runtime: args.runtime,
+		preset: args.preset,
+		set: args.set,
+		endow: args.endow,
+		collators: args.collators,
 	})
 }
+
+/// Parses a `path=value` assignment, with the value being JSON or otherwise a string.
+fn parse_set(input: &str) -> Result<(String, Value), String> {
+	let (path, value) = input
+		.split_once('=')
+		.ok_or_else(|| format!("invalid assignment `{input}`, expected `path=value`"))?;
+	let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+	Ok((path.to_string(), value))
+}
+
+/// Parses an `address=amount` endowment.
+fn parse_endow(input: &str) -> Result<(String, u128), String> {
+	let (address, amount) = input
+		.split_once('=')
+		.ok_or_else(|| format!("invalid endowment `{input}`, expected `address=amount`"))?;
+	let amount = amount.parse().map_err(|e| format!("invalid amount `{amount}`: {e}"))?;
+	Ok((address.to_string(), amount))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cli::MockCli;
+	use serde_json::json;
+
+	#[test]
+	fn build_handles_invalid_collators() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let collators = temp_dir.path().join("collators.json");
+		std::fs::write(&collators, "[{\"account\": \"5GrwvaEF\"}]")?;
+		let error = Collator::from_file(&collators).unwrap_err();
+		let mut cli = MockCli::new()
+			.expect_intro("Building your chain spec")
+			.expect_outro_cancel(format!("🚫 Could not read the collators: {error}"));
+
+		let command = BuildSpecCommand {
+			output_file: Some(temp_dir.path().join("chain-spec.json")),
+			release: true,
+			id: Some(2000),
+			default_bootnode: true,
+			chain_type: None,
+			relay: None,
+			protocol_id: None,
+			genesis_state: true,
+			genesis_code: true,
+			runtime: None,
+			preset: None,
+			set: vec![],
+			endow: vec![],
+			collators: Some(collators),
+		};
+		assert_eq!(command.build(&mut cli)?, "spec");
+		assert!(!temp_dir.path().join("chain-spec.json").exists());
+		cli.verify()
+	}
+
+	#[test]
+	fn customize_works() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("chain-spec.json");
+		std::fs::write(&path, json!({"properties": {"tokenDecimals": 12}}).to_string())?;
+		let mut chain_spec = ChainSpec::from(&path)?;
+		customize(
+			&mut chain_spec,
+			None,
+			&[],
+			vec![("/properties/tokenDecimals".into(), json!(10))],
+		)?;
+		chain_spec.to_file(&path)?;
+		let contents: Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+		assert_eq!(contents, json!({"properties": {"tokenDecimals": 10}}));
+		// Failures are surfaced, rather than ignored
+		assert!(customize(
+			&mut chain_spec,
+			None,
+			&[],
+			vec![("/properties/tokenDecimals/value".into(), json!(1))]
+		)
+		.is_err());
+		assert!(customize(&mut chain_spec, None, &[("5GrwvaEF".into(), 1)], vec![]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn parse_set_works() {
+		assert_eq!(
+			parse_set("/properties/tokenDecimals=10"),
+			Ok(("/properties/tokenDecimals".to_string(), json!(10)))
+		);
+		assert_eq!(
+			parse_set("/genesis/runtimeGenesis/patch/sudo/key=5GrwvaEF"),
+			Ok(("/genesis/runtimeGenesis/patch/sudo/key".to_string(), json!("5GrwvaEF")))
+		);
+		assert_eq!(parse_set("/bootNodes=[]"), Ok(("/bootNodes".to_string(), json!([]))));
+		assert!(parse_set("/bootNodes").is_err());
+	}
+
+	#[test]
+	fn parse_endow_works() {
+		assert_eq!(parse_endow("5GrwvaEF=1000"), Ok(("5GrwvaEF".to_string(), 1000)));
+		assert_eq!(
+			parse_endow(&format!("5GrwvaEF={}", u128::MAX)),
+			Ok(("5GrwvaEF".to_string(), u128::MAX))
+		);
+		assert!(parse_endow("5GrwvaEF").is_err());
+		assert!(parse_endow("5GrwvaEF=-1").is_err());
+	}
+}
@@ -3,9 +3,10 @@
 use crate::{cli::traits::*, style::style};
 use anyhow::Result;
 use clap::{Args, Subcommand};
+use pop_common::sourcing::cache::{Manifest, MANIFEST};
 use std::{
 	fs::{read_dir, remove_file},
-	path::PathBuf,
+	path::{Path, PathBuf},
 };
 
 #[derive(Args)]
@@ -88,17 +89,24 @@ impl<'a, CLI: Cli> CleanCacheCommand<'a, CLI> {
 				// confirm removal
 				remove_file(file)?;
 			}
+			forget(&self.cache, contents.iter().map(|(name, _, _)| name.as_str()))?;
 
 			self.cli.outro(format!("ℹ️ {} artifacts removed", contents.len()))?;
 		} else {
 			// Prompt for selection of artifacts to be removed
+			let manifest = Manifest::load(&self.cache).unwrap_or_default();
 			let selected = {
 				let mut prompt = self
 					.cli
 					.multiselect("Select the artifacts you wish to remove:")
 					.required(false);
 				for (name, path, size) in &contents {
-					prompt = prompt.item(path, name, format!("{}MiB", size / 1_048_576))
+					let size = format!("{}MiB", size / 1_048_576);
+					let hint = match manifest.get(name) {
+						Some(artifact) => format!("{size}, sourced from {}", artifact.source),
+						None => size,
+					};
+					prompt = prompt.item(path, name, hint)
 				}
 				prompt.interact()?
 			};
@@ -124,6 +132,10 @@ impl<'a, CLI: Cli> CleanCacheCommand<'a, CLI> {
 			for file in &selected {
 				remove_file(file)?
 			}
+			forget(
+				&self.cache,
+				selected.iter().filter_map(|f| f.file_name().and_then(|f| f.to_str())),
+			)?;
 
 			self.cli.outro(format!("ℹ️ {} artifacts removed", selected.len()))?;
 		}
@@ -132,7 +144,20 @@ impl<'a, CLI: Cli> CleanCacheCommand<'a, CLI> {
 	}
 }
 
-/// Returns the contents of the specified path.
+/// Removes any provenance recorded within the cache manifest for the specified files.
+fn forget<'a>(cache: &Path, files: impl Iterator<Item = &'a str>) -> Result<()> {
+	if !cache.join(MANIFEST).exists() {
+		return Ok(());
+	}
+	Manifest::update(cache, &(), |manifest| {
+		for file in files {
+			manifest.remove(file);
+		}
+	})?;
+	Ok(())
+}
+
+/// Returns the contents of the specified path, excluding the cache manifest.
 fn contents(path: &PathBuf) -> Result<Vec<(String, PathBuf, u64)>> {
 	let mut contents: Vec<_> = read_dir(path)?
 		.filter_map(|e| {
@@ -144,7 +169,7 @@ fn contents(path: &PathBuf) -> Result<Vec<(String, PathBuf, u64)>> {
 					.map(|f| (f.0 .0, f.0 .1, f.1.len()))
 			})
 		})
-		.filter(|(name, _, _)| !name.starts_with('.'))
+		.filter(|(name, _, _)| !name.starts_with('.') && name != MANIFEST)
 		.collect();
 	contents.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
 	Ok(contents)
@@ -366,6 +391,50 @@ mod tests {
 		cli.verify()
 	}
 
+	#[test]
+	fn clean_cache_prompts_with_provenance() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let cache = temp.path().to_path_buf();
+		File::create(cache.join("polkadot-v1.12.0"))?;
+		File::create(cache.join("pop-node"))?;
+		let manifest = r#"{
+			"artifacts": {
+				"polkadot-v1.12.0": {
+					"binary": "polkadot",
+					"version": "v1.12.0",
+					"source": "https://github.com/paritytech/polkadot-sdk/releases/download/polkadot-v1.12.0/polkadot",
+					"digest": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+					"size": 0,
+					"sourced_at": 0,
+					"target": null
+				}
+			}
+		}"#;
+		std::fs::write(cache.join(MANIFEST), manifest)?;
+		let items = vec![
+			(
+				"polkadot-v1.12.0".to_string(),
+				"0MiB, sourced from https://github.com/paritytech/polkadot-sdk/releases/download/polkadot-v1.12.0/polkadot".to_string(),
+			),
+			("pop-node".to_string(), "0MiB".to_string()),
+		];
+		let mut cli = MockCli::new()
+			.expect_multiselect::<PathBuf>(
+				"Select the artifacts you wish to remove:",
+				Some(false),
+				true,
+				Some(items),
+			)
+			.expect_confirm("Are you sure you want to remove the 2 selected artifacts?", true)
+			.expect_outro("ℹ️ 2 artifacts removed");
+
+		CleanCacheCommand { cli: &mut cli, cache: cache.clone(), all: false }.execute()?;
+
+		assert!(cache.join(MANIFEST).exists());
+		assert_eq!(Manifest::load(&cache)?.artifacts().count(), 0);
+		cli.verify()
+	}
+
 	#[test]
 	fn contents_works() -> Result<()> {
 		use std::fs::File;
@@ -375,6 +444,7 @@ mod tests {
 		for file in &files {
 			File::create(cache.join(file))?;
 		}
+		File::create(cache.join(MANIFEST))?;
 		files.sort();
 
 		let contents = contents(&cache)?;
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{NetworkEntry, Registry};
+use std::{thread::sleep, time::Duration};
+
+/// The time to wait for a network to be torn down by its supervising process.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Args)]
+pub(crate) struct DownArgs {
+	/// The name of a network launched with `pop up parachain --detach`.
+	#[arg(required_unless_present = "all")]
+	pub(crate) name: Option<String>,
+	/// Tear down all networks running in the background.
+	#[arg(short, long, conflicts_with = "name")]
+	pub(crate) all: bool,
+}
+
+/// Tears down networks running in the background.
+pub(crate) struct DownCommand<'a, CLI: Cli> {
+	/// The cli to be used.
+	pub(crate) cli: &'a mut CLI,
+	/// The registry of networks running in the background.
+	pub(crate) registry: Registry,
+	/// The name of the network to be torn down.
+	pub(crate) name: Option<String>,
+	/// Whether to tear down all networks.
+	pub(crate) all: bool,
+}
+
+impl<'a, CLI: Cli> DownCommand<'a, CLI> {
+	/// Executes the command.
+	pub(crate) fn execute(self) -> Result<()> {
+		self.cli.intro("Tear down a network")?;
+
+		let networks = match (&self.name, self.all) {
+			(Some(name), false) => match self.registry.get(name)? {
+				Some(network) => vec![network],
+				None => {
+					self.cli.outro_cancel(format!("🚫 No network named `{name}` was found."))?;
+					return Ok(());
+				},
+			},
+			_ => self.registry.list()?,
+		};
+		if networks.is_empty() {
+			self.cli.outro("ℹ️ No networks are running in the background.")?;
+			return Ok(());
+		}
+
+		for network in &networks {
+			if !tear_down(network, TIMEOUT)? {
+				self.cli.warning(format!(
+					"⚠️ The process supervising `{}` (pid {}) did not exit in time, so its nodes were terminated directly.",
+					network.name, network.pid
+				))?;
+			}
+			self.registry.remove(&network.name)?;
+			self.cli.success(format!("✅ Network `{}` torn down", network.name))?;
+		}
+		self.cli.outro(format!("ℹ️ {} network(s) torn down", networks.len()))?;
+		Ok(())
+	}
+}
+
+/// Signals the process supervising a network to tear it down, waiting for it to exit before
+/// terminating any remaining nodes. Returns whether the supervising process exited in time.
+///
+/// # Arguments
+/// * `network` - The network to be torn down.
+/// * `timeout` - The time to wait for the supervising process to exit.
+fn tear_down(network: &NetworkEntry, timeout: Duration) -> Result<bool> {
+	const INTERVAL: Duration = Duration::from_millis(250);
+	let mut exited = true;
+	if network.running() {
+		network.stop()?;
+		let mut waited = Duration::ZERO;
+		while network.running() {
+			if waited >= timeout {
+				exited = false;
+				break;
+			}
+			sleep(INTERVAL);
+			waited += INTERVAL;
+		}
+	}
+	network.kill()?;
+	Ok(exited)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		cli::MockCli,
+		commands::status::tests::{network, supervisor},
+	};
+	use std::process::Command;
+
+	#[test]
+	fn down_handles_unknown_network() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let mut cli = MockCli::new()
+			.expect_intro("Tear down a network")
+			.expect_outro_cancel("🚫 No network named `pop` was found.");
+
+		DownCommand {
+			cli: &mut cli,
+			registry: Registry::new(temp.path()),
+			name: Some("pop".into()),
+			all: false,
+		}
+		.execute()?;
+
+		cli.verify()
+	}
+
+	#[test]
+	fn down_handles_no_networks() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let mut cli = MockCli::new().expect_outro("ℹ️ No networks are running in the background.");
+
+		DownCommand { cli: &mut cli, registry: Registry::new(temp.path()), name: None, all: true }
+			.execute()?;
+
+		cli.verify()
+	}
+
+	#[test]
+	fn down_tears_down_networks() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let registry = Registry::new(temp.path());
+		// Simulate a supervising process, reaping it once terminated
+		let mut supervisor = supervisor("pop")?;
+		let pid = supervisor.id();
+		let reaper = std::thread::spawn(move || supervisor.wait());
+		// Simulate an unrelated process, which reuses the identifiers of a stale network
+		let mut unrelated = Command::new("sleep").arg("60").spawn()?;
+		for (name, pid) in [("pop", pid), ("stale", unrelated.id())] {
+			let mut network = network(name, pid);
+			network.nodes[0].pid = (name == "stale").then(|| unrelated.id());
+			network.nodes[0].base_path = Some("/tmp/zombie-1/alice".into());
+			registry.register(&network)?;
+		}
+		let mut cli = MockCli::new()
+			.expect_success("✅ Network `pop` torn down")
+			.expect_success("✅ Network `stale` torn down")
+			.expect_outro("ℹ️ 2 network(s) torn down");
+
+		DownCommand { cli: &mut cli, registry, name: None, all: true }.execute()?;
+
+		assert!(!reaper.join().unwrap()?.success());
+		assert!(unrelated.try_wait()?.is_none());
+		assert!(Registry::new(temp.path()).list()?.is_empty());
+		unrelated.kill()?;
+		unrelated.wait()?;
+		cli.verify()
+	}
+}
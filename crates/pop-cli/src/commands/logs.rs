@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::Result;
+use clap::Args;
+use console::Style;
+use pop_parachains::{LogFilter, LogFollower, LogLevel, NetworkEntry, NetworkInfo, Registry};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+use tokio::time::sleep;
+
+/// The interval at which logs are read when following them.
+const INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Args)]
+pub(crate) struct LogsArgs {
+	/// The name of a node of a network launched with `pop up parachain --detach`. The logs of all
+	/// nodes of the network are shown when not specified.
+	pub(crate) node: Option<String>,
+	/// The name of the network. Only required when multiple networks are running in the
+	/// background.
+	#[arg(short, long)]
+	pub(crate) network: Option<String>,
+	/// Only show lines matching the specified regular expression.
+	#[arg(short, long)]
+	pub(crate) grep: Option<String>,
+	/// Only show lines of the specified level or above. Lines without a level, such as those
+	/// output when a node panics, are always shown.
+	#[arg(short, long, value_enum)]
+	pub(crate) level: Option<LogLevel>,
+	/// Continue to show lines as they are written, until ctrl-c.
+	#[arg(short, long)]
+	pub(crate) follow: bool,
+}
+
+/// Shows the logs of the nodes of a network running in the background.
+pub(crate) struct LogsCommand<'a, CLI: Cli> {
+	/// The cli to be used.
+	pub(crate) cli: &'a mut CLI,
+	/// The registry of networks running in the background.
+	pub(crate) registry: Registry,
+	/// The arguments of the command.
+	pub(crate) args: LogsArgs,
+}
+
+impl<'a, CLI: Cli> LogsCommand<'a, CLI> {
+	/// Executes the command.
+	pub(crate) async fn execute(self) -> Result<()> {
+		self.cli.intro("Network logs")?;
+
+		let filter = match LogFilter::new(self.args.grep.as_deref(), self.args.level) {
+			Ok(filter) => filter,
+			Err(e) => {
+				self.cli.outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
+		let network = match self.network()? {
+			Ok(network) => network,
+			Err(message) => {
+				self.cli.outro_cancel(format!("🚫 {message}"))?;
+				return Ok(());
+			},
+		};
+		let nodes: Vec<_> = NetworkInfo::load(&network.base_dir)?
+			.nodes()
+			.filter(|n| self.args.node.as_ref().is_none_or(|name| &n.name == name))
+			.map(|n| (n.name.clone(), n.log_path.clone()))
+			.collect();
+
+		if self.args.follow {
+			self.cli.info("Following logs - ctrl-c to stop")?;
+			tokio::select! {
+				result = output(nodes, filter, true) => result?,
+				result = tokio::signal::ctrl_c() => result?,
+			}
+			self.cli.outro("Done")?;
+		} else {
+			output(nodes, filter, false).await?;
+			self.cli.outro(format!("ℹ️ Logs of `{}` shown", network.name))?;
+		}
+		Ok(())
+	}
+
+	/// Resolves the network whose logs are to be shown, or a message describing why it could
+	/// not be resolved.
+	fn network(&self) -> Result<std::result::Result<NetworkEntry, String>> {
+		if let Some(name) = &self.args.network {
+			return Ok(self
+				.registry
+				.get(name)?
+				.ok_or_else(|| format!("No network named `{name}` was found.")));
+		}
+		let mut networks = self.registry.list()?;
+		if let Some(node) = &self.args.node {
+			networks.retain(|n| n.nodes.iter().any(|n| &n.name == node));
+		}
+		let names = || networks.iter().map(|n| n.name.as_str()).collect::<Vec<_>>().join(", ");
+		Ok(match (networks.len(), &self.args.node) {
+			(1, _) => Ok(networks.remove(0)),
+			(0, None) => Err("No networks are running in the background.".into()),
+			(0, Some(node)) => Err(format!("No node named `{node}` was found.")),
+			(_, None) => Err(format!(
+				"Multiple networks are running in the background: {}. Use `--network` to specify one.",
+				names()
+			)),
+			(_, Some(node)) => Err(format!(
+				"A node named `{node}` was found in multiple networks: {}. Use `--network` to specify one.",
+				names()
+			)),
+		})
+	}
+}
+
+/// Outputs the lines of the logs of the specified nodes, each prefixed by the name of its node
+/// in a distinct color.
+///
+/// # Arguments
+/// * `nodes` - The names of the nodes and the paths of their logs.
+/// * `filter` - The filter applied to the lines.
+/// * `follow` - Whether lines continue to be output as they are written.
+pub(crate) async fn output(
+	nodes: Vec<(String, PathBuf)>,
+	filter: LogFilter,
+	follow: bool,
+) -> Result<()> {
+	let prefixes = prefixes(nodes.iter().map(|(name, _)| name.as_str()));
+	let mut follower = LogFollower::new(nodes, filter);
+	loop {
+		for line in follower.read()? {
+			println!("{} {}", prefixes[&line.node], line.line);
+		}
+		if !follow {
+			return Ok(());
+		}
+		sleep(INTERVAL).await;
+	}
+}
+
+/// The prefix of the lines of each node, padded to a common width.
+///
+/// # Arguments
+/// * `names` - The names of the nodes.
+fn prefixes<'a>(names: impl Iterator<Item = &'a str> + Clone) -> HashMap<String, String> {
+	let styles = [
+		Style::new().cyan(),
+		Style::new().magenta(),
+		Style::new().green(),
+		Style::new().yellow(),
+		Style::new().blue(),
+		Style::new().red(),
+	];
+	let width = names.clone().map(|n| n.len()).max().unwrap_or_default();
+	names
+		.enumerate()
+		.map(|(i, name)| {
+			let style = &styles[i % styles.len()];
+			(name.to_string(), style.apply_to(format!("{name:width$} |")).to_string())
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{cli::MockCli, commands::status::tests::network};
+
+	fn args(node: Option<&str>, network: Option<&str>) -> LogsArgs {
+		LogsArgs {
+			node: node.map(String::from),
+			network: network.map(String::from),
+			grep: None,
+			level: None,
+			follow: false,
+		}
+	}
+
+	#[tokio::test]
+	async fn logs_handles_no_networks() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let mut cli = MockCli::new()
+			.expect_intro("Network logs")
+			.expect_outro_cancel("🚫 No networks are running in the background.");
+
+		LogsCommand { cli: &mut cli, registry: Registry::new(temp.path()), args: args(None, None) }
+			.execute()
+			.await?;
+
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn logs_handles_unknown_node_and_network() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let registry = Registry::new(temp.path());
+		registry.register(&network("pop", std::process::id()))?;
+
+		let mut cli =
+			MockCli::new().expect_outro_cancel("🚫 No node named `collator-01` was found.");
+		LogsCommand { cli: &mut cli, registry, args: args(Some("collator-01"), None) }
+			.execute()
+			.await?;
+		cli.verify()?;
+
+		let mut cli = MockCli::new().expect_outro_cancel("🚫 No network named `other` was found.");
+		LogsCommand {
+			cli: &mut cli,
+			registry: Registry::new(temp.path()),
+			args: args(None, Some("other")),
+		}
+		.execute()
+		.await?;
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn logs_handles_ambiguous_node() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let registry = Registry::new(temp.path());
+		registry.register(&network("pop", std::process::id()))?;
+		registry.register(&network("other", std::process::id()))?;
+		let mut cli = MockCli::new().expect_outro_cancel(
+			"🚫 A node named `alice` was found in multiple networks: other, pop. Use `--network` to specify one.",
+		);
+
+		LogsCommand { cli: &mut cli, registry, args: args(Some("alice"), None) }
+			.execute()
+			.await?;
+
+		cli.verify()
+	}
+
+	#[test]
+	fn prefixes_works() {
+		console::set_colors_enabled(false);
+		let prefixes = prefixes(["alice", "collator-01"].into_iter());
+		assert_eq!(prefixes["alice"], "alice       |");
+		assert_eq!(prefixes["collator-01"], "collator-01 |");
+	}
+}
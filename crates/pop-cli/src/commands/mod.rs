@@ -4,14 +4,28 @@ use crate::{cache, cli::Cli};
 use clap::Subcommand;
 use pop_common::templates::Template;
 use serde_json::{json, Value};
+#[cfg(feature = "parachain")]
+use {crate::data_dir, pop_parachains::Registry};
 
 pub(crate) mod build;
 pub(crate) mod call;
 pub(crate) mod clean;
+#[cfg(feature = "parachain")]
+pub(crate) mod down;
 pub(crate) mod install;
+#[cfg(feature = "parachain")]
+pub(crate) mod logs;
 pub(crate) mod new;
+#[cfg(feature = "parachain")]
+pub(crate) mod register;
+#[cfg(feature = "parachain")]
+pub(crate) mod spec;
+#[cfg(feature = "parachain")]
+pub(crate) mod status;
 pub(crate) mod test;
 pub(crate) mod up;
+#[cfg(feature = "parachain")]
+pub(crate) mod upgrade;
 
 #[derive(Subcommand)]
 #[command(subcommand_required = true)]
@@ -34,6 +48,28 @@ pub(crate) enum Command {
 	#[clap(alias = "u")]
 	#[cfg(any(feature = "parachain", feature = "contract"))]
 	Up(up::UpArgs),
+	/// Register a parachain on a running relay chain.
+	#[clap(alias = "r")]
+	#[cfg(feature = "parachain")]
+	Register(register::RegisterArgs),
+	/// Upgrade the runtime of a running chain.
+	#[cfg(feature = "parachain")]
+	Upgrade(upgrade::UpgradeArgs),
+	/// Tear down a local network running in the background.
+	#[clap(alias = "d")]
+	#[cfg(feature = "parachain")]
+	Down(down::DownArgs),
+	/// List or inspect local networks running in the background.
+	#[clap(alias = "s")]
+	#[cfg(feature = "parachain")]
+	Status(status::StatusArgs),
+	/// Show the logs of the nodes of a local network running in the background.
+	#[clap(alias = "l")]
+	#[cfg(feature = "parachain")]
+	Logs(logs::LogsArgs),
+	/// Inspect or compare chain specifications.
+	#[cfg(feature = "parachain")]
+	Spec(spec::SpecArgs),
 	/// Test a smart contract.
 	#[clap(alias = "t")]
 	#[cfg(feature = "contract")]
@@ -94,6 +130,8 @@ impl Command {
 					build::Command::Contract(cmd) => cmd.execute().map(|_| Value::Null),
 					#[cfg(feature = "parachain")]
 					build::Command::Spec(cmd) => cmd.execute().await.map(|_| Value::Null),
+					#[cfg(feature = "parachain")]
+					build::Command::Runtime(cmd) => cmd.execute().await.map(|_| Value::Null),
 				},
 			},
 			#[cfg(feature = "contract")]
@@ -107,6 +145,48 @@ impl Command {
 				#[cfg(feature = "contract")]
 				up::Command::Contract(cmd) => cmd.execute().await.map(|_| Value::Null),
 			},
+			#[cfg(feature = "parachain")]
+			Self::Register(args) => match args.command {
+				register::Command::Parachain(cmd) => cmd.execute().await.map(|_| Value::Null),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Upgrade(args) => match args.command {
+				upgrade::Command::Runtime(cmd) => cmd.execute().await.map(|_| Value::Null),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Down(args) => down::DownCommand {
+				cli: &mut Cli,
+				registry: Registry::new(&data_dir()?),
+				name: args.name,
+				all: args.all,
+			}
+			.execute()
+			.map(|_| Value::Null),
+			#[cfg(feature = "parachain")]
+			Self::Status(args) => status::StatusCommand {
+				cli: &mut Cli,
+				registry: Registry::new(&data_dir()?),
+				name: args.name,
+			}
+			.execute()
+			.map(|_| Value::Null),
+			#[cfg(feature = "parachain")]
+			Self::Logs(args) =>
+				logs::LogsCommand { cli: &mut Cli, registry: Registry::new(&data_dir()?), args }
+					.execute()
+					.await
+					.map(|_| Value::Null),
+			#[cfg(feature = "parachain")]
+			Self::Spec(args) => match args.command {
+				spec::Command::Diff(args) =>
+					spec::DiffCommand { cli: &mut Cli, left: args.left, right: args.right }
+						.execute()
+						.map(|_| Value::Null),
+				spec::Command::Inspect(args) =>
+					spec::InspectCommand { cli: &mut Cli, path: args.path }
+						.execute()
+						.map(|_| Value::Null),
+			},
 			#[cfg(feature = "contract")]
 			Self::Test(args) => match args.command {
 				test::Command::Contract(cmd) => match cmd.execute().await {
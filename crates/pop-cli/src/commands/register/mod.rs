@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use clap::{Args, Subcommand};
+
+mod parachain;
+
+/// Arguments for registering with a running relay chain.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub(crate) struct RegisterArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Register with a running relay chain.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Register a parachain on a running relay chain and track it until onboarded. Parachains
+	/// registered via the registrar are onboarded as on-demand parachains (parathreads), which
+	/// only produce blocks once coretime is acquired, such as via `--order`.
+	#[clap(alias = "p")]
+	Parachain(parachain::RegisterParachainCommand),
+}
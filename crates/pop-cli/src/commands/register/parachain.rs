@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{commands::up::parachain::SpinnerReporter, style::style};
+use clap::Args;
+use cliclack::{intro, log, outro, outro_cancel};
+use pop_parachains::{read_genesis_artifact, Registration, RegistrationMethod};
+use std::{path::PathBuf, time::Duration};
+use url::Url;
+
+#[derive(Args)]
+pub(crate) struct RegisterParachainCommand {
+	/// The identifier of the parachain, as used when generating its chain specification via
+	/// `pop build spec`.
+	#[arg(short, long)]
+	id: u32,
+	/// Websocket endpoint of a node of the relay chain.
+	#[arg(short, long)]
+	url: Url,
+	/// The genesis state file of the parachain [default: ./para-{id}-genesis-state].
+	#[arg(long)]
+	genesis_state: Option<PathBuf>,
+	/// The genesis code file of the parachain [default: ./para-{id}.wasm].
+	#[arg(long)]
+	genesis_code: Option<PathBuf>,
+	/// Secret key URI of the account used to register the parachain, e.g.
+	/// - for a dev account "//Alice"
+	/// - with a password "//Alice///SECRET_PASSWORD"
+	///
+	/// The parachain is initialized directly when this is the sudo account of the relay chain,
+	/// otherwise it is reserved and registered via the registrar.
+	#[arg(short, long, default_value = "//Alice")]
+	suri: String,
+	/// The maximum time to wait for the parachain to be onboarded, in seconds.
+	#[arg(long, default_value_t = 600)]
+	timeout: u64,
+	/// The maximum amount to pay for an on-demand order, placed once a parachain registered via
+	/// the registrar is onboarded so that it can produce a block. Parachains registered via the
+	/// registrar otherwise require coretime to be acquired separately.
+	#[arg(long)]
+	order: Option<u128>,
+}
+
+impl RegisterParachainCommand {
+	/// Executes the command.
+	pub(crate) async fn execute(self) -> anyhow::Result<()> {
+		intro(format!("{}: Register a parachain", style(" Pop CLI ").black().on_magenta()))?;
+
+		// Read the genesis artifacts generated via `pop build spec`
+		let id = self.id;
+		let genesis_state = self
+			.genesis_state
+			.unwrap_or_else(|| PathBuf::from(format!("para-{id}-genesis-state")));
+		let genesis_code =
+			self.genesis_code.unwrap_or_else(|| PathBuf::from(format!("para-{id}.wasm")));
+		let mut artifacts = Vec::new();
+		for path in [&genesis_state, &genesis_code] {
+			match read_genesis_artifact(path) {
+				Ok(artifact) => artifacts.push(artifact),
+				Err(e) => {
+					outro_cancel(format!(
+						"🚫 Could not read {}: {e}. Use `pop build spec` to generate the genesis artifacts.",
+						path.display()
+					))?;
+					return Ok(());
+				},
+			}
+		}
+		let (head, code) = (artifacts.remove(0), artifacts.remove(0));
+		log::info(format!(
+			"ℹ️ Using the genesis state at {} and the genesis code at {}",
+			genesis_state.display(),
+			genesis_code.display()
+		))?;
+
+		let spinner = cliclack::spinner();
+		spinner.start(format!("Connecting to {}...", self.url));
+		let registration = match Registration::new(&self.url, &self.suri, id, head, code).await {
+			Ok(registration) => registration,
+			Err(e) => {
+				spinner.error(format!("🚫 Could not connect to {}", self.url));
+				outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
+
+		// Register the parachain
+		spinner.start(format!("Registering parachain {id}..."));
+		let method = match registration.register(&SpinnerReporter(spinner.clone())).await {
+			Ok(method) => method,
+			Err(e) => {
+				spinner.error(format!("🚫 Could not register parachain {id}"));
+				outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
+		spinner.stop(format!(
+			"📝 Parachain {id} registered via {}",
+			match method {
+				RegistrationMethod::Sudo => "sudo",
+				RegistrationMethod::Registrar => "the registrar",
+			}
+		));
+
+		// Track the parachain until onboarded
+		let spinner = cliclack::spinner();
+		spinner.start("Waiting for the parachain to be onboarded...");
+		let timeout = Duration::from_secs(self.timeout);
+		if let Err(e) = registration
+			.wait_until_onboarded(method, timeout, &SpinnerReporter(spinner.clone()))
+			.await
+		{
+			spinner.error(format!("🚫 Parachain {id} was not onboarded"));
+			outro_cancel(format!("🚫 {e}"))?;
+			return Ok(());
+		}
+		spinner.stop(format!("✅ Parachain {id} onboarded as a {}", method.onboarded()));
+
+		// Place an on-demand order, so that a parachain onboarded as a parathread can produce a
+		// block
+		let ordered = match (method, self.order) {
+			(RegistrationMethod::Registrar, Some(max_amount)) => {
+				let spinner = cliclack::spinner();
+				spinner.start("Placing an on-demand order...");
+				if let Err(e) =
+					registration.place_order(max_amount, &SpinnerReporter(spinner.clone())).await
+				{
+					spinner
+						.error(format!("🚫 Could not place an on-demand order for parachain {id}"));
+					outro_cancel(format!("🚫 {e}"))?;
+					return Ok(());
+				}
+				spinner.stop(format!("🛒 On-demand order placed for parachain {id}"));
+				true
+			},
+			_ => false,
+		};
+		outro(hint(id, method, ordered))?;
+		Ok(())
+	}
+}
+
+/// A hint of what follows the onboarding of a parachain.
+///
+/// # Arguments
+/// * `id` - The identifier of the parachain.
+/// * `method` - The method used to register the parachain.
+/// * `ordered` - Whether an on-demand order was placed for the parachain.
+fn hint(id: u32, method: RegistrationMethod, ordered: bool) -> String {
+	match (method, ordered) {
+		(RegistrationMethod::Sudo, _) =>
+			format!("Parachain {id} is ready to produce blocks once its collators are running."),
+		(RegistrationMethod::Registrar, true) => format!(
+			"Parachain {id} can produce a block for the on-demand order once its collators are running. Further blocks require further on-demand orders (`--order`) or bulk coretime."
+		),
+		(RegistrationMethod::Registrar, false) => format!(
+			"Parachain {id} requires coretime to produce blocks, such as by placing on-demand orders (`--order`) or purchasing bulk coretime."
+		),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn hint_works() {
+		assert_eq!(
+			hint(2000, RegistrationMethod::Sudo, false),
+			"Parachain 2000 is ready to produce blocks once its collators are running."
+		);
+		assert!(hint(2000, RegistrationMethod::Registrar, false)
+			.starts_with("Parachain 2000 requires coretime"));
+		assert!(hint(2000, RegistrationMethod::Registrar, true)
+			.starts_with("Parachain 2000 can produce a block for the on-demand order"));
+	}
+}
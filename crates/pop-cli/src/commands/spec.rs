@@ -0,0 +1,263 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use pop_parachains::{ChainSpec, Difference, Inspection, StorageChanges};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Arguments for inspecting or comparing chain specifications.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub(crate) struct SpecArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Inspect or compare chain specifications.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Compare two chain specifications, such as before and after a runtime upgrade.
+	#[clap(alias = "d")]
+	Diff(DiffArgs),
+	/// Show a summary of a chain specification. The well-known storage items of a raw chain
+	/// specification are decoded using the metadata of its runtime.
+	#[clap(alias = "i")]
+	Inspect(InspectArgs),
+}
+
+#[derive(Args)]
+pub(crate) struct DiffArgs {
+	/// The first chain specification.
+	pub(crate) left: PathBuf,
+	/// The second chain specification.
+	pub(crate) right: PathBuf,
+}
+
+#[derive(Args)]
+pub(crate) struct InspectArgs {
+	/// The chain specification.
+	pub(crate) path: PathBuf,
+}
+
+/// Shows a summary of a chain specification.
+pub(crate) struct InspectCommand<'a, CLI: Cli> {
+	/// The cli to be used.
+	pub(crate) cli: &'a mut CLI,
+	/// The chain specification.
+	pub(crate) path: PathBuf,
+}
+
+impl<'a, CLI: Cli> InspectCommand<'a, CLI> {
+	/// Executes the command.
+	pub(crate) fn execute(self) -> Result<()> {
+		self.cli.intro("Inspect chain specification")?;
+		let inspection = match inspect(&self.path) {
+			Ok(inspection) => inspection,
+			Err(e) => {
+				self.cli.outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
+		self.cli.info(serde_json::to_string_pretty(&inspection)?)?;
+		self.cli.outro(format!("ℹ️ {} inspected", self.path.display()))?;
+		Ok(())
+	}
+}
+
+/// Compares two chain specifications.
+pub(crate) struct DiffCommand<'a, CLI: Cli> {
+	/// The cli to be used.
+	pub(crate) cli: &'a mut CLI,
+	/// The first chain specification.
+	pub(crate) left: PathBuf,
+	/// The second chain specification.
+	pub(crate) right: PathBuf,
+}
+
+impl<'a, CLI: Cli> DiffCommand<'a, CLI> {
+	/// Executes the command.
+	pub(crate) fn execute(self) -> Result<()> {
+		self.cli.intro("Compare chain specifications")?;
+		let compared = inspect(&self.left).and_then(|left| {
+			let right = inspect(&self.right)?;
+			Ok((left.diff(&right)?, left, right))
+		});
+		let (differences, left, right) = match compared {
+			Ok(compared) => compared,
+			Err(e) => {
+				self.cli.outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
+		// The digest of any raw genesis storage is included, so that storage which is not
+		// decoded is also compared
+		if differences.is_empty() {
+			self.cli.outro("✅ The chain specifications are equivalent")?;
+			return Ok(());
+		}
+
+		// Differences are grouped by the top-level fields, the runtime code, the genesis and the
+		// raw genesis storage
+		for name in ["Fields", "Code", "Genesis", "Storage"] {
+			let mut lines: Vec<_> =
+				differences.iter().filter(|d| section(&d.path) == name).map(format).collect();
+			if let (Some(left), Some(right), "Storage") = (&left.storage, &right.storage, name) {
+				let changes = left.changes(right);
+				if changes != StorageChanges::default() {
+					lines.push(format!(
+						"{} added, {} removed, {} changed storage entries",
+						changes.added, changes.removed, changes.changed
+					));
+				}
+			}
+			if !lines.is_empty() {
+				self.cli.info(format!("{name}:\n{}", lines.join("\n")))?;
+			}
+		}
+		self.cli.outro(format!("ℹ️ {} difference(s) found", differences.len()))?;
+		Ok(())
+	}
+}
+
+/// Loads and inspects a chain specification.
+///
+/// # Arguments
+/// * `path` - The chain specification.
+fn inspect(path: &Path) -> Result<Inspection> {
+	let chain_spec = ChainSpec::from(path)
+		.map_err(|e| anyhow::anyhow!("Failed to load {}: {e}", path.display()))?;
+	Ok(chain_spec.inspect()?)
+}
+
+/// The section of a chain specification containing a difference: its top-level fields, its
+/// runtime code, its genesis or its raw genesis storage.
+///
+/// # Arguments
+/// * `path` - The location of the difference, as a JSON pointer.
+fn section(path: &str) -> &'static str {
+	match path {
+		"/codeHash" => "Code",
+		path if path == "/genesis" || path.starts_with("/genesis/") => "Genesis",
+		path if path == "/storage" || path.starts_with("/storage/") => "Storage",
+		_ => "Fields",
+	}
+}
+
+/// Formats a difference as a single line, marking removed (`-`), added (`+`) and changed (`~`)
+/// values.
+///
+/// # Arguments
+/// * `difference` - The difference.
+fn format(difference: &Difference) -> String {
+	let value = |v: &Value| v.to_string();
+	match (&difference.left, &difference.right) {
+		(Some(left), Some(right)) =>
+			format!("~ {}: {} → {}", difference.path, value(left), value(right)),
+		(Some(left), None) => format!("- {}: {}", difference.path, value(left)),
+		(None, Some(right)) => format!("+ {}: {}", difference.path, value(right)),
+		(None, None) => format!("~ {}", difference.path),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use serde_json::json;
+	use std::fs::write;
+
+	fn chain_spec(path: &Path, name: &str, balance: u128) -> Result<()> {
+		write(
+			path,
+			json!({
+				"name": name,
+				"id": "pop",
+				"genesis": { "runtimeGenesis": {
+					"code": "0x0001",
+					"patch": { "balances": { "balances": [["alice", balance]] } }
+				}}
+			})
+			.to_string(),
+		)?;
+		Ok(())
+	}
+
+	#[test]
+	fn inspect_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("spec.json");
+		chain_spec(&path, "Pop", 1)?;
+		let inspection = ChainSpec::from(&path)?.inspect()?;
+		let mut cli = MockCli::new()
+			.expect_intro("Inspect chain specification")
+			.expect_info(serde_json::to_string_pretty(&inspection)?)
+			.expect_outro(format!("ℹ️ {} inspected", path.display()));
+
+		InspectCommand { cli: &mut cli, path }.execute()?;
+
+		cli.verify()
+	}
+
+	#[test]
+	fn inspect_handles_missing_chain_spec() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("spec.json");
+		let mut cli = MockCli::new()
+			.expect_intro("Inspect chain specification")
+			.expect_outro_cancel(format!(
+				"🚫 Failed to load {}: No such file or directory (os error 2)",
+				path.display()
+			));
+
+		InspectCommand { cli: &mut cli, path }.execute()?;
+
+		cli.verify()
+	}
+
+	#[test]
+	fn diff_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let left = temp_dir.path().join("left.json");
+		chain_spec(&left, "Pop", 1)?;
+		let right = temp_dir.path().join("right.json");
+		chain_spec(&right, "Pop Testnet", 2)?;
+		let mut cli = MockCli::new()
+			.expect_intro("Compare chain specifications")
+			.expect_info("Fields:\n~ /name: \"Pop\" → \"Pop Testnet\"")
+			.expect_info("Genesis:\n~ /genesis/balances/balances/0/1: 1 → 2")
+			.expect_outro("ℹ️ 2 difference(s) found");
+
+		DiffCommand { cli: &mut cli, left: left.clone(), right }.execute()?;
+		cli.verify()?;
+
+		let mut cli = MockCli::new()
+			.expect_intro("Compare chain specifications")
+			.expect_outro("✅ The chain specifications are equivalent");
+		DiffCommand { cli: &mut cli, left: left.clone(), right: left }.execute()?;
+		cli.verify()
+	}
+
+	#[test]
+	fn section_works() {
+		assert_eq!(section("/name"), "Fields");
+		assert_eq!(section("/genesisHash"), "Fields");
+		assert_eq!(section("/codeHash"), "Code");
+		assert_eq!(section("/genesis"), "Genesis");
+		assert_eq!(section("/genesis/sudo/key"), "Genesis");
+		assert_eq!(section("/storage/digest"), "Storage");
+	}
+
+	#[test]
+	fn format_works() {
+		let difference = |left: Option<Value>, right: Option<Value>| Difference {
+			path: "/codeHash".into(),
+			left,
+			right,
+		};
+		assert_eq!(format(&difference(Some(json!("0x01")), None)), "- /codeHash: \"0x01\"");
+		assert_eq!(format(&difference(None, Some(json!(1)))), "+ /codeHash: 1");
+		assert_eq!(format(&difference(Some(json!([])), Some(json!({})))), "~ /codeHash: [] → {}");
+	}
+}
@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{NetworkEntry, Registry};
+
+#[derive(Args)]
+pub(crate) struct StatusArgs {
+	/// The name of a network launched with `pop up parachain --detach`. All networks are listed
+	/// when not specified.
+	pub(crate) name: Option<String>,
+}
+
+/// Lists or inspects networks running in the background.
+pub(crate) struct StatusCommand<'a, CLI: Cli> {
+	/// The cli to be used.
+	pub(crate) cli: &'a mut CLI,
+	/// The registry of networks running in the background.
+	pub(crate) registry: Registry,
+	/// The name of the network to be inspected, if any.
+	pub(crate) name: Option<String>,
+}
+
+impl<'a, CLI: Cli> StatusCommand<'a, CLI> {
+	/// Executes the command.
+	pub(crate) fn execute(self) -> Result<()> {
+		self.cli.intro("Network status")?;
+
+		// Inspect the specified network
+		if let Some(name) = &self.name {
+			let Some(network) = self.registry.get(name)? else {
+				self.cli.outro_cancel(format!("🚫 No network named `{name}` was found."))?;
+				return Ok(());
+			};
+			self.cli.info(summary(&network))?;
+			self.cli.info(format!("ℹ️ Launched from {}", network.config.display()))?;
+			self.cli.info(format!("ℹ️ Base directory: {}", network.base_dir.display()))?;
+			for node in &network.nodes {
+				let pid = node.pid.map_or(String::new(), |pid| format!(", pid {pid}"));
+				self.cli.info(format!("{} ({}): {}{pid}", node.name, node.chain, node.ws_uri))?;
+			}
+			self.cli.outro(hint(&network))?;
+			return Ok(());
+		}
+
+		// Otherwise list all networks
+		let networks = self.registry.list()?;
+		if networks.is_empty() {
+			self.cli.outro("ℹ️ No networks are running in the background.")?;
+			return Ok(());
+		}
+		for network in &networks {
+			self.cli.info(summary(network))?;
+		}
+		self.cli.outro(format!("ℹ️ {} network(s) found", networks.len()))?;
+		Ok(())
+	}
+}
+
+/// A one line summary of the state of a network.
+fn summary(network: &NetworkEntry) -> String {
+	let state = match network.running() {
+		true => format!("🟢 running (pid {})", network.pid),
+		false => "🔴 stopped".to_string(),
+	};
+	format!("{}: {state}, {} node(s)", network.name, network.nodes.len())
+}
+
+/// A hint of how the network can be torn down.
+fn hint(network: &NetworkEntry) -> String {
+	match network.running() {
+		true => format!("Use `pop down {}` to tear down the network.", network.name),
+		false => format!(
+			"The network is no longer supervised. Use `pop down {}` to remove it.",
+			network.name
+		),
+	}
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use pop_parachains::NodeEntry;
+	use std::path::PathBuf;
+
+	pub(crate) fn network(name: &str, pid: u32) -> NetworkEntry {
+		NetworkEntry {
+			name: name.to_string(),
+			pid,
+			config: PathBuf::from("/pop/network.toml"),
+			base_dir: PathBuf::from("/tmp/zombie-1"),
+			started_at: 0,
+			nodes: vec![NodeEntry {
+				name: "alice".to_string(),
+				chain: "paseo-local".to_string(),
+				ws_uri: "ws://127.0.0.1:9944".to_string(),
+				pid: Some(42),
+				base_path: None,
+			}],
+		}
+	}
+
+	// Simulates the process supervising a network, with the command line of the current
+	// executable.
+	pub(crate) fn supervisor(name: &str) -> Result<std::process::Child> {
+		use std::os::unix::process::CommandExt;
+		Ok(std::process::Command::new("sh")
+			.arg0(std::env::current_exe()?)
+			.args(["-c", "while :; do sleep 1; done", "--supervise", "--name", name])
+			.spawn()?)
+	}
+
+	#[test]
+	fn status_handles_no_networks() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let mut cli = MockCli::new()
+			.expect_intro("Network status")
+			.expect_outro("ℹ️ No networks are running in the background.");
+
+		StatusCommand { cli: &mut cli, registry: Registry::new(temp.path()), name: None }
+			.execute()?;
+
+		cli.verify()
+	}
+
+	#[test]
+	fn status_lists_networks() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let registry = Registry::new(temp.path());
+		let mut supervisor = supervisor("pop")?;
+		let pid = supervisor.id();
+		registry.register(&network("pop", pid))?;
+		registry.register(&network("stale", u32::MAX >> 1))?;
+		let mut cli = MockCli::new()
+			.expect_info(format!("pop: 🟢 running (pid {pid}), 1 node(s)"))
+			.expect_info("stale: 🔴 stopped, 1 node(s)")
+			.expect_outro("ℹ️ 2 network(s) found");
+
+		StatusCommand { cli: &mut cli, registry, name: None }.execute()?;
+
+		supervisor.kill()?;
+		supervisor.wait()?;
+		cli.verify()
+	}
+
+	#[test]
+	fn status_inspects_network() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let registry = Registry::new(temp.path());
+		let mut supervisor = supervisor("pop")?;
+		let pid = supervisor.id();
+		registry.register(&network("pop", pid))?;
+		let mut cli = MockCli::new()
+			.expect_info(format!("pop: 🟢 running (pid {pid}), 1 node(s)"))
+			.expect_info("ℹ️ Launched from /pop/network.toml")
+			.expect_info("ℹ️ Base directory: /tmp/zombie-1")
+			.expect_info("alice (paseo-local): ws://127.0.0.1:9944, pid 42")
+			.expect_outro("Use `pop down pop` to tear down the network.");
+
+		StatusCommand { cli: &mut cli, registry, name: Some("pop".into()) }.execute()?;
+
+		supervisor.kill()?;
+		supervisor.wait()?;
+		cli.verify()
+	}
+
+	#[test]
+	fn status_handles_unknown_network() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let mut cli = MockCli::new().expect_outro_cancel("🚫 No network named `pop` was found.");
+
+		StatusCommand {
+			cli: &mut cli,
+			registry: Registry::new(temp.path()),
+			name: Some("pop".into()),
+		}
+		.execute()?;
+
+		cli.verify()
+	}
+}
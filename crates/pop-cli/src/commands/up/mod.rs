@@ -5,7 +5,7 @@ use clap::{Args, Subcommand};
 #[cfg(feature = "contract")]
 mod contract;
 #[cfg(feature = "parachain")]
-mod parachain;
+pub(crate) mod parachain;
 
 /// Arguments for launching or deploying.
 #[derive(Args)]
@@ -1,23 +1,41 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::style::{style, Theme};
-use clap::Args;
+use crate::{
+	commands::logs,
+	style::{style, Theme},
+};
+use clap::{Args, ValueEnum};
 use cliclack::{
 	clear_screen, confirm, intro, log, multi_progress, outro, outro_cancel, set_theme, ProgressBar,
 	Theme as _, ThemeState,
 };
 use console::{Emoji, Style, Term};
 use duct::cmd;
-use pop_common::Status;
-use pop_parachains::{Error, IndexSet, NetworkNode, Zombienet};
-use std::{path::Path, time::Duration};
+use pop_common::{
+	sourcing::{Binary, Error as SourcingError},
+	Status,
+};
+use pop_parachains::{
+	generate_network_config, is_supported, Error, Fork, Hooks, HrmpChannel, IndexSet, LogFilter,
+	NetworkEntry, NetworkInfo, NetworkSnapshot, NodeInfo, Readiness, Registry, Zombienet,
+};
+use std::{
+	env::current_dir,
+	ffi::OsString,
+	fs::{create_dir_all, File},
+	path::Path,
+	process::Stdio,
+	time::Duration,
+};
 use tokio::time::sleep;
 
 #[derive(Args)]
 pub(crate) struct ZombienetCommand {
-	/// The Zombienet network configuration file to be used.
+	/// The Zombienet network configuration file to be used, in TOML, JSON (`.json`) or YAML
+	/// (`.yaml`/`.yml`) format. If not specified within a parachain project, a default network
+	/// configuration is generated for the project.
 	#[arg(short, long)]
-	file: String,
+	file: Option<String>,
 	/// The version of the binary to be used for the relay chain, as per the release tag (e.g.
 	/// "v1.13.0"). See https://github.com/paritytech/polkadot-sdk/releases for more details.
 	#[arg(short, long)]
@@ -39,6 +57,23 @@ pub(crate) struct ZombienetCommand {
 	/// A specific binary name can also be optionally specified via query string parameter (e.g. 'https://github.com/org/repository?binaryname#ref'), defaulting to the name of the repository when not specified.
 	#[arg(short, long)]
 	parachain: Option<Vec<String>>,
+	/// Open bidirectional HRMP channels between parachains at genesis, specified as
+	/// `sender:recipient` (e.g. "2000:1000").
+	#[arg(long, value_delimiter = ',')]
+	hrmp: Option<Vec<HrmpChannel>>,
+	/// Fork a live chain as a parachain of the network, specified as `id=url`, with the block to
+	/// be forked optionally specified as #fragment (e.g. '2000=wss://rpc.example.io#0x1234...').
+	/// Defaults to the latest finalized block when not specified.
+	#[arg(long)]
+	fork: Option<Vec<Fork>>,
+	/// Save a snapshot of the state of the network once terminated, under the specified name.
+	/// The network can then be relaunched from the snapshot via `--restore`.
+	#[arg(long, conflicts_with = "check")]
+	snapshot: Option<String>,
+	/// Relaunch the network from the snapshot of the specified name, previously saved via
+	/// `--snapshot`, with each node continuing from its saved database rather than from genesis.
+	#[arg(long, conflicts_with_all = ["check", "fork"])]
+	restore: Option<String>,
 	/// The command to run after the network has been launched.
 	#[clap(name = "cmd", short = 'c', long)]
 	command: Option<String>,
@@ -48,20 +83,174 @@ pub(crate) struct ZombienetCommand {
 	/// Automatically source all needed binaries required without prompting for confirmation.
 	#[clap(short('y'), long)]
 	skip_confirm: bool,
+	/// Leave the network running in the background once launched. Running networks can be
+	/// inspected with `pop status` and torn down with `pop down`.
+	#[arg(short, long)]
+	detach: bool,
+	/// The name used to identify a detached network. Defaults to the name of the network
+	/// configuration file.
+	#[arg(long, requires = "detach")]
+	name: Option<String>,
+	/// Supervise the network on behalf of a detached launch.
+	#[arg(long, hide = true, requires = "detach")]
+	supervise: bool,
+	/// The format used to output the details of the launched network. The details are also
+	/// written to `network.json` within the base directory of the network.
+	#[arg(short, long, value_enum, default_value_t)]
+	output: OutputFormat,
+	/// Output the logs of all nodes once launched, each line prefixed by the name of its node.
+	/// The logs of a detached network can be shown with `pop logs`.
+	#[arg(long, conflicts_with_all = ["detach", "check"])]
+	follow: bool,
+	/// Wait until the network is ready for use before completing the launch: the relay chain
+	/// having produced (`best`) or finalized (`finalized`) the number of blocks specified by
+	/// `--wait-blocks`, and each parachain a block.
+	#[arg(long, value_enum)]
+	wait_until: Option<WaitUntil>,
+	/// The number of relay chain blocks to wait for when using `--wait-until`.
+	#[arg(long, default_value_t = 1, requires = "wait_until")]
+	wait_blocks: u32,
+	/// The maximum time to wait for the network to be ready, in seconds.
+	#[arg(long, default_value_t = 300, requires = "wait_until")]
+	wait_timeout: u64,
+	/// Validate the network configuration file without sourcing any binaries or launching the
+	/// network, reporting all problems found.
+	#[arg(long, conflicts_with = "detach")]
+	check: bool,
+	/// The relay chain network to be launched when generating a network configuration for the
+	/// current project, such as `paseo-local`. Not to be confused with `--relay-chain`, which
+	/// specifies the version of its binary.
+	#[arg(long, default_value = "paseo-local", conflicts_with = "file")]
+	relay_network: String,
+	/// The number of relay chain validators when generating a network configuration for the
+	/// current project.
+	#[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..), conflicts_with = "file")]
+	validators: u8,
+	/// The number of parachain collators when generating a network configuration for the current
+	/// project.
+	#[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..), conflicts_with = "file")]
+	collators: u8,
+}
+
+/// The conditions which can be waited for before a launched network is considered ready.
+#[derive(Clone, Copy, ValueEnum)]
+pub(crate) enum WaitUntil {
+	/// Blocks have been produced.
+	Best,
+	/// Blocks have been finalized.
+	Finalized,
+}
+
+/// The formats in which the details of a launched network can be output.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub(crate) enum OutputFormat {
+	/// Human-readable text.
+	#[default]
+	Text,
+	/// JSON, written to stdout.
+	Json,
 }
 
 impl ZombienetCommand {
 	/// Executes the command.
 	pub(crate) async fn execute(self) -> anyhow::Result<()> {
-		clear_screen()?;
+		if !self.supervise {
+			clear_screen()?;
+		}
 		intro(format!("{}: Launch a local network", style(" Pop CLI ").black().on_magenta()))?;
 		set_theme(Theme);
 
+		// Generate a network configuration for the current project, if not specified
+		let file = match &self.file {
+			Some(file) => file.clone(),
+			None => {
+				if !matches!(is_supported(None), Ok(true)) {
+					outro_cancel("🚫 No network configuration file was specified. Use `--file`, or run the command within a parachain project to launch it with a default network.")?;
+					return Ok(());
+				}
+				match generate_network_config(
+					&current_dir()?,
+					&self.relay_network,
+					self.validators,
+					self.collators,
+				) {
+					Ok(file) => {
+						log::info(format!(
+							"ℹ️ Using a network configuration generated for the current project: {}",
+							file.display()
+						))?;
+						file.display().to_string()
+					},
+					Err(Error::Config(message)) => {
+						outro_cancel(format!("🚫 A configuration error occurred: `{message}`"))?;
+						return Ok(());
+					},
+					Err(Error::MissingBinary(name)) => {
+						outro_cancel(format!("🚫 The `{name}` binary could not be found. Use `pop build --release` to build the project first."))?;
+						return Ok(());
+					},
+					Err(e) => return Err(e.into()),
+				}
+			},
+		};
+
+		// Validate the network configuration only, if requested
+		if self.check {
+			let problems = pop_parachains::check(Path::new(&file), self.parachain.as_ref())?;
+			if problems.is_empty() {
+				outro(format!("✅ {file} is valid"))?;
+			} else {
+				for problem in &problems {
+					match problem.position {
+						Some(_) => log::error(format!("{file}:{problem}"))?,
+						None => log::error(format!("{file}: {problem}"))?,
+					}
+				}
+				outro_cancel(format!("🚫 {} problem(s) found in {file}", problems.len()))?;
+			}
+			return Ok(());
+		}
+
+		// Ensure a detached network can be uniquely identified
+		let registry = Registry::new(&crate::data_dir()?);
+		let name = match self.detach {
+			true => {
+				let name = match &self.name {
+					Some(name) => name.clone(),
+					None => Path::new(&file)
+						.file_stem()
+						.and_then(|s| s.to_str())
+						.unwrap_or("network")
+						.to_string(),
+				};
+				if !self.supervise && registry.get(&name)?.is_some_and(|n| n.running()) {
+					outro_cancel(format!("🚫 A network named `{name}` is already running. Use `pop down {name}` to tear it down, or specify a different `--name`."))?;
+					return Ok(());
+				}
+				Some(name)
+			},
+			false => None,
+		};
+
 		// Parse arguments
 		let cache = crate::cache()?;
+		let snapshots = cache.join("snapshots");
+		let (snapshot, restore) = match (
+			self.snapshot
+				.as_deref()
+				.map(|n| NetworkSnapshot::new(&snapshots, n))
+				.transpose(),
+			self.restore.as_deref().map(|n| NetworkSnapshot::new(&snapshots, n)).transpose(),
+		) {
+			(Ok(snapshot), Ok(restore)) => (snapshot, restore),
+			(Err(e), _) | (_, Err(e)) => {
+				outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
 		let mut zombienet = match Zombienet::new(
 			&cache,
-			&self.file,
+			&file,
 			self.relay_chain.as_deref(),
 			self.relay_chain_runtime.as_deref(),
 			self.system_parachain.as_deref(),
@@ -69,7 +258,10 @@ impl ZombienetCommand {
 			self.parachain.as_ref(),
 		)
 		.await
-		{
+		.and_then(|mut zombienet| {
+			zombienet.open_hrmp_channels(self.hrmp.as_deref().unwrap_or_default())?;
+			Ok(zombienet)
+		}) {
 			Ok(n) => n,
 			Err(e) =>
 				return match e {
@@ -90,64 +282,130 @@ impl ZombienetCommand {
 			return Ok(());
 		}
 
+		// Launch the network from a background process, which supervises it until torn down
+		if let (Some(name), false) = (&name, self.supervise) {
+			return self.detach(name, &registry).await;
+		}
+
+		// Fork any live chains from a snapshot of their state
+		for fork in self.fork.iter().flatten() {
+			let spinner = cliclack::spinner();
+			spinner.start(format!("🍴 Forking {}...", fork.url));
+			let status = ProgressReporter(format!("🍴 Forking {}: ", fork.url), spinner.clone());
+			match zombienet.fork(fork, &cache.join("forks"), &status).await {
+				Ok(path) => spinner.stop(format!(
+					"🍴 Forked {} as parachain {}: {}",
+					fork.url,
+					fork.para_id,
+					style(path.display()).dim()
+				)),
+				Err(e) => {
+					spinner.error(format!("🚫 Could not fork {}", fork.url));
+					outro_cancel(format!("🚫 {e}"))?;
+					return Ok(());
+				},
+			}
+		}
+
+		// Relaunch the network from a snapshot of its state, if requested
+		if let (Some(name), Some(snapshot)) = (&self.restore, &restore) {
+			if !snapshot.exists() {
+				outro_cancel(format!(
+					"🚫 No snapshot named `{name}` was found. Use `--snapshot {name}` to save one when the network is terminated."
+				))?;
+				return Ok(());
+			}
+			if let Err(e) = zombienet.restore(snapshot) {
+				outro_cancel(format!("🚫 Could not restore snapshot `{name}`: {e}"))?;
+				return Ok(());
+			}
+			log::info(format!(
+				"📸 Restoring snapshot `{name}`: {}",
+				style(snapshot.path().display()).dim()
+			))?;
+		}
+
 		// Finally spawn network and wait for signal to terminate
 		let spinner = cliclack::spinner();
 		spinner.start("🚀 Launching local network...");
 		match zombienet.spawn().await {
 			Ok(network) => {
-				let mut result =
-					"🚀 Network launched successfully - ctrl-c to terminate".to_string();
-				let base_dir = network.base_dir().expect("base_dir expected to exist");
-				let bar = Style::new().magenta().dim().apply_to(Emoji("│", "|"));
-
-				let output = |node: &NetworkNode| -> String {
-					let name = node.name();
-					let mut output = format!(
-						"\n{bar}       {name}:
-{bar}         portal: https://polkadot.js.org/apps/?rpc={}#/explorer
-{bar}         logs: tail -f {base_dir}/{name}/{name}.log",
-						node.ws_uri(),
-					);
-					if self.verbose {
-						output += &format!(
-							"\n{bar}         command: {} {}",
-							node.spec().command(),
-							node.args().join(" ")
-						);
+				// Wait until the network is ready for use, if requested
+				if let Some(wait_until) = self.wait_until {
+					spinner.set_message("⏳ Waiting for the network to be ready...");
+					let readiness = match wait_until {
+						WaitUntil::Best => Readiness::Best(self.wait_blocks),
+						WaitUntil::Finalized => Readiness::Finalized(self.wait_blocks),
+					};
+					let timeout = Duration::from_secs(self.wait_timeout);
+					if let Err(e) = Zombienet::wait_until(&network, readiness, timeout).await {
+						spinner.error("🚫 The network did not become ready");
+						outro_cancel(format!("🚫 {e}"))?;
+						return Ok(());
 					}
-					output
-				};
-				// Add relay info
-				let mut validators = network.relaychain().nodes();
-				validators.sort_by_key(|n| n.name());
-				result.push_str(&format!("\n{bar}  ⛓️ {}", network.relaychain().chain()));
-				for node in validators {
-					result.push_str(&output(node));
 				}
-				// Add parachain info
-				let mut parachains = network.parachains();
-				parachains.sort_by_key(|p| p.para_id());
-				for parachain in network.parachains() {
-					result.push_str(&format!(
-						"\n{bar}  ⛓️ {}",
-						parachain.chain_id().map_or(
-							format!("para_id: {}", parachain.para_id()),
-							|chain| format!("{chain}: {}", parachain.para_id())
-						)
-					));
-					let mut collators = parachain.collators();
-					collators.sort_by_key(|n| n.name());
-					for node in collators {
-						result.push_str(&output(node));
-					}
+
+				// Describe the network for consumption by other tooling
+				let info = NetworkInfo::new(&network);
+				info.save()?;
+				if let Some(name) = &name {
+					let config = Path::new(&file).canonicalize()?;
+					registry.register(&NetworkEntry::new(name, &config, &network))?;
 				}
 
 				if let Some(command) = &self.command {
-					run_custom_command(&spinner, command).await?;
+					run_custom_command(&spinner, command, self.wait_until.is_none()).await?;
 				}
 
-				spinner.stop(result);
-				tokio::signal::ctrl_c().await?;
+				self.output(
+					&spinner,
+					"🚀 Network launched successfully - ctrl-c to terminate",
+					&info,
+				)?;
+
+				// Output the logs of all nodes, if requested
+				let follower = self.follow.then(|| {
+					let nodes =
+						info.nodes().map(|n| (n.name.clone(), n.log_path.clone())).collect();
+					tokio::spawn(logs::output(nodes, LogFilter::default(), true))
+				});
+
+				// Run any hooks until signalled to terminate
+				let hooks = zombienet.hooks();
+				let env = Hooks::env(&info);
+				tokio::select! {
+					result = hooks.run(&network, &env) => {
+						if let Err(e) = result {
+							log::error(format!("🚫 {e}"))?;
+						}
+						terminated().await?;
+					},
+					result = terminated() => result?,
+				}
+				if let Some(follower) = follower {
+					follower.abort();
+				}
+				if let Err(e) = hooks.shutdown(&env).await {
+					log::error(format!("🚫 {e}"))?;
+				}
+				// Save a snapshot of the state of the network, if requested
+				if let (Some(name), Some(snapshot)) = (&self.snapshot, &snapshot) {
+					let spinner = cliclack::spinner();
+					spinner.start(format!("📸 Saving snapshot `{name}`..."));
+					match zombienet.snapshot(network, snapshot).await {
+						Ok(()) => spinner.stop(format!(
+							"📸 Snapshot `{name}` saved: {}",
+							style(snapshot.path().display()).dim()
+						)),
+						Err(e) => {
+							spinner.error(format!("🚫 Could not save snapshot `{name}`"));
+							log::error(format!("🚫 {e}"))?;
+						},
+					}
+				}
+				if let Some(name) = &name {
+					registry.remove(name)?;
+				}
 				outro("Done")?;
 			},
 			Err(e) => {
@@ -158,6 +416,82 @@ impl ZombienetCommand {
 		Ok(())
 	}
 
+	/// Launches the network in the background, by re-invoking the current command as a process
+	/// which supervises the network until signalled to terminate.
+	///
+	/// # Arguments
+	/// * `name` - The name used to identify the network.
+	/// * `registry` - The registry of networks running in the background.
+	async fn detach(&self, name: &str, registry: &Registry) -> anyhow::Result<()> {
+		let spinner = cliclack::spinner();
+		spinner.start("🚀 Launching local network in the background...");
+
+		// Any binaries have already been sourced, so the supervisor runs without prompting
+		let mut args: Vec<OsString> = std::env::args_os().skip(1).collect();
+		args.push("--supervise".into());
+		if !self.skip_confirm {
+			args.push("--skip-confirm".into());
+		}
+		if self.name.is_none() {
+			args.extend(["--name".into(), name.into()]);
+		}
+		create_dir_all(registry.path())?;
+		let log_path = registry.log(name)?;
+		let log = File::create(&log_path)?;
+		let mut command = std::process::Command::new(std::env::current_exe()?);
+		command.args(args).stdin(Stdio::null()).stdout(log.try_clone()?).stderr(log);
+		// Detach from the process group of the shell, so the network outlives it
+		#[cfg(unix)]
+		std::os::unix::process::CommandExt::process_group(&mut command, 0);
+		let mut supervisor = command.spawn()?;
+
+		// Wait for the supervisor to register the launched network
+		let network = loop {
+			if let Some(status) = supervisor.try_wait()? {
+				spinner.error(format!("Supervising process exited: {status}"));
+				outro_cancel(format!(
+					"🚫 Could not launch local network. See {} for details.",
+					log_path.display()
+				))?;
+				return Ok(());
+			}
+			if let Some(network) = registry.get(name)?.filter(|n| n.pid == supervisor.id()) {
+				break network;
+			}
+			sleep(Duration::from_millis(500)).await;
+		};
+
+		let info = NetworkInfo::load(&network.base_dir)?;
+		self.output(&spinner, &format!("🚀 Network `{name}` launched in the background"), &info)?;
+		outro(format!(
+			"Use `pop status {name}` to inspect the network and `pop down {name}` to tear it down."
+		))?;
+		Ok(())
+	}
+
+	/// Outputs the details of a launched network, in the requested format.
+	///
+	/// # Arguments
+	/// * `spinner` - The spinner used to report the launch of the network.
+	/// * `message` - The message to be displayed once launched.
+	/// * `info` - The details of the launched network.
+	fn output(
+		&self,
+		spinner: &ProgressBar,
+		message: &str,
+		info: &NetworkInfo,
+	) -> anyhow::Result<()> {
+		match self.output {
+			OutputFormat::Text =>
+				spinner.stop(format!("{message}{}", format_network(info, self.verbose))),
+			OutputFormat::Json => {
+				spinner.stop(message);
+				println!("{}", serde_json::to_string_pretty(info)?);
+			},
+		}
+		Ok(())
+	}
+
 	async fn source_binaries(
 		zombienet: &mut Zombienet,
 		cache: &Path,
@@ -278,6 +612,7 @@ impl ZombienetCommand {
 		let release = true;
 		match verbose {
 			true => {
+				// Source binaries sequentially so that verbose output remains readable
 				let reporter = VerboseReporter;
 				for binary in binaries {
 					log::info(format!("📦 Sourcing {}...", binary.name()))?;
@@ -293,27 +628,9 @@ impl ZombienetCommand {
 				reporter.update("");
 			},
 			false => {
-				let multi = multi_progress("📦 Sourcing binaries...".to_string());
-				let queue: Vec<_> = binaries
-					.into_iter()
-					.map(|binary| {
-						let progress = multi.add(cliclack::spinner());
-						progress.start(format!("{}: waiting...", binary.name()));
-						(binary, progress)
-					})
-					.collect();
-				let mut error = false;
-				for (binary, progress) in queue {
-					let prefix = format!("{}: ", binary.name());
-					let progress_reporter = ProgressReporter(prefix, progress);
-					if let Err(e) = binary.source(release, &progress_reporter, verbose).await {
-						progress_reporter.1.error(format!("🚫 {}: {e}", binary.name()));
-						error = true;
-					}
-					progress_reporter.1.stop(format!("✅  {}", binary.name()));
-				}
-				multi.stop();
-				if error {
+				let binaries = binaries.into_iter().map(|binary| binary.clone());
+				let results = source_concurrently(binaries, release, verbose).await;
+				if results.iter().any(|result| result.is_err()) {
 					outro_cancel(
 						"🚫 Cannot launch the network until all required binaries are available.",
 					)?;
@@ -326,9 +643,59 @@ impl ZombienetCommand {
 	}
 }
 
-async fn run_custom_command(spinner: &ProgressBar, command: &str) -> Result<(), anyhow::Error> {
+/// Sources binaries concurrently, reporting the progress of each via its own spinner. A failure to
+/// source one binary is reported without cancelling the sourcing of the others. Any builds are
+/// limited by the sourcing itself (see `POP_BUILD_JOBS`).
+///
+/// # Arguments
+/// * `binaries` - The binaries to be sourced.
+/// * `release` - Whether any binaries needing to be built should be done so using the release
+///   profile.
+/// * `verbose` - Whether verbose output is required.
+async fn source_concurrently(
+	binaries: impl IntoIterator<Item = Binary>,
+	release: bool,
+	verbose: bool,
+) -> Vec<anyhow::Result<()>> {
+	let multi = multi_progress("📦 Sourcing binaries...".to_string());
+	let tasks: Vec<_> = binaries
+		.into_iter()
+		.map(|binary| {
+			let progress = multi.add(cliclack::spinner());
+			progress.start(format!("{}: waiting...", binary.name()));
+			tokio::spawn(async move {
+				let prefix = format!("{}: ", binary.name());
+				let progress_reporter = ProgressReporter(prefix, progress);
+				let result = binary.source(release, &progress_reporter, verbose).await;
+				match &result {
+					Ok(_) => progress_reporter.1.stop(format!("✅  {}", binary.name())),
+					Err(e) => progress_reporter.1.error(format!("🚫 {}: {e}", binary.name())),
+				}
+				result
+			})
+		})
+		.collect();
+	let mut results = Vec::with_capacity(tasks.len());
+	for task in tasks {
+		results.push(match task.await {
+			Ok(result) => result.map_err(SourcingError::into),
+			Err(e) => Err(e.into()),
+		});
+	}
+	multi.stop();
+	results
+}
+
+async fn run_custom_command(
+	spinner: &ProgressBar,
+	command: &str,
+	wait: bool,
+) -> Result<(), anyhow::Error> {
 	spinner.set_message(format!("Spinning up network & running command: {}", command));
-	sleep(Duration::from_secs(15)).await;
+	// Allow time for the network to start producing blocks, unless already waited for
+	if wait {
+		sleep(Duration::from_secs(15)).await;
+	}
 
 	// Split the command into the base command and arguments
 	let mut parts = command.split_whitespace();
@@ -342,6 +709,64 @@ async fn run_custom_command(spinner: &ProgressBar, command: &str) -> Result<(),
 	Ok(())
 }
 
+/// Formats the details of a launched network for display.
+///
+/// # Arguments
+/// * `info` - The details of the launched network.
+/// * `verbose` - Whether the command line of each node should be included.
+fn format_network(info: &NetworkInfo, verbose: bool) -> String {
+	let bar = Style::new().magenta().dim().apply_to(Emoji("│", "|"));
+	let output = |node: &NodeInfo| -> String {
+		let name = &node.name;
+		let mut output = format!(
+			"\n{bar}       {name}:
+{bar}         portal: https://polkadot.js.org/apps/?rpc={}#/explorer
+{bar}         logs: tail -f {}",
+			node.ws_uri,
+			node.log_path.display(),
+		);
+		if verbose {
+			output += &format!("\n{bar}         command: {} {}", node.command, node.args.join(" "));
+		}
+		output
+	};
+	// Add relay info
+	let mut result = format!("\n{bar}  ⛓️ {}", info.relay_chain.chain);
+	for node in &info.relay_chain.nodes {
+		result.push_str(&output(node));
+	}
+	// Add parachain info
+	for parachain in &info.parachains {
+		result.push_str(&format!(
+			"\n{bar}  ⛓️ {}",
+			parachain.chain_id.as_ref().map_or(
+				format!("para_id: {}", parachain.para_id),
+				|chain| format!("{chain}: {}", parachain.para_id)
+			)
+		));
+		for node in &parachain.collators {
+			result.push_str(&output(node));
+		}
+	}
+	result
+}
+
+/// Waits for a signal to terminate, either via ctrl-c or, on unix, `SIGTERM`.
+async fn terminated() -> anyhow::Result<()> {
+	#[cfg(unix)]
+	{
+		use tokio::signal::unix::{signal, SignalKind};
+		let mut terminate = signal(SignalKind::terminate())?;
+		tokio::select! {
+			result = tokio::signal::ctrl_c() => result?,
+			_ = terminate.recv() => {},
+		}
+	}
+	#[cfg(not(unix))]
+	tokio::signal::ctrl_c().await?;
+	Ok(())
+}
+
 /// Reports any observed status updates to a progress bar.
 struct ProgressReporter(String, ProgressBar);
 
@@ -350,6 +775,25 @@ impl Status for ProgressReporter {
 		self.1
 			.start(format!("{}{}", self.0, status.replace("   Compiling", "Compiling")))
 	}
+
+	fn progress(&self, downloaded: u64, total: Option<u64>) {
+		const MIB: f64 = 1_048_576.0;
+		let downloaded = downloaded as f64 / MIB;
+		let progress = match total {
+			Some(total) => format!("{downloaded:.1}/{:.1} MiB", total as f64 / MIB),
+			None => format!("{downloaded:.1} MiB"),
+		};
+		self.1.start(format!("{}Downloading {progress}...", self.0))
+	}
+}
+
+/// Reports any observed status updates to a spinner.
+pub(crate) struct SpinnerReporter(pub(crate) ProgressBar);
+
+impl Status for SpinnerReporter {
+	fn update(&self, status: &str) {
+		self.0.start(status)
+	}
 }
 
 /// Reports any observed status updates as indented messages.
@@ -374,6 +818,82 @@ impl Status for VerboseReporter {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use pop_common::sourcing::Source;
+	use pop_parachains::{ParachainInfo, RelayChainInfo};
+	use std::path::PathBuf;
+
+	#[test]
+	fn relay_network_is_distinct_from_relay_chain() -> anyhow::Result<()> {
+		#[derive(clap::Parser)]
+		struct Command {
+			#[command(flatten)]
+			zombienet: ZombienetCommand,
+		}
+		let command = <Command as clap::Parser>::try_parse_from([
+			"pop",
+			"--relay-network",
+			"kusama-local",
+			"-r",
+			"v1.13.0",
+		])?;
+		assert_eq!(command.zombienet.relay_network, "kusama-local");
+		assert_eq!(command.zombienet.relay_chain.as_deref(), Some("v1.13.0"));
+		assert!(
+			<Command as clap::Parser>::try_parse_from(["pop", "--relay", "kusama-local"]).is_err()
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn source_concurrently_works() -> anyhow::Result<()> {
+		let mut server = mockito::Server::new_async().await;
+		let mut mocks = Vec::new();
+		for name in ["polkadot", "pop-node"] {
+			let mock = server
+				.mock("GET", format!("/{name}").as_str())
+				.with_chunked_body(move |w| {
+					// Ensure sourcing is still in progress when the other binary fails
+					std::thread::sleep(Duration::from_millis(250));
+					w.write_all(name.as_bytes())
+				})
+				.create_async()
+				.await;
+			mocks.push(mock);
+		}
+		let temp_dir = tempfile::tempdir()?;
+		let source = |name: &str| Binary::Source {
+			name: name.to_string(),
+			source: Source::Url {
+				url: format!("{}/{name}", server.url()),
+				name: name.to_string(),
+				checksum: None,
+			},
+			cache: temp_dir.path().to_path_buf(),
+		};
+		let binaries = [
+			source("polkadot"),
+			Binary::Local {
+				name: "missing".into(),
+				path: PathBuf::from("./missing"),
+				manifest: None,
+			},
+			source("pop-node"),
+		];
+
+		let results = source_concurrently(binaries.clone(), true, false).await;
+		assert!(results[0].is_ok());
+		assert_eq!(
+			results[1].as_ref().unwrap_err().to_string(),
+			"Missing binary: The \"./missing\" binary cannot be sourced automatically."
+		);
+		assert!(results[2].is_ok());
+		for mock in mocks {
+			mock.assert_async().await;
+		}
+		assert!(binaries[0].exists());
+		assert!(binaries[2].exists());
+		Ok(())
+	}
 
 	#[tokio::test]
 	async fn test_run_custom_command() -> Result<(), anyhow::Error> {
@@ -383,8 +903,42 @@ mod tests {
 		let command = "echo 2 + 2";
 
 		// Call the run_custom_command function
-		run_custom_command(&spinner, command).await?;
+		run_custom_command(&spinner, command, true).await?;
 
 		Ok(())
 	}
+
+	#[test]
+	fn format_network_works() {
+		let node = |name: &str| NodeInfo {
+			name: name.to_string(),
+			ws_uri: "ws://127.0.0.1:9944".to_string(),
+			rpc_port: Some(9944),
+			prometheus_port: None,
+			p2p_port: None,
+			log_path: format!("/tmp/zombie-1/{name}/{name}.log").into(),
+			command: "polkadot".to_string(),
+			args: vec!["--rpc-port".to_string(), "9944".to_string()],
+		};
+		let info = NetworkInfo {
+			base_dir: "/tmp/zombie-1".into(),
+			relay_chain: RelayChainInfo {
+				chain: "paseo-local".to_string(),
+				nodes: vec![node("alice")],
+			},
+			parachains: vec![ParachainInfo {
+				para_id: 2000,
+				chain_id: None,
+				collators: vec![node("collator-01")],
+			}],
+		};
+
+		let output = console::strip_ansi_codes(&format_network(&info, false)).to_string();
+		assert!(output.contains("⛓️ paseo-local"));
+		assert!(output.contains("⛓️ para_id: 2000"));
+		assert!(output.contains("logs: tail -f /tmp/zombie-1/collator-01/collator-01.log"));
+		assert!(!output.contains("command:"));
+		let output = format_network(&info, true);
+		assert!(output.contains("command: polkadot --rpc-port 9944"));
+	}
 }
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use clap::{Args, Subcommand};
+
+mod runtime;
+
+/// Arguments for upgrading a running chain.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub(crate) struct UpgradeArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Upgrade a running chain.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Upgrade the runtime of a running chain, such as one launched via `pop up parachain`. Fails
+	/// if the upgrade cannot be applied or block production stalls afterwards.
+	#[clap(alias = "r")]
+	Runtime(runtime::UpgradeRuntimeCommand),
+}
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{commands::up::parachain::SpinnerReporter, style::style};
+use anyhow::anyhow;
+use clap::Args;
+use cliclack::{intro, log, outro};
+use pop_parachains::{runtime_wasm, Error, RuntimeUpgrade, UpgradeMethod, UpgradeOutcome};
+use std::{env::current_dir, fs::read, path::PathBuf, time::Duration};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+#[derive(Args)]
+pub(crate) struct UpgradeRuntimeCommand {
+	/// The runtime (WASM) to be applied, such as one exported via `pop build spec` or built
+	/// within a parachain project. Defaults to the runtime built within the current project at
+	/// `target/release/wbuild`.
+	#[arg(short, long)]
+	wasm: Option<PathBuf>,
+	/// Websocket endpoint of a node of the chain to be upgraded.
+	#[arg(short, long, default_value = DEFAULT_URL)]
+	url: Url,
+	/// Secret key URI of the sudo account of the chain, e.g.
+	/// - for a dev account "//Alice"
+	/// - with a password "//Alice///SECRET_PASSWORD"
+	#[arg(short, long, default_value = "//Alice")]
+	suri: String,
+	/// The number of blocks expected to be produced after the upgrade.
+	#[arg(long, default_value_t = 2)]
+	blocks: u32,
+	/// The maximum time to wait for the upgrade to be applied, and then for the subsequent blocks
+	/// to be produced, in seconds. Parachain upgrades are only applied once approved by the relay
+	/// chain.
+	#[arg(long, default_value_t = 300)]
+	timeout: u64,
+}
+
+impl UpgradeRuntimeCommand {
+	/// Executes the command.
+	pub(crate) async fn execute(self) -> anyhow::Result<()> {
+		intro(format!("{}: Upgrade a runtime", style(" Pop CLI ").black().on_magenta()))?;
+
+		// Locate the runtime, if not specified
+		let wasm = match self.wasm {
+			Some(wasm) => wasm,
+			None => match runtime_wasm(&current_dir()?) {
+				Ok(wasm) => wasm,
+				Err(Error::Config(message)) =>
+					return Err(anyhow!("{message}. Use `--wasm` to specify the runtime.")),
+				Err(e) => return Err(e.into()),
+			},
+		};
+		let code = match read(&wasm) {
+			Ok(code) => code,
+			Err(e) => return Err(anyhow!("Could not read the runtime at {}: {e}", wasm.display())),
+		};
+		log::info(format!("ℹ️ Using the runtime at {} ({} bytes)", wasm.display(), code.len()))?;
+
+		let spinner = cliclack::spinner();
+		spinner.start(format!("Connecting to {}...", self.url));
+		let upgrade = match RuntimeUpgrade::new(&self.url, code).await {
+			Ok(upgrade) => upgrade,
+			Err(e) => {
+				spinner.error(format!("🚫 Could not connect to {}", self.url));
+				return Err(e.into());
+			},
+		};
+		let spec_version = upgrade.spec_version();
+		let method = match upgrade.method() {
+			Ok(method) => method,
+			Err(e) => {
+				spinner.error(format!("🚫 The runtime of {} cannot be upgraded", self.url));
+				return Err(e.into());
+			},
+		};
+		spinner.stop(format!(
+			"Connected to {} (spec version {spec_version}), upgrading via {}",
+			self.url,
+			match method {
+				UpgradeMethod::Authorized => "`System::authorize_upgrade`",
+				UpgradeMethod::SetCode => "`System::set_code`",
+			}
+		));
+
+		// Apply the upgrade and observe block production afterwards
+		let spinner = cliclack::spinner();
+		spinner.start("Upgrading runtime...");
+		let timeout = Duration::from_secs(self.timeout);
+		match upgrade
+			.apply(&self.suri, self.blocks, timeout, &SpinnerReporter(spinner.clone()))
+			.await
+		{
+			Ok(outcome) => {
+				spinner.stop(format!("⬆️ Code updated at block {}", outcome.updated_at));
+				// A stall is an error, as with any other failure, so that it can be detected by
+				// scripts
+				let summary = summary(spec_version, &outcome, self.blocks);
+				match outcome.continued {
+					true => outro(summary)?,
+					false => return Err(anyhow!(summary)),
+				}
+			},
+			Err(e) => {
+				spinner.error("🚫 The runtime upgrade failed");
+				return Err(e.into());
+			},
+		}
+		Ok(())
+	}
+}
+
+/// A summary of the outcome of a runtime upgrade.
+///
+/// # Arguments
+/// * `spec_version` - The spec version of the runtime prior to the upgrade.
+/// * `outcome` - The outcome of the upgrade.
+/// * `blocks` - The number of blocks expected to be produced after the upgrade.
+fn summary(spec_version: u32, outcome: &UpgradeOutcome, blocks: u32) -> String {
+	match outcome.continued {
+		true => format!(
+			"✅ Runtime upgraded from spec version {spec_version} to {}, with {blocks} block(s) produced since block {}",
+			outcome.spec_version, outcome.updated_at
+		),
+		false => format!(
+			"Block production stalled after the runtime was upgraded at block {} (spec version {spec_version} to {})",
+			outcome.updated_at, outcome.spec_version
+		),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn summary_works() {
+		let mut outcome = UpgradeOutcome { updated_at: 10, spec_version: 1_001, continued: true };
+		assert_eq!(
+			summary(1_000, &outcome, 2),
+			"✅ Runtime upgraded from spec version 1000 to 1001, with 2 block(s) produced since block 10"
+		);
+		outcome.continued = false;
+		assert_eq!(
+			summary(1_000, &outcome, 2),
+			"Block production stalled after the runtime was upgraded at block 10 (spec version 1000 to 1001)"
+		);
+	}
+}
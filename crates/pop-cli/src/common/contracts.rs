@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use cliclack::{confirm, log::warning, spinner};
+use pop_common::sourcing::{self, offline};
 use pop_contracts::contracts_node_generator;
 use std::path::PathBuf;
 
@@ -14,6 +15,11 @@ pub async fn check_contracts_node_and_prompt(skip_confirm: bool) -> anyhow::Resu
 	let mut binary = contracts_node_generator(cache_path, None).await?;
 	let mut node_path = binary.path();
 	if !binary.exists() {
+		// The binary cannot be sourced when offline
+		if offline() {
+			let missing = binary.missing().iter().map(|p| p.display().to_string()).collect();
+			return Err(sourcing::Error::Offline(missing).into());
+		}
 		warning("⚠️ The substrate-contracts-node binary is not found.")?;
 		if confirm("📦 Would you like to source it automatically now?")
 			.initial_value(true)
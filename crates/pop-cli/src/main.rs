@@ -26,6 +26,9 @@ async fn main() -> Result<()> {
 	let maybe_tel = init().unwrap_or(None);
 
 	let cli = Cli::parse();
+	if cli.offline {
+		pop_common::sourcing::set_offline(true);
+	}
 	let res = cli.command.execute().await;
 
 	#[cfg(feature = "telemetry")]
@@ -56,6 +59,10 @@ async fn main() -> Result<()> {
 pub struct Cli {
 	#[command(subcommand)]
 	command: Command,
+	/// Resolve binaries purely from the local cache, without querying GitHub. Can also be enabled
+	/// via the `POP_OFFLINE` environment variable.
+	#[arg(long, global = true)]
+	offline: bool,
 }
 
 /// Determines the cache to be used.
@@ -68,6 +75,16 @@ fn cache() -> Result<PathBuf> {
 	Ok(cache_path)
 }
 
+/// Determines the data directory to be used, containing state which persists across invocations.
+#[cfg(feature = "parachain")]
+fn data_dir() -> Result<PathBuf> {
+	let data_path = dirs::data_dir()
+		.ok_or(anyhow!("the data directory could not be determined"))?
+		.join("pop");
+	create_dir_all(data_path.as_path())?;
+	Ok(data_path)
+}
+
 /// Initializes telemetry.
 #[cfg(feature = "telemetry")]
 fn init() -> Result<Option<Telemetry>> {
@@ -114,6 +131,15 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	#[cfg(feature = "parachain")]
+	fn test_data_dir() -> Result<(), Box<dyn std::error::Error>> {
+		let path = data_dir()?;
+		assert_eq!(path.file_name().unwrap().to_str().unwrap().to_string(), "pop");
+		assert!(path.exists());
+		Ok(())
+	}
+
 	#[test]
 	fn parse_args_works() {
 		for args in vec![
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::{sourcing, templates};
+use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -19,6 +20,8 @@ pub enum Error {
 	ManifestError(#[from] cargo_toml::Error),
 	#[error("ParseError error: {0}")]
 	ParseError(#[from] url::ParseError),
+	#[error("The GitHub API rate limit of {limit} requests has been exceeded and resets in {} minute(s). Set `GITHUB_TOKEN` or `GH_TOKEN` to increase the limit.", minutes_until(.reset))]
+	RateLimited { limit: u64, reset: u64 },
 	#[error("SourceError error: {0}")]
 	SourceError(#[from] sourcing::Error),
 	#[error("TemplateError error: {0}")]
@@ -28,3 +31,9 @@ pub enum Error {
 	#[error("Unsupported platform: {arch} {os}")]
 	UnsupportedPlatform { arch: &'static str, os: &'static str },
 }
+
+// The number of whole minutes until the specified time, in seconds since the Unix epoch.
+fn minutes_until(time: &u64) -> u64 {
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+	time.saturating_sub(now).div_ceil(60)
+}
@@ -1,13 +1,25 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::{errors::Error, polkadot_sdk::parse_latest_tag, APP_USER_AGENT};
+use crate::{
+	errors::Error,
+	polkadot_sdk::parse_latest_tag,
+	sourcing::{github_api_url_from, mirror, offline},
+	APP_USER_AGENT,
+};
 use anyhow::Result;
 use git2::{
 	build::RepoBuilder, FetchOptions, IndexAddOption, RemoteCallbacks, Repository as GitRepository,
 	ResetType,
 };
 use git2_credentials::CredentialHandler;
-use std::{fs, path::Path};
+use reqwest::{Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use std::{
+	env, fs,
+	path::{Path, PathBuf},
+	time::{SystemTime, UNIX_EPOCH},
+};
 use url::Url;
 
 /// A helper for handling Git operations.
@@ -145,11 +157,17 @@ impl Git {
 	}
 }
 
+/// The environment variable used to configure how long cached release listings remain valid, in
+/// seconds.
+pub const POP_RELEASES_TTL: &str = "POP_RELEASES_TTL";
+const DEFAULT_RELEASES_TTL: u64 = 60 * 60;
+
 /// A helper for handling GitHub operations.
 pub struct GitHub {
 	pub org: String,
 	pub name: String,
 	api: String,
+	cache: Option<PathBuf>,
 }
 
 impl GitHub {
@@ -161,37 +179,76 @@ impl GitHub {
 	///
 	/// * `url` - the URL of the repository to clone.
 	pub fn parse(url: &str) -> Result<Self> {
+		Self::parse_from(url, mirror())
+	}
+
+	/// Parse URL of a GitHub repository, using the API of the specified mirror of GitHub.
+	///
+	/// # Arguments
+	///
+	/// * `url` - the URL of the repository to clone.
+	/// * `mirror` - the mirror of GitHub, if any.
+	pub(crate) fn parse_from(url: &str, mirror: Option<&str>) -> Result<Self> {
 		let url = Url::parse(url)?;
 		Ok(Self {
 			org: Self::org(&url)?.into(),
 			name: Self::name(&url)?.into(),
-			api: "https://api.github.com".into(),
+			api: github_api_url_from(mirror),
+			cache: dirs::cache_dir().map(|c| c.join("pop").join(".releases")),
 		})
 	}
 
-	// Overrides the api base url for testing
+	// Overrides the api base url for testing, without caching any release listings
 	#[cfg(test)]
-	fn with_api(mut self, api: impl Into<String>) -> Self {
+	pub(crate) fn with_api(mut self, api: impl Into<String>) -> Self {
 		self.api = api.into();
+		self.cache = None;
+		self
+	}
+
+	// Overrides the location used for caching release listings for testing
+	#[cfg(test)]
+	fn with_cache(mut self, cache: impl Into<PathBuf>) -> Self {
+		self.cache = Some(cache.into());
 		self
 	}
 
-	/// Fetch the latest releases of the GitHub repository.
+	/// Fetch the latest releases of the GitHub repository, failing immediately when offline.
+	///
+	/// Release listings are cached locally, with a cached listing used until it expires as per the
+	/// `POP_RELEASES_TTL` environment variable (defaults to an hour). An expired listing is also
+	/// used should the GitHub API rate limit be exceeded.
 	pub async fn releases(&self) -> Result<Vec<Release>> {
-		let client = reqwest::ClientBuilder::new().user_agent(APP_USER_AGENT).build()?;
-		let url = self.api_releases_url();
-		let response = client.get(url).send().await?.error_for_status()?;
-		Ok(response.json::<Vec<Release>>().await?)
+		if offline() {
+			return Err(Error::Git("releases cannot be fetched while offline".to_string()).into());
+		}
+		let ttl = env::var(POP_RELEASES_TTL)
+			.ok()
+			.and_then(|t| t.parse().ok())
+			.unwrap_or(DEFAULT_RELEASES_TTL);
+		let cached = self.cached_releases();
+		if let Some(cached) = cached.as_ref().filter(|c| now().saturating_sub(c.fetched) < ttl) {
+			return Ok(cached.releases.clone());
+		}
+		let response = match self.get(&self.api_releases_url()).await {
+			Ok(response) => response,
+			Err(e) => {
+				// Fall back to an expired listing when rate limited
+				if let (Some(Error::RateLimited { .. }), Some(cached)) = (e.downcast_ref(), cached)
+				{
+					return Ok(cached.releases);
+				}
+				return Err(e);
+			},
+		};
+		let releases = response.json::<Vec<Release>>().await?;
+		self.cache_releases(&releases);
+		Ok(releases)
 	}
 
 	/// Retrieves the commit hash associated with a specified tag in a GitHub repository.
 	pub async fn get_commit_sha_from_release(&self, tag_name: &str) -> Result<String> {
-		let client = reqwest::ClientBuilder::new().user_agent(APP_USER_AGENT).build()?;
-		let response = client
-			.get(self.api_tag_information(tag_name))
-			.send()
-			.await?
-			.error_for_status()?;
+		let response = self.get(&self.api_tag_information(tag_name)).await?;
 		let value = response.json::<serde_json::Value>().await?;
 		let commit = value
 			.get("object")
@@ -203,9 +260,7 @@ impl GitHub {
 	}
 
 	pub async fn get_repo_license(&self) -> Result<String> {
-		let client = reqwest::ClientBuilder::new().user_agent(APP_USER_AGENT).build()?;
-		let url = self.api_license_url();
-		let response = client.get(url).send().await?.error_for_status()?;
+		let response = self.get(&self.api_license_url()).await?;
 		let value = response.json::<serde_json::Value>().await?;
 		let license = value
 			.get("license")
@@ -216,6 +271,103 @@ impl GitHub {
 		Ok(license)
 	}
 
+	/// Sends a request to the GitHub API, authenticated using any token provided via the
+	/// `GITHUB_TOKEN` or `GH_TOKEN` environment variables.
+	///
+	/// # Arguments
+	/// * `url` - The url of the request.
+	async fn get(&self, url: &str) -> Result<Response> {
+		// Tokens are only ever sent to GitHub, never to a mirror
+		let token = match mirror() {
+			None => Self::token(),
+			Some(_) => None,
+		};
+		Self::get_with_token(url, token.as_deref()).await
+	}
+
+	/// Fetches a file published alongside a release, such as a checksum, from GitHub or the
+	/// configured mirror. Any token provided via the `GITHUB_TOKEN` or `GH_TOKEN` environment
+	/// variables is only used when the file is hosted by GitHub itself.
+	///
+	/// # Arguments
+	/// * `url` - The url of the file.
+	pub(crate) async fn fetch(url: &Url) -> Result<Response> {
+		let token = match url.host_str() == Some(Self::GITHUB) {
+			true => Self::token(),
+			false => None,
+		};
+		Self::get_with_token(url.as_str(), token.as_deref()).await
+	}
+
+	/// Any token provided via the `GITHUB_TOKEN` or `GH_TOKEN` environment variables.
+	fn token() -> Option<String> {
+		["GITHUB_TOKEN", "GH_TOKEN"]
+			.into_iter()
+			.find_map(|v| env::var(v).ok().filter(|t| !t.is_empty()))
+	}
+
+	/// Sends a request to the GitHub API, failing with [`Error::RateLimited`] if the rate limit
+	/// has been exceeded.
+	///
+	/// # Arguments
+	/// * `url` - The url of the request.
+	/// * `token` - If applicable, the token used to authenticate the request.
+	async fn get_with_token(url: &str, token: Option<&str>) -> Result<Response> {
+		let client = reqwest::ClientBuilder::new().user_agent(APP_USER_AGENT).build()?;
+		let mut request = client.get(url);
+		if let Some(token) = token {
+			request = request.bearer_auth(token);
+		}
+		let response = request.send().await?;
+		if matches!(response.status(), StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS) {
+			let header = |name: &str| {
+				response
+					.headers()
+					.get(name)
+					.and_then(|v| v.to_str().ok())
+					.and_then(|v| v.parse::<u64>().ok())
+			};
+			if header("x-ratelimit-remaining") == Some(0) {
+				return Err(Error::RateLimited {
+					limit: header("x-ratelimit-limit").unwrap_or_default(),
+					reset: header("x-ratelimit-reset").unwrap_or_default(),
+				}
+				.into());
+			}
+		}
+		Ok(response.error_for_status()?)
+	}
+
+	/// The location of the cached release listing, if caching is enabled. The location is keyed by
+	/// the API, so that listings from a mirror and from GitHub are cached separately.
+	fn releases_cache(&self) -> Option<PathBuf> {
+		let api = format!("{:x}", Sha256::digest(self.api.as_bytes()));
+		self.cache
+			.as_ref()
+			.map(|c| c.join(format!("{}-{}-{}.json", self.org, self.name, &api[..16])))
+	}
+
+	/// Any cached release listing, regardless of whether it has expired.
+	fn cached_releases(&self) -> Option<CachedReleases> {
+		let contents = fs::read_to_string(self.releases_cache()?).ok()?;
+		serde_json::from_str(&contents).ok()
+	}
+
+	/// Caches a release listing, on a best effort basis.
+	///
+	/// # Arguments
+	/// * `releases` - The release listing.
+	fn cache_releases(&self, releases: &[Release]) {
+		let Some(path) = self.releases_cache() else {
+			return;
+		};
+		let cached = CachedReleases { fetched: now(), releases: releases.to_vec() };
+		if let Ok(contents) = serde_json::to_string(&cached) {
+			let _ = path.parent().map(fs::create_dir_all);
+			let _ = fs::write(path, contents);
+		}
+	}
+
 	fn api_releases_url(&self) -> String {
 		format!("{}/repos/{}/{}/releases", self.api, self.org, self.name)
 	}
@@ -259,7 +411,7 @@ impl GitHub {
 }
 
 /// Represents the data of a GitHub release.
-#[derive(Debug, PartialEq, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Release {
 	pub tag_name: String,
 	pub name: String,
@@ -267,6 +419,20 @@ pub struct Release {
 	pub commit: Option<String>,
 }
 
+/// A release listing cached locally.
+#[derive(Deserialize, Serialize)]
+struct CachedReleases {
+	/// When the listing was fetched, in seconds since the Unix epoch.
+	fetched: u64,
+	/// The releases.
+	releases: Vec<Release>,
+}
+
+/// The current time, in seconds since the Unix epoch.
+fn now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
 /// A descriptor of a remote repository.
 #[derive(Debug, PartialEq)]
 pub struct Repository {
@@ -365,6 +531,88 @@ mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn releases_are_cached() -> Result<(), Box<dyn std::error::Error>> {
+		let mut mock_server = Server::new_async().await;
+		let temp_dir = tempfile::tempdir()?;
+
+		let payload = r#"[{
+			"tag_name": "polkadot-v1.10.0",
+			"name": "Polkadot v1.10.0",
+			"prerelease": false
+		  }]"#;
+		let repo = GitHub::parse(BASE_PARACHAIN)?
+			.with_api(mock_server.url())
+			.with_cache(temp_dir.path());
+		let mock = mock_server
+			.mock("GET", format!("/repos/{}/{}/releases", repo.org, repo.name).as_str())
+			.with_status(200)
+			.with_header("content-type", "application/json")
+			.with_body(payload)
+			.expect(1)
+			.create_async()
+			.await;
+		let releases = repo.releases().await?;
+		// Subsequent listing is served from the cache
+		assert_eq!(repo.releases().await?, releases);
+		assert!(repo.releases_cache().unwrap().exists());
+		mock.assert_async().await;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn releases_fails_when_rate_limited() -> Result<(), Box<dyn std::error::Error>> {
+		let mut mock_server = Server::new_async().await;
+		let temp_dir = tempfile::tempdir()?;
+
+		let repo = GitHub::parse(BASE_PARACHAIN)?
+			.with_api(mock_server.url())
+			.with_cache(temp_dir.path());
+		let reset = now() + 30 * 60;
+		let mock = mock_server
+			.mock("GET", format!("/repos/{}/{}/releases", repo.org, repo.name).as_str())
+			.with_status(403)
+			.with_header("x-ratelimit-limit", "60")
+			.with_header("x-ratelimit-remaining", "0")
+			.with_header("x-ratelimit-reset", &reset.to_string())
+			.expect(2)
+			.create_async()
+			.await;
+		let error = repo.releases().await.unwrap_err();
+		assert!(matches!(
+			error.downcast_ref::<Error>(),
+			Some(Error::RateLimited { limit: 60, reset: r }) if *r == reset
+		));
+		assert!(error.to_string().contains("resets in 30 minute(s)"));
+
+		// An expired listing is used when rate limited
+		let release = Release {
+			tag_name: "polkadot-v1.10.0".into(),
+			name: "Polkadot v1.10.0".into(),
+			prerelease: false,
+			commit: None,
+		};
+		let cached = CachedReleases { fetched: 0, releases: vec![release.clone()] };
+		fs::write(repo.releases_cache().unwrap(), serde_json::to_string(&cached)?)?;
+		assert_eq!(repo.releases().await?, vec![release]);
+		mock.assert_async().await;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_with_token_authenticates() -> Result<(), Box<dyn std::error::Error>> {
+		let mut mock_server = Server::new_async().await;
+		let mock = mock_server
+			.mock("GET", "/rate_limit")
+			.match_header("authorization", "Bearer token")
+			.with_status(200)
+			.create_async()
+			.await;
+		GitHub::get_with_token(&format!("{}/rate_limit", mock_server.url()), Some("token")).await?;
+		mock.assert_async().await;
+		Ok(())
+	}
+
 	#[tokio::test]
 	async fn get_releases_with_commit_sha() -> Result<(), Box<dyn std::error::Error>> {
 		let mut mock_server = Server::new_async().await;
@@ -435,6 +683,37 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn parse_from_works() -> Result<(), Box<dyn std::error::Error>> {
+		let repo = GitHub::parse_from(POLKADOT_SDK, None)?;
+		assert_eq!(repo.api, "https://api.github.com");
+		let repo = GitHub::parse_from(POLKADOT_SDK, Some("https://mirror.example.com"))?;
+		assert_eq!((repo.org.as_str(), repo.name.as_str()), ("paritytech", "polkadot-sdk"));
+		assert_eq!(repo.api, "https://mirror.example.com/api");
+		assert_eq!(
+			repo.api_releases_url(),
+			"https://mirror.example.com/api/repos/paritytech/polkadot-sdk/releases"
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn releases_cache_is_keyed_by_api() -> Result<(), Box<dyn std::error::Error>> {
+		let temp_dir = tempfile::tempdir()?;
+		let github = GitHub::parse_from(POLKADOT_SDK, None)?.with_cache(temp_dir.path());
+		let mirror = GitHub::parse_from(POLKADOT_SDK, Some("https://mirror.example.com"))?
+			.with_cache(temp_dir.path());
+		assert_ne!(github.releases_cache(), mirror.releases_cache());
+		let cache = github.releases_cache().unwrap();
+		assert_eq!(cache.parent(), Some(temp_dir.path()));
+		assert!(cache
+			.file_name()
+			.unwrap()
+			.to_string_lossy()
+			.starts_with("paritytech-polkadot-sdk-"));
+		Ok(())
+	}
+
 	#[test]
 	fn test_parse_org() -> Result<(), Box<dyn std::error::Error>> {
 		assert_eq!(GitHub::parse(BASE_PARACHAIN)?.org, "r0gue-io");
@@ -6,6 +6,8 @@ pub mod manifest;
 pub mod polkadot_sdk;
 pub mod sourcing;
 pub mod templates;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 pub use build::Profile;
 pub use errors::Error;
@@ -20,6 +22,21 @@ static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_P
 pub trait Status {
 	/// Update the observer with the provided `status`.
 	fn update(&self, status: &str);
+
+	/// Update the observer with a warning, which is reported as a status update by default.
+	///
+	/// # Arguments
+	/// * `warning` - The warning.
+	fn warning(&self, warning: &str) {
+		self.update(warning)
+	}
+
+	/// Update the observer with the progress of a download, in bytes. Ignored by default.
+	///
+	/// # Arguments
+	/// * `downloaded` - The number of bytes downloaded so far.
+	/// * `total` - If known, the total number of bytes to be downloaded.
+	fn progress(&self, _downloaded: u64, _total: Option<u64>) {}
 }
 
 impl Status for () {
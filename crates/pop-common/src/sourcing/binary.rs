@@ -3,7 +3,8 @@
 use crate::{
 	polkadot_sdk::parse_latest_tag,
 	sourcing::{
-		from_local_package, Error,
+		cache::{Artifact, Manifest},
+		from_local_package, offline, Error,
 		GitHub::{ReleaseArchive, SourceCodeArchive},
 		Source::{self, Archive, Git, GitHub},
 	},
@@ -12,7 +13,7 @@ use crate::{
 use std::path::{Path, PathBuf};
 
 /// A binary used to launch a node.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Binary {
 	/// A local binary.
 	Local {
@@ -54,6 +55,20 @@ impl Binary {
 		}
 	}
 
+	/// The artifacts required by the binary which are missing locally.
+	pub fn missing(&self) -> Vec<PathBuf> {
+		match self {
+			Self::Local { path, .. } =>
+				(!path.exists()).then(|| path.clone()).into_iter().collect(),
+			Self::Source { source, cache, .. } => source
+				.artifacts(cache)
+				.into_iter()
+				.map(|(_, path)| path)
+				.filter(|path| !path.exists())
+				.collect(),
+		}
+	}
+
 	/// Whether the binary is defined locally.
 	pub fn local(&self) -> bool {
 		matches!(self, Self::Local { .. })
@@ -87,7 +102,10 @@ impl Binary {
 	}
 
 	/// Attempts to resolve a version of a binary based on whether one is specified, an existing
-	/// version can be found cached locally, or uses the latest version.
+	/// version can be found cached locally, or uses the latest version. Cached versions which no
+	/// longer match the provenance recorded within the cache manifest are ignored. When offline,
+	/// the latest version recorded within the cache manifest is used when no versions are
+	/// available.
 	///
 	/// # Arguments
 	/// * `name` - The name of the binary.
@@ -108,11 +126,17 @@ impl Binary {
 				.map(|v| v.as_ref())
 				// Default to latest version available locally
 				.filter_map(|version| {
-					let path = cache.join(format!("{name}-{version}"));
-					path.exists().then_some(Some(version.to_string()))
+					let file = format!("{name}-{version}");
+					let path = cache.join(&file);
+					(path.exists() && Self::intact(cache, &file, &path))
+						.then_some(Some(version.to_string()))
 				})
 				.nth(0)
 				.unwrap_or_else(|| {
+					// Default to latest version cached locally when offline
+					if offline() && available.is_empty() {
+						return Self::latest_cached_version(name, cache);
+					}
 					// Default to latest version
 					let versions = available.iter().map(|v| v.as_ref()).collect::<Vec<&str>>();
 					parse_latest_tag(versions)
@@ -120,7 +144,36 @@ impl Binary {
 		}
 	}
 
-	/// Sources the binary.
+	/// The latest version of a binary recorded within the cache manifest and still cached.
+	///
+	/// # Arguments
+	/// * `name` - The name of the binary.
+	/// * `cache` - The location used for caching binaries.
+	fn latest_cached_version(name: &str, cache: &Path) -> Option<String> {
+		let manifest = Manifest::load(cache).ok()?;
+		let versions = manifest
+			.versions(name)
+			.filter(|v| {
+				let file = format!("{name}-{v}");
+				let path = cache.join(&file);
+				path.exists() && manifest.intact(&file, &path)
+			})
+			.collect();
+		parse_latest_tag(versions)
+	}
+
+	/// Whether a cached artifact matches the provenance recorded within the cache manifest. An
+	/// unreadable manifest records nothing, so any artifact is then assumed to be intact.
+	///
+	/// # Arguments
+	/// * `cache` - The location used for caching binaries.
+	/// * `file` - The file name of the artifact within the cache.
+	/// * `path` - The path of the artifact.
+	fn intact(cache: &Path, file: &str, path: &Path) -> bool {
+		Manifest::load(cache).unwrap_or_default().intact(file, path)
+	}
+
+	/// Sources the binary, recording the provenance of any artifacts stored within the cache.
 	///
 	/// # Arguments
 	/// * `release` - Whether any binaries needing to be built should be done so using the release
@@ -141,18 +194,66 @@ impl Binary {
 				Some(manifest) =>
 					from_local_package(manifest, name, release, status, verbose).await,
 			},
-			Self::Source { source, cache, .. } =>
-				source.source(cache, release, status, verbose).await,
+			Self::Source { name, source, cache } => {
+				// Only artifacts already within the cache are available when offline
+				if offline() {
+					let missing = self.missing();
+					if missing.is_empty() {
+						return Ok(());
+					}
+					return Err(Error::Offline(
+						missing.iter().map(|p| p.display().to_string()).collect(),
+					));
+				}
+				source.source(cache, release, status, verbose).await?;
+				// Record the provenance of the sourced artifacts within the cache manifest
+				let url = source.url();
+				let artifacts = source
+					.artifacts(cache)
+					.into_iter()
+					.filter_map(|(_, path)| {
+						let file = path.file_name()?.to_str()?.to_string();
+						Some(
+							Artifact::new(&path, name, self.version(), url.clone())
+								.map(|a| (file, a)),
+						)
+					})
+					.collect::<Result<Vec<_>, _>>()?;
+				Manifest::update(cache, status, |manifest| {
+					for (file, artifact) in artifacts {
+						manifest.insert(file, artifact);
+					}
+				})
+			},
 		}
 	}
 
-	/// Whether any locally cached version can be replaced with a newer version.
+	/// Whether any locally cached version can be replaced with a newer version, or no longer
+	/// matches the provenance recorded within the cache manifest and should be sourced again.
 	pub fn stale(&self) -> bool {
-		// Only binaries sourced from GitHub release archives can currently be determined as stale
-		let Self::Source { source: GitHub(ReleaseArchive { tag, latest, .. }), .. } = self else {
+		let Self::Source { source, cache, .. } = self else {
 			return false;
 		};
-		latest.as_ref().map_or(false, |l| tag.as_ref() != Some(l))
+		let manifest = Manifest::load(cache).unwrap_or_default();
+		let corrupted = source.artifacts(cache).into_iter().any(|(_, path)| {
+			path.file_name()
+				.and_then(|f| f.to_str())
+				.is_some_and(|file| path.exists() && !manifest.intact(file, &path))
+		});
+		// Only binaries sourced from GitHub release archives can currently be determined as
+		// outdated
+		let GitHub(ReleaseArchive { tag, latest, .. }) = source else {
+			return corrupted;
+		};
+		// Prefer the version recorded when the binary was sourced
+		let path = self.path();
+		let version = path
+			.file_name()
+			.and_then(|f| f.to_str())
+			.and_then(|file| manifest.get(file))
+			.and_then(|artifact| artifact.version.as_ref())
+			.or(tag.as_ref());
+		corrupted || latest.as_ref().map_or(false, |l| version != Some(l))
 	}
 
 	/// Specifies that the latest available versions are to be used (where possible).
@@ -186,7 +287,11 @@ impl Binary {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{sourcing::tests::Output, target};
+	use crate::{
+		sourcing::tests::Output,
+		target,
+		test_utils::{isolated, Offline},
+	};
 	use anyhow::Result;
 	use duct::cmd;
 	use std::fs::{create_dir_all, File};
@@ -257,6 +362,75 @@ mod tests {
 			Binary::resolve_version(name, None, &available, temp_dir.path()).unwrap(),
 			available[1]
 		);
+		// Cached, but no longer matching its recorded provenance
+		let file = format!("{name}-{}", available[0]);
+		let path = temp_dir.path().join(&file);
+		std::fs::write(&path, "polkadot")?;
+		let artifact = Artifact::new(&path, name, Some(available[0]), String::new())?;
+		Manifest::update(temp_dir.path(), &(), |m| m.insert(file, artifact))?;
+		assert_eq!(
+			Binary::resolve_version(name, None, &available, temp_dir.path()).unwrap(),
+			available[0]
+		);
+		std::fs::write(&path, "corrupt")?;
+		assert_eq!(
+			Binary::resolve_version(name, None, &available, temp_dir.path()).unwrap(),
+			available[1]
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn latest_cached_version_works() -> Result<()> {
+		let name = "polkadot";
+		let temp_dir = tempdir()?;
+		let cache = temp_dir.path();
+		assert_eq!(Binary::latest_cached_version(name, cache), None);
+
+		for version in ["v1.12.0", "v1.13.0", "stable2409"] {
+			let file = format!("{name}-{version}");
+			File::create(cache.join(&file))?;
+			let artifact = Artifact::new(&cache.join(&file), name, Some(version), String::new())?;
+			Manifest::update(cache, &(), |m| m.insert(file, artifact))?;
+		}
+		assert_eq!(Binary::latest_cached_version(name, cache).unwrap(), "stable2409");
+		// Recorded versions which are no longer cached are ignored
+		std::fs::remove_file(cache.join(format!("{name}-stable2409")))?;
+		assert_eq!(Binary::latest_cached_version(name, cache).unwrap(), "v1.13.0");
+		assert_eq!(Binary::latest_cached_version("pop-node", cache), None);
+		Ok(())
+	}
+
+	#[test]
+	fn missing_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let cache = temp_dir.path();
+		let binary = Binary::Source {
+			name: "polkadot".to_string(),
+			source: GitHub(ReleaseArchive {
+				owner: "paritytech".into(),
+				repository: "polkadot-sdk".into(),
+				tag: Some("v1.12.0".into()),
+				tag_format: None,
+				archive: "polkadot.tar.gz".into(),
+				contents: vec![("polkadot", None), ("polkadot-execute-worker", None)],
+				checksum: None,
+				latest: None,
+			}),
+			cache: cache.to_path_buf(),
+		};
+		assert_eq!(
+			binary.missing(),
+			[cache.join("polkadot-v1.12.0"), cache.join("polkadot-execute-worker-v1.12.0")]
+		);
+		File::create(cache.join("polkadot-v1.12.0"))?;
+		assert_eq!(binary.missing(), [cache.join("polkadot-execute-worker-v1.12.0")]);
+
+		let path = cache.join("pop-node");
+		let binary = Binary::Local { name: "pop-node".into(), path: path.clone(), manifest: None };
+		assert_eq!(binary.missing(), [path.as_path()]);
+		File::create(&path)?;
+		assert!(binary.missing().is_empty());
 		Ok(())
 	}
 
@@ -275,7 +449,7 @@ mod tests {
 
 		let mut binary = Binary::Source {
 			name: name.to_string(),
-			source: Archive { url: url.to_string(), contents },
+			source: Archive { url: url.to_string(), contents, checksum: None },
 			cache: temp_dir.path().to_path_buf(),
 		};
 
@@ -354,6 +528,7 @@ mod tests {
 						tag_format: Some(tag_format.to_string()),
 						archive: archive.clone(),
 						contents: contents.into_iter().map(|b| (b, None)).collect(),
+						checksum: None,
 						latest: latest.clone(),
 					}),
 					cache: temp_dir.path().to_path_buf(),
@@ -424,7 +599,7 @@ mod tests {
 
 		let mut binary = Binary::Source {
 			name: name.to_string(),
-			source: Source::Url { url: url.to_string(), name: name.to_string() },
+			source: Source::Url { url: url.to_string(), name: name.to_string(), checksum: None },
 			cache: temp_dir.path().to_path_buf(),
 		};
 
@@ -477,7 +652,7 @@ mod tests {
 
 		Binary::Source {
 			name: name.to_string(),
-			source: Source::Url { url: url.to_string(), name: name.to_string() },
+			source: Source::Url { url: url.to_string(), name: name.to_string(), checksum: None },
 			cache: temp_dir.path().to_path_buf(),
 		}
 		.source(true, &Output, true)
@@ -485,4 +660,82 @@ mod tests {
 		assert!(path.exists());
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn source_fails_when_offline() -> Result<()> {
+		if !isolated("sourcing::binary::tests::source_fails_when_offline") {
+			return Ok(());
+		}
+		let _offline = Offline::enable();
+		let temp_dir = tempdir()?;
+		let binary = Binary::Source {
+			name: "polkadot".to_string(),
+			source: Source::Url {
+				// Unreachable, as nothing should be requested when offline
+				url: "http://127.0.0.1:1/polkadot".to_string(),
+				name: "polkadot".to_string(),
+				checksum: None,
+			},
+			cache: temp_dir.path().to_path_buf(),
+		};
+		assert!(matches!(
+			binary.source(true, &Output, true).await,
+			Err(Error::Offline(missing)) if missing == [binary.path().display().to_string()]
+		));
+
+		// Cached artifacts are available when offline
+		File::create(binary.path())?;
+		binary.source(true, &Output, true).await?;
+		Ok(())
+	}
+
+	#[test]
+	fn resolve_version_uses_cache_when_offline() -> Result<()> {
+		if !isolated("sourcing::binary::tests::resolve_version_uses_cache_when_offline") {
+			return Ok(());
+		}
+		let _offline = Offline::enable();
+		let name = "polkadot";
+		let temp_dir = tempdir()?;
+		let cache = temp_dir.path();
+		let none: [&str; 0] = [];
+		assert_eq!(Binary::resolve_version(name, None, &none, cache), None);
+		let file = format!("{name}-v1.12.0");
+		File::create(cache.join(&file))?;
+		let artifact = Artifact::new(&cache.join(&file), name, Some("v1.12.0"), String::new())?;
+		Manifest::update(cache, &(), |m| m.insert(file, artifact))?;
+		assert_eq!(Binary::resolve_version(name, None, &none, cache).unwrap(), "v1.12.0");
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn sourcing_records_provenance() -> Result<()> {
+		let name = "polkadot";
+		let mut server = mockito::Server::new_async().await;
+		let mock = server.mock("GET", "/polkadot").with_body("polkadot").create_async().await;
+		let url = format!("{}/polkadot", server.url());
+		let temp_dir = tempdir()?;
+
+		let binary = Binary::Source {
+			name: name.to_string(),
+			source: Source::Url { url: url.clone(), name: name.to_string(), checksum: None },
+			cache: temp_dir.path().to_path_buf(),
+		};
+		binary.source(true, &Output, true).await?;
+		mock.assert_async().await;
+
+		let manifest = Manifest::load(temp_dir.path())?;
+		let artifact = manifest.get(name).unwrap();
+		assert_eq!(artifact.binary, name);
+		assert_eq!(artifact.version, None);
+		assert_eq!(artifact.source, url);
+		assert_eq!(artifact.size, 8);
+		assert_eq!(artifact.target.as_deref(), target().ok());
+		assert!(!binary.stale());
+
+		// A cached artifact which no longer matches its recorded provenance is stale
+		std::fs::write(binary.path(), "corrupt")?;
+		assert!(binary.stale());
+		Ok(())
+	}
 }
@@ -0,0 +1,284 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{sourcing::Error, target, Status};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use std::{
+	collections::BTreeMap,
+	fs::{read_to_string, rename, write, File},
+	io::copy,
+	path::Path,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The name of the manifest file within a cache.
+pub const MANIFEST: &str = "manifest.json";
+
+/// The name of the file used to serialize updates to a manifest, as binaries may be sourced
+/// concurrently, including by separate processes.
+const LOCK: &str = ".manifest.lock";
+
+/// A manifest recording the provenance of the artifacts within a cache.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Manifest {
+	/// The cached artifacts, keyed by file name.
+	artifacts: BTreeMap<String, Artifact>,
+}
+
+impl Manifest {
+	/// Loads the manifest of the specified cache, defaulting to an empty manifest when none exists.
+	///
+	/// # Arguments
+	/// * `cache` - The location of the cache.
+	pub fn load(cache: &Path) -> Result<Self, Error> {
+		let path = cache.join(MANIFEST);
+		if !path.exists() {
+			return Ok(Self::default());
+		}
+		Ok(serde_json::from_str(&read_to_string(path)?)?)
+	}
+
+	/// Saves the manifest to the specified cache. The manifest is written to a temporary file
+	/// first, so that it is never left partially written.
+	///
+	/// # Arguments
+	/// * `cache` - The location of the cache.
+	pub fn save(&self, cache: &Path) -> Result<(), Error> {
+		let temp = cache.join(format!(".{MANIFEST}.tmp"));
+		write(&temp, serde_json::to_string_pretty(self)?)?;
+		rename(temp, cache.join(MANIFEST))?;
+		Ok(())
+	}
+
+	/// Updates the manifest of the specified cache, ensuring concurrent updates are not lost. A
+	/// manifest which cannot be read is replaced, as it only records provenance.
+	///
+	/// # Arguments
+	/// * `cache` - The location of the cache.
+	/// * `status` - Used to observe status updates.
+	/// * `update` - The update to be applied to the manifest.
+	pub fn update<T>(
+		cache: &Path,
+		status: &impl Status,
+		update: impl FnOnce(&mut Manifest) -> T,
+	) -> Result<T, Error> {
+		let lock = File::create(cache.join(LOCK))?;
+		lock.lock()?;
+		let mut manifest = Self::load(cache).unwrap_or_else(|e| {
+			status.warning(&format!(
+				"⚠️ The cache manifest at {} could not be read and will be replaced: {e}",
+				cache.join(MANIFEST).display()
+			));
+			Self::default()
+		});
+		let result = update(&mut manifest);
+		manifest.save(cache)?;
+		Ok(result)
+	}
+
+	/// Whether the artifact at the specified path is intact: i.e. it matches its recorded
+	/// provenance. Artifacts without any recorded provenance are assumed to be intact.
+	///
+	/// # Arguments
+	/// * `file` - The file name of the artifact within the cache.
+	/// * `path` - The path of the artifact.
+	pub fn intact(&self, file: &str, path: &Path) -> bool {
+		self.get(file).is_none_or(|artifact| artifact.matches(path))
+	}
+
+	/// The recorded artifacts, keyed by file name.
+	pub fn artifacts(&self) -> impl Iterator<Item = (&str, &Artifact)> {
+		self.artifacts.iter().map(|(file, artifact)| (file.as_str(), artifact))
+	}
+
+	/// The artifact recorded for the specified file, if any.
+	///
+	/// # Arguments
+	/// * `file` - The file name of the artifact within the cache.
+	pub fn get(&self, file: &str) -> Option<&Artifact> {
+		self.artifacts.get(file)
+	}
+
+	/// Records an artifact, replacing any existing record for the same file.
+	///
+	/// # Arguments
+	/// * `file` - The file name of the artifact within the cache.
+	/// * `artifact` - The provenance of the artifact.
+	pub fn insert(&mut self, file: impl Into<String>, artifact: Artifact) {
+		self.artifacts.insert(file.into(), artifact);
+	}
+
+	/// Removes the record of an artifact, returning it if it existed.
+	///
+	/// # Arguments
+	/// * `file` - The file name of the artifact within the cache.
+	pub fn remove(&mut self, file: &str) -> Option<Artifact> {
+		self.artifacts.remove(file)
+	}
+
+	/// The recorded versions of the specified binary.
+	///
+	/// # Arguments
+	/// * `binary` - The name of the binary.
+	pub fn versions<'a>(&'a self, binary: &'a str) -> impl Iterator<Item = &'a str> {
+		self.artifacts
+			.values()
+			.filter(move |a| a.binary == binary)
+			.filter_map(|a| a.version.as_deref())
+	}
+}
+
+/// The provenance of a cached artifact.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Artifact {
+	/// The name of the binary the artifact was sourced for.
+	pub binary: String,
+	/// If applicable, the version of the binary - i.e. a release tag, branch or commit.
+	pub version: Option<String>,
+	/// The location the artifact was sourced from.
+	pub source: String,
+	/// The SHA-256 digest of the artifact, hex encoded.
+	pub digest: String,
+	/// The size of the artifact in bytes.
+	pub size: u64,
+	/// When the artifact was sourced, in seconds since the Unix epoch.
+	pub sourced_at: u64,
+	/// If known, the target triple of the artifact.
+	pub target: Option<String>,
+}
+
+impl Artifact {
+	/// Describes the provenance of the artifact at the specified path.
+	///
+	/// # Arguments
+	/// * `path` - The path of the artifact.
+	/// * `binary` - The name of the binary the artifact was sourced for.
+	/// * `version` - If applicable, the version of the binary.
+	/// * `source` - The location the artifact was sourced from.
+	pub(crate) fn new(
+		path: &Path,
+		binary: &str,
+		version: Option<&str>,
+		source: String,
+	) -> Result<Self, Error> {
+		let mut hasher = Sha256::new();
+		let size = copy(&mut File::open(path)?, &mut hasher)?;
+		Ok(Self {
+			binary: binary.to_string(),
+			version: version.map(|v| v.to_string()),
+			source,
+			digest: format!("{:x}", hasher.finalize()),
+			size,
+			sourced_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+			target: target().ok().map(|t| t.to_string()),
+		})
+	}
+
+	/// Whether the file at the specified path matches the recorded size and digest. The digest is
+	/// always recomputed, as modification times can be preserved or reset when a file is altered.
+	///
+	/// # Arguments
+	/// * `path` - The path of the artifact.
+	pub fn matches(&self, path: &Path) -> bool {
+		let Ok(metadata) = path.metadata() else {
+			return false;
+		};
+		if metadata.len() != self.size {
+			return false;
+		}
+		let mut hasher = Sha256::new();
+		File::open(path)
+			.and_then(|mut file| copy(&mut file, &mut hasher))
+			.is_ok_and(|_| format!("{:x}", hasher.finalize()) == self.digest)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sourcing::tests::Warnings;
+	use anyhow::Result;
+	use tempfile::tempdir;
+
+	#[test]
+	fn load_defaults_when_missing() -> Result<()> {
+		let temp_dir = tempdir()?;
+		assert_eq!(Manifest::load(temp_dir.path())?, Manifest::default());
+		assert!(!temp_dir.path().join(MANIFEST).exists());
+		Ok(())
+	}
+
+	#[test]
+	fn update_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let cache = temp_dir.path();
+		let path = cache.join("polkadot-v1.12.0");
+		write(&path, "polkadot")?;
+		let artifact =
+			Artifact::new(&path, "polkadot", Some("v1.12.0"), "https://github.com".into())?;
+		assert_eq!(artifact.digest, format!("{:x}", Sha256::digest(b"polkadot")));
+		assert_eq!(artifact.size, 8);
+		assert_eq!(artifact.target.as_deref(), target().ok());
+
+		Manifest::update(cache, &(), |m| m.insert("polkadot-v1.12.0", artifact.clone()))?;
+		let mut manifest = Manifest::load(cache)?;
+		assert_eq!(manifest.get("polkadot-v1.12.0"), Some(&artifact));
+		assert_eq!(manifest.artifacts().count(), 1);
+		assert_eq!(manifest.versions("polkadot").collect::<Vec<_>>(), ["v1.12.0"]);
+		assert_eq!(manifest.versions("pop-node").count(), 0);
+
+		assert_eq!(manifest.remove("polkadot-v1.12.0"), Some(artifact));
+		manifest.save(cache)?;
+		assert_eq!(Manifest::load(cache)?, Manifest::default());
+		assert!(!cache.join(format!(".{MANIFEST}.tmp")).exists());
+		Ok(())
+	}
+
+	#[test]
+	fn update_replaces_corrupt_manifest() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let cache = temp_dir.path();
+		write(cache.join(MANIFEST), "{\"artifacts\": {")?;
+		assert!(Manifest::load(cache).is_err());
+
+		let path = cache.join("polkadot");
+		write(&path, "polkadot")?;
+		let artifact = Artifact::new(&path, "polkadot", None, "https://github.com".into())?;
+		let warnings = Warnings::default();
+		Manifest::update(cache, &warnings, |m| m.insert("polkadot", artifact.clone()))?;
+		assert_eq!(
+			*warnings.0.lock().unwrap(),
+			[format!(
+				"⚠️ The cache manifest at {} could not be read and will be replaced: JSON error: EOF while parsing an object at line 1 column 15",
+				cache.join(MANIFEST).display()
+			)]
+		);
+		assert_eq!(Manifest::load(cache)?.get("polkadot"), Some(&artifact));
+		Ok(())
+	}
+
+	#[test]
+	fn intact_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let cache = temp_dir.path();
+		let path = cache.join("polkadot");
+		write(&path, "polkadot")?;
+		let mut manifest = Manifest::default();
+		assert!(manifest.intact("polkadot", &path));
+
+		let artifact = Artifact::new(&path, "polkadot", None, "https://github.com".into())?;
+		manifest.insert("polkadot", artifact);
+		assert!(manifest.intact("polkadot", &path));
+		assert!(!manifest.intact("polkadot", &cache.join("missing")));
+
+		// Truncated
+		write(&path, "polka")?;
+		assert!(!manifest.intact("polkadot", &path));
+		// Modified, but same size and with its modification time preserved
+		let modified = path.metadata()?.modified()?;
+		write(&path, "POLKADOT")?;
+		File::options().write(true).open(&path)?.set_modified(modified)?;
+		assert!(!manifest.intact("polkadot", &path));
+		Ok(())
+	}
+}
@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use serde::Deserialize;
+use std::{fs::read_to_string, path::Path, sync::OnceLock};
+
+/// The environment variable used to specify a mirror of GitHub from which binaries are sourced.
+pub const POP_MIRROR: &str = "POP_MIRROR";
+
+const GITHUB_URL: &str = "https://github.com";
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// The subset of the pop configuration file relating to sourcing.
+#[derive(Deserialize)]
+struct Config {
+	mirror: Option<String>,
+}
+
+/// The mirror of GitHub to be used when sourcing binaries, if configured. The mirror is
+/// determined by the `POP_MIRROR` environment variable, otherwise the `mirror` option within the
+/// pop configuration file.
+///
+/// A mirror is expected to have the same layout as GitHub - i.e.
+/// `{mirror}/{owner}/{repository}/releases/download/{tag}/{archive}` - with any release listings
+/// available at `{mirror}/api/repos/{owner}/{repository}/releases`.
+pub fn mirror() -> Option<&'static str> {
+	static MIRROR: OnceLock<Option<String>> = OnceLock::new();
+	MIRROR
+		.get_or_init(|| {
+			resolve(std::env::var(POP_MIRROR).ok(), || {
+				let path = dirs::config_dir()?.join("pop").join("config.json");
+				from_config(&path)
+			})
+		})
+		.as_deref()
+}
+
+/// Resolves the mirror to be used, preferring any specified via the environment over that within
+/// the configuration file.
+///
+/// # Arguments
+/// * `env` - The value of the `POP_MIRROR` environment variable, if set.
+/// * `config` - Reads any mirror specified within the configuration file.
+fn resolve(env: Option<String>, config: impl FnOnce() -> Option<String>) -> Option<String> {
+	env.filter(|m| !m.trim().is_empty())
+		.or_else(config)
+		.map(|m| m.trim().trim_end_matches('/').to_string())
+}
+
+/// The base url of GitHub, or of the configured mirror.
+pub fn github_url() -> String {
+	github_url_from(mirror())
+}
+
+/// The base url of the GitHub API, or of the API of the configured mirror.
+pub fn github_api_url() -> String {
+	github_api_url_from(mirror())
+}
+
+/// The base url of GitHub, or of the specified mirror.
+///
+/// # Arguments
+/// * `mirror` - The mirror of GitHub, if any.
+pub(crate) fn github_url_from(mirror: Option<&str>) -> String {
+	mirror.map_or_else(|| GITHUB_URL.to_string(), |m| m.to_string())
+}
+
+/// The base url of the GitHub API, or of the API of the specified mirror.
+///
+/// # Arguments
+/// * `mirror` - The mirror of GitHub, if any.
+pub(crate) fn github_api_url_from(mirror: Option<&str>) -> String {
+	mirror.map_or_else(|| GITHUB_API_URL.to_string(), |m| format!("{m}/api"))
+}
+
+/// Reads any mirror specified within a configuration file.
+///
+/// # Arguments
+/// * `path` - The path of the configuration file.
+fn from_config(path: &Path) -> Option<String> {
+	let config: Config = serde_json::from_str(&read_to_string(path).ok()?).ok()?;
+	config.mirror.filter(|m| !m.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use std::fs::write;
+	use tempfile::tempdir;
+
+	const MIRROR: &str = "https://mirror.example.com";
+
+	#[test]
+	fn from_config_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let path = temp_dir.path().join("config.json");
+		assert_eq!(from_config(&path), None);
+
+		write(&path, r#"{"opt_out":{"version":"0.5.0"}}"#)?;
+		assert_eq!(from_config(&path), None);
+
+		write(&path, r#"{"opt_out":{"version":""},"mirror":"https://mirror.example.com"}"#)?;
+		assert_eq!(from_config(&path).as_deref(), Some("https://mirror.example.com"));
+
+		write(&path, r#"{"mirror":" "}"#)?;
+		assert_eq!(from_config(&path), None);
+		Ok(())
+	}
+
+	#[test]
+	fn resolve_works() {
+		let config = || Some("https://config.example.com".to_string());
+		assert_eq!(resolve(None, || None), None);
+		assert_eq!(resolve(Some(" ".into()), || None), None);
+		assert_eq!(resolve(None, config).as_deref(), Some("https://config.example.com"));
+		assert_eq!(
+			resolve(Some(" https://mirror.example.com/ ".into()), config).as_deref(),
+			Some("https://mirror.example.com")
+		);
+	}
+
+	#[test]
+	fn github_url_from_works() {
+		assert_eq!(github_url_from(None), "https://github.com");
+		assert_eq!(github_url_from(Some(MIRROR)), MIRROR);
+	}
+
+	#[test]
+	fn github_api_url_from_works() {
+		assert_eq!(github_api_url_from(None), "https://api.github.com");
+		assert_eq!(github_api_url_from(Some(MIRROR)), "https://mirror.example.com/api");
+	}
+}
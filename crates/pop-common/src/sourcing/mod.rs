@@ -2,21 +2,30 @@
 
 mod binary;
 pub use binary::*;
+pub mod cache;
+mod mirror;
+pub use mirror::*;
 
 use crate::{Git, Status, APP_USER_AGENT};
 use duct::cmd;
 use flate2::read::GzDecoder;
-use reqwest::StatusCode;
+use reqwest::{header::RANGE, Client, StatusCode};
+use sha2::{Digest as _, Sha256};
 use std::{
 	fs::{copy, metadata, read_dir, rename, File},
-	io::{BufRead, Seek, SeekFrom, Write},
+	io::{BufRead, Read, Seek, SeekFrom, Write},
 	os::unix::fs::PermissionsExt,
 	path::{Path, PathBuf},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		LazyLock,
+	},
 	time::Duration,
 };
 use tar::Archive;
-use tempfile::{tempdir, tempfile};
+use tempfile::{tempdir, tempfile, NamedTempFile};
 use thiserror::Error;
+use tokio::sync::Semaphore;
 use url::Url;
 
 #[derive(Error, Debug)]
@@ -25,16 +34,78 @@ pub enum Error {
 	AnyhowError(#[from] anyhow::Error),
 	#[error("Archive error: {0}")]
 	ArchiveError(String),
+	#[error("Checksum error: {0}")]
+	ChecksumError(String),
+	#[error("Checksum mismatch for {url}: expected {expected}, found {actual}")]
+	ChecksumMismatch { url: String, expected: String, actual: String },
 	#[error("HTTP error: {0}")]
 	HttpError(#[from] reqwest::Error),
 	#[error("IO error: {0}")]
 	IO(#[from] std::io::Error),
+	#[error("JSON error: {0}")]
+	JsonError(#[from] serde_json::Error),
 	#[error("Missing binary: {0}")]
 	MissingBinary(String),
+	#[error("The following artifacts are not cached and cannot be sourced while offline: {}", .0.join(", "))]
+	Offline(Vec<String>),
 	#[error("ParseError error: {0}")]
 	ParseError(#[from] url::ParseError),
 }
 
+/// The environment variable used to enable offline mode.
+pub const POP_OFFLINE: &str = "POP_OFFLINE";
+
+/// The environment variable used to allow sourcing artifacts whose published checksum is missing,
+/// without verifying their integrity.
+pub const POP_ALLOW_UNVERIFIED: &str = "POP_ALLOW_UNVERIFIED";
+
+/// The environment variable used to configure the number of packages built concurrently.
+pub const POP_BUILD_JOBS: &str = "POP_BUILD_JOBS";
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+// Limits the number of packages built concurrently, as cargo already makes use of all available
+// cores. Configurable via `POP_BUILD_JOBS`.
+static BUILDS: LazyLock<Semaphore> =
+	LazyLock::new(|| Semaphore::new(build_jobs(std::env::var(POP_BUILD_JOBS).ok().as_deref())));
+
+/// The number of packages which may be built concurrently, defaulting to one.
+///
+/// # Arguments
+/// * `value` - The configured number of packages, if any.
+fn build_jobs(value: Option<&str>) -> usize {
+	value.and_then(|v| v.trim().parse().ok()).filter(|jobs| *jobs > 0).unwrap_or(1)
+}
+
+/// Whether offline mode is enabled, either explicitly or via the `POP_OFFLINE` environment
+/// variable. When offline, binaries are resolved purely from the cache and GitHub is never queried.
+pub fn offline() -> bool {
+	OFFLINE.load(Ordering::Relaxed) || enabled(std::env::var(POP_OFFLINE).ok().as_deref())
+}
+
+/// Whether sourcing artifacts without verifying their integrity is allowed, via the
+/// `POP_ALLOW_UNVERIFIED` environment variable, when a checksum is expected to be published but is
+/// missing.
+fn allow_unverified() -> bool {
+	enabled(std::env::var(POP_ALLOW_UNVERIFIED).ok().as_deref())
+}
+
+/// Whether the value of a boolean environment variable enables the corresponding option.
+///
+/// # Arguments
+/// * `value` - The value of the environment variable, if set.
+fn enabled(value: Option<&str>) -> bool {
+	value.is_some_and(|v| !matches!(v.to_lowercase().as_str(), "" | "0" | "false"))
+}
+
+/// Enables or disables offline mode.
+///
+/// # Arguments
+/// * `offline` - Whether binaries should be resolved purely from the cache.
+pub fn set_offline(offline: bool) {
+	OFFLINE.store(offline, Ordering::Relaxed);
+}
+
 /// The source of a binary.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Source {
@@ -45,6 +116,8 @@ pub enum Source {
 		url: String,
 		/// The archive contents required, including the binary name.
 		contents: Vec<String>,
+		/// If applicable, the checksum used to verify the integrity of the archive.
+		checksum: Option<Checksum>,
 	},
 	/// A git repository.
 	Git {
@@ -68,6 +141,8 @@ pub enum Source {
 		url: String,
 		/// The name of the binary.
 		name: String,
+		/// If applicable, the checksum used to verify the integrity of the binary.
+		checksum: Option<Checksum>,
 	},
 }
 
@@ -90,33 +165,56 @@ impl Source {
 	) -> Result<(), Error> {
 		use Source::*;
 		match self {
-			Archive { url, contents } => {
-				let contents: Vec<_> =
-					contents.iter().map(|name| (name.as_str(), cache.join(name))).collect();
-				from_archive(url, &contents, status).await
-			},
-			Git { url, reference, manifest, package, artifacts } => {
-				let artifacts: Vec<_> = artifacts
-					.iter()
-					.map(|name| match reference {
-						Some(version) => (name.as_str(), cache.join(format!("{name}-{version}"))),
-						None => (name.as_str(), cache.join(name)),
-					})
-					.collect();
+			Archive { url, checksum, .. } =>
+				from_archive(url, &self.artifacts(cache), checksum.as_ref(), status).await,
+			Git { url, reference, manifest, package, .. } =>
 				from_git(
 					url.as_str(),
 					reference.as_deref(),
 					manifest.as_ref(),
 					package,
-					&artifacts,
+					&self.artifacts(cache),
 					release,
 					status,
 					verbose,
 				)
-				.await
-			},
+				.await,
 			GitHub(source) => source.source(cache, release, status, verbose).await,
-			Url { url, name } => from_url(url, &cache.join(name), status).await,
+			Url { url, name, checksum } =>
+				from_url(url, &cache.join(name), checksum.as_ref(), status).await,
+		}
+	}
+
+	/// The artifacts resulting from sourcing the binary, as pairs of the name of each artifact
+	/// within the source and its destination within the cache.
+	///
+	/// # Arguments
+	///
+	/// * `cache` - the cache to be used.
+	pub(super) fn artifacts(&self, cache: &Path) -> Vec<(&str, PathBuf)> {
+		use Source::*;
+		match self {
+			Archive { contents, .. } =>
+				contents.iter().map(|name| (name.as_str(), cache.join(name))).collect(),
+			Git { reference, artifacts, .. } => artifacts
+				.iter()
+				.map(|name| match reference {
+					Some(version) => (name.as_str(), cache.join(format!("{name}-{version}"))),
+					None => (name.as_str(), cache.join(name)),
+				})
+				.collect(),
+			GitHub(source) => source.artifacts(cache),
+			Url { name, .. } => vec![(name.as_str(), cache.join(name))],
+		}
+	}
+
+	/// The location the binary is sourced from.
+	pub(super) fn url(&self) -> String {
+		use Source::*;
+		match self {
+			Archive { url, .. } | Url { url, .. } => url.clone(),
+			Git { url, .. } => url.to_string(),
+			GitHub(source) => source.url(),
 		}
 	}
 }
@@ -139,6 +237,8 @@ pub enum GitHub {
 		/// The archive contents required, including the binary name.
 		/// The second parameter can be used to specify another name for the binary once extracted.
 		contents: Vec<(&'static str, Option<String>)>,
+		/// If applicable, the checksum used to verify the integrity of the archive.
+		checksum: Option<Checksum>,
 		/// If applicable, the latest release tag available.
 		latest: Option<String>,
 	},
@@ -178,77 +278,227 @@ impl GitHub {
 	) -> Result<(), Error> {
 		use GitHub::*;
 		match self {
-			ReleaseArchive { owner, repository, tag, tag_format, archive, contents, .. } => {
-				// Complete url and contents based on tag
-				let base_url = format!("https://github.com/{owner}/{repository}/releases");
-				let url = match tag.as_ref() {
-					Some(tag) => {
-						let tag = tag_format.as_ref().map_or_else(
-							|| tag.to_string(),
-							|tag_format| tag_format.replace("{tag}", tag),
-						);
-						format!("{base_url}/download/{tag}/{archive}")
-					},
-					None => format!("{base_url}/latest/download/{archive}"),
-				};
-				let contents: Vec<_> = contents
-					.iter()
-					.map(|(name, target)| match tag.as_ref() {
-						Some(tag) => (
-							*name,
-							cache.join(format!(
-								"{}-{tag}",
-								target.as_ref().map_or(*name, |t| t.as_str())
-							)),
-						),
-						None => (*name, cache.join(target.as_ref().map_or(*name, |t| t.as_str()))),
-					})
-					.collect();
-				from_archive(&url, &contents, status).await
-			},
-			SourceCodeArchive { owner, repository, reference, manifest, package, artifacts } => {
-				let artifacts: Vec<_> = artifacts
-					.iter()
-					.map(|name| match reference {
-						Some(reference) =>
-							(name.as_str(), cache.join(format!("{name}-{reference}"))),
-						None => (name.as_str(), cache.join(name)),
-					})
-					.collect();
+			ReleaseArchive { checksum, .. } =>
+				from_archive(&self.url(), &self.artifacts(cache), checksum.as_ref(), status).await,
+			SourceCodeArchive { owner, repository, reference, manifest, package, .. } =>
 				from_github_archive(
 					owner,
 					repository,
 					reference.as_ref().map(|r| r.as_str()),
 					manifest.as_ref(),
 					package,
-					&artifacts,
+					&self.artifacts(cache),
 					release,
 					status,
 					verbose,
 				)
-				.await
+				.await,
+		}
+	}
+
+	/// The artifacts resulting from sourcing the binary, as pairs of the name of each artifact
+	/// within the source and its destination within the cache.
+	///
+	/// # Arguments
+	///
+	/// * `cache` - the cache to be used.
+	fn artifacts(&self, cache: &Path) -> Vec<(&str, PathBuf)> {
+		use GitHub::*;
+		match self {
+			ReleaseArchive { tag, contents, .. } => contents
+				.iter()
+				.map(|(name, target)| {
+					let target = target.as_ref().map_or(*name, |t| t.as_str());
+					match tag.as_ref() {
+						Some(tag) => (*name, cache.join(format!("{target}-{tag}"))),
+						None => (*name, cache.join(target)),
+					}
+				})
+				.collect(),
+			SourceCodeArchive { reference, artifacts, .. } => artifacts
+				.iter()
+				.map(|name| match reference {
+					Some(reference) => (name.as_str(), cache.join(format!("{name}-{reference}"))),
+					None => (name.as_str(), cache.join(name)),
+				})
+				.collect(),
+		}
+	}
+
+	/// The location the binary is sourced from.
+	fn url(&self) -> String {
+		self.url_from(mirror())
+	}
+
+	/// The location the binary is sourced from, using the specified mirror of GitHub.
+	///
+	/// # Arguments
+	/// * `mirror` - The mirror of GitHub, if any.
+	fn url_from(&self, mirror: Option<&str>) -> String {
+		use GitHub::*;
+		let github_url = github_url_from(mirror);
+		match self {
+			ReleaseArchive { owner, repository, tag, tag_format, archive, .. } => {
+				// Complete url based on tag
+				let base_url = format!("{github_url}/{owner}/{repository}/releases");
+				match tag.as_ref() {
+					Some(tag) => {
+						let tag = tag_format.as_ref().map_or_else(
+							|| tag.to_string(),
+							|tag_format| tag_format.replace("{tag}", tag),
+						);
+						format!("{base_url}/download/{tag}/{archive}")
+					},
+					None => format!("{base_url}/latest/download/{archive}"),
+				}
+			},
+			SourceCodeArchive { owner, repository, .. } =>
+				format!("{github_url}/{owner}/{repository}"),
+		}
+	}
+}
+
+/// A checksum used to verify the integrity of a downloaded artifact.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Checksum {
+	/// The expected SHA-256 digest of the artifact, hex encoded.
+	Sha256(String),
+	/// The SHA-256 digest is published alongside the artifact as `{artifact}.sha256`.
+	Sha256File,
+	/// The SHA-256 digest is published alongside the artifact within the specified checksums file
+	/// (e.g. `SHA256SUMS`).
+	Sha256Sums(String),
+}
+
+impl Checksum {
+	/// Resolves the expected SHA-256 digest of the artifact located at the specified url, if
+	/// published.
+	///
+	/// # Arguments
+	/// * `url` - The url of the artifact.
+	async fn resolve(&self, url: &str) -> Result<Option<String>, Error> {
+		let url = Url::parse(url)?;
+		let artifact = url
+			.path_segments()
+			.and_then(|mut s| s.next_back())
+			.filter(|a| !a.is_empty())
+			.ok_or_else(|| {
+				Error::ChecksumError(format!(
+					"the artifact name could not be determined from {url}"
+				))
+			})?;
+		// Published checksums are located relative to the artifact, with a checksum which has
+		// not been published being unavailable rather than an error
+		let published = |file: String| {
+			let url = url.join(&file);
+			async move {
+				match crate::GitHub::fetch(&url?).await {
+					Ok(response) => Ok(Some(response.text().await?)),
+					Err(e)
+						if e.downcast_ref::<reqwest::Error>().and_then(|e| e.status()) ==
+							Some(StatusCode::NOT_FOUND) =>
+						Ok(None),
+					Err(e) => Err(Error::from(e)),
+				}
+			}
+		};
+		let digest = match self {
+			Checksum::Sha256(digest) => Some(digest.clone()),
+			Checksum::Sha256File => match published(format!("{artifact}.sha256")).await? {
+				Some(published) => published.split_whitespace().next().map(|d| d.to_string()),
+				None => return Ok(None),
 			},
+			Checksum::Sha256Sums(file) => match published(file.clone()).await? {
+				Some(published) => published.lines().find_map(|l| {
+					// Format: `{digest}  {file}`, where the file may be prefixed with `*`
+					// (binary mode)
+					let mut parts = l.split_whitespace();
+					let digest = parts.next()?;
+					let file = parts.next()?.trim_start_matches('*').trim_start_matches("./");
+					(file == artifact).then(|| digest.to_string())
+				}),
+				None => return Ok(None),
+			},
+		};
+		digest
+			.map(|d| d.to_lowercase())
+			.filter(|d| d.len() == 64 && d.chars().all(|c| c.is_ascii_hexdigit()))
+			.map(Some)
+			.ok_or_else(|| {
+				Error::ChecksumError(format!("a valid SHA-256 digest for {artifact} was not found"))
+			})
+	}
+
+	/// Verifies that the contents downloaded from the specified url match the expected digest,
+	/// failing when no checksum has been published for the contents unless explicitly allowed
+	/// via `POP_ALLOW_UNVERIFIED`.
+	///
+	/// # Arguments
+	/// * `url` - The url the contents were downloaded from.
+	/// * `contents` - The downloaded contents.
+	/// * `status` - Used to observe status updates.
+	async fn verify(
+		&self,
+		url: &str,
+		mut contents: impl Read,
+		status: &impl Status,
+	) -> Result<(), Error> {
+		let Some(expected) = self.resolve(url).await? else {
+			return unverified(url, allow_unverified(), status);
+		};
+		let mut hasher = Sha256::new();
+		std::io::copy(&mut contents, &mut hasher)?;
+		let actual = format!("{:x}", hasher.finalize());
+		if actual != expected {
+			return Err(Error::ChecksumMismatch { url: url.to_string(), expected, actual });
 		}
+		Ok(())
 	}
 }
 
+/// Handles contents whose published checksum is missing, which is an error unless sourcing
+/// unverified contents is allowed.
+///
+/// # Arguments
+/// * `url` - The url the contents were downloaded from.
+/// * `allowed` - Whether sourcing unverified contents is allowed.
+/// * `status` - Used to observe status updates.
+fn unverified(url: &str, allowed: bool, status: &impl Status) -> Result<(), Error> {
+	if !allowed {
+		return Err(Error::ChecksumError(format!(
+			"no checksum is published for {url}, so its integrity cannot be verified. Set {POP_ALLOW_UNVERIFIED}=1 to source it regardless"
+		)));
+	}
+	status.warning(&format!(
+		"⚠️ No checksum is published for {url}, so its integrity could not be verified."
+	));
+	Ok(())
+}
+
 /// Source binary by downloading and extracting from an archive.
 ///
 /// # Arguments
 /// * `url` - The url of the archive.
 /// * `contents` - The contents within the archive which are required.
+/// * `checksum` - If applicable, the checksum used to verify the integrity of the archive.
 /// * `status` - Used to observe status updates.
 async fn from_archive(
 	url: &str,
 	contents: &[(&str, PathBuf)],
+	checksum: Option<&Checksum>,
 	status: &impl Status,
 ) -> Result<(), Error> {
 	// Download archive
 	status.update(&format!("Downloading from {url}..."));
-	let response = reqwest::get(url).await?.error_for_status()?;
 	let mut file = tempfile()?;
-	file.write_all(&response.bytes().await?)?;
+	stream(url, &mut file, status).await?;
 	file.seek(SeekFrom::Start(0))?;
+	// Verify archive integrity before anything is extracted
+	if let Some(checksum) = checksum {
+		status.update("Verifying checksum...");
+		checksum.verify(url, &mut file, status).await?;
+		file.seek(SeekFrom::Start(0))?;
+	}
 	// Extract contents
 	status.update("Extracting from archive...");
 	let tar = GzDecoder::new(file);
@@ -336,39 +586,21 @@ async fn from_github_archive(
 	status: &impl Status,
 	verbose: bool,
 ) -> Result<(), Error> {
-	// User agent required when using GitHub API
-	let client = reqwest::ClientBuilder::new().user_agent(APP_USER_AGENT).build()?;
-	let response =
-		match reference {
-			Some(reference) => {
-				// Various potential urls to try based on not knowing the type of ref
-				let urls = [
-					format!("https://github.com/{owner}/{repository}/archive/refs/heads/{reference}.tar.gz"),
-					format!("https://github.com/{owner}/{repository}/archive/refs/tags/{reference}.tar.gz"),
-					format!("https://github.com/{owner}/{repository}/archive/{reference}.tar.gz"),
-				];
-				let mut response = None;
-				for url in urls {
-					status.update(&format!("Downloading from {url}..."));
-					response = Some(client.get(url).send().await?.error_for_status());
-					if let Some(Err(e)) = &response {
-						if e.status() == Some(StatusCode::NOT_FOUND) {
-							tokio::time::sleep(Duration::from_secs(1)).await;
-							continue;
-						}
-					}
-					break;
-				}
-				response.expect("value set above")?
-			},
-			None => {
-				let url = format!("https://api.github.com/repos/{owner}/{repository}/tarball");
-				status.update(&format!("Downloading from {url}..."));
-				client.get(url).send().await?.error_for_status()?
-			},
-		};
 	let mut file = tempfile()?;
-	file.write_all(&response.bytes().await?)?;
+	let urls = source_code_archive_urls(owner, repository, reference, mirror());
+	for (i, url) in urls.iter().enumerate() {
+		status.update(&format!("Downloading from {url}..."));
+		match stream(url, &mut file, status).await {
+			Err(Error::HttpError(e))
+				if e.status() == Some(StatusCode::NOT_FOUND) && i < urls.len() - 1 =>
+			{
+				tokio::time::sleep(Duration::from_secs(1)).await;
+				continue;
+			},
+			result => result?,
+		}
+		break;
+	}
 	file.seek(SeekFrom::Start(0))?;
 	// Extract contents
 	status.update("Extracting from archive...");
@@ -398,6 +630,33 @@ async fn from_github_archive(
 	Ok(())
 }
 
+/// The urls from which a source code archive can be downloaded, in the order they should be tried.
+/// The type of any reference is unknown, so urls for a branch, tag and commit are all included.
+///
+/// # Arguments
+/// * `owner` - The owner of the repository.
+/// * `repository` - The name of the repository.
+/// * `reference` - If applicable, the branch, tag or commit.
+/// * `mirror` - The mirror of GitHub, if any.
+fn source_code_archive_urls(
+	owner: &str,
+	repository: &str,
+	reference: Option<&str>,
+	mirror: Option<&str>,
+) -> Vec<String> {
+	match reference {
+		Some(reference) => {
+			let base_url = format!("{}/{owner}/{repository}/archive", github_url_from(mirror));
+			vec![
+				format!("{base_url}/refs/heads/{reference}.tar.gz"),
+				format!("{base_url}/refs/tags/{reference}.tar.gz"),
+				format!("{base_url}/{reference}.tar.gz"),
+			]
+		},
+		None => vec![format!("{}/repos/{owner}/{repository}/tarball", github_api_url_from(mirror))],
+	}
+}
+
 /// Source binary by building a local package.
 ///
 /// # Arguments
@@ -426,11 +685,17 @@ pub(crate) async fn from_local_package(
 /// # Arguments
 /// * `url` - The url of the binary.
 /// * `path` - The (local) destination path.
+/// * `checksum` - If applicable, the checksum used to verify the integrity of the binary.
 /// * `status` - Used to observe status updates.
-async fn from_url(url: &str, path: &Path, status: &impl Status) -> Result<(), Error> {
+async fn from_url(
+	url: &str,
+	path: &Path,
+	checksum: Option<&Checksum>,
+	status: &impl Status,
+) -> Result<(), Error> {
 	// Download required version of binaries
 	status.update(&format!("Downloading from {url}..."));
-	download(url, path).await?;
+	download(url, path, checksum, status).await?;
 	status.update("Sourcing complete.");
 	Ok(())
 }
@@ -452,6 +717,14 @@ async fn build(
 	status: &impl Status,
 	verbose: bool,
 ) -> Result<(), Error> {
+	// Wait for any other builds to complete
+	let _permit = match BUILDS.try_acquire() {
+		Ok(permit) => permit,
+		Err(_) => {
+			status.update("Waiting for other builds to complete...");
+			BUILDS.acquire().await.expect("expected build semaphore to remain open")
+		},
+	};
 	// Define arguments
 	let manifest_path = manifest.as_ref().to_str().expect("expected manifest path to be valid");
 	let mut args = vec!["build", "-p", package, "--manifest-path", manifest_path];
@@ -489,24 +762,158 @@ async fn build(
 /// # Arguments
 /// * `url` - The url of the file.
 /// * `path` - The (local) destination path.
-async fn download(url: &str, dest: &Path) -> Result<(), Error> {
-	// Download to destination path
-	let response = reqwest::get(url).await?.error_for_status()?;
-	let mut file = File::create(dest)?;
-	file.write_all(&response.bytes().await?)?;
+/// * `checksum` - If applicable, the checksum used to verify the integrity of the file.
+/// * `status` - Used to observe status updates.
+async fn download(
+	url: &str,
+	dest: &Path,
+	checksum: Option<&Checksum>,
+	status: &impl Status,
+) -> Result<(), Error> {
+	// Download alongside the destination, so that an incomplete file is never left in its place
+	let mut file = NamedTempFile::new_in(dest.parent().unwrap_or(Path::new(".")))?;
+	stream(url, file.as_file_mut(), status).await?;
+	// Verify integrity before moving to destination path
+	if let Some(checksum) = checksum {
+		file.seek(SeekFrom::Start(0))?;
+		checksum.verify(url, file.as_file_mut(), status).await?;
+	}
 	// Make executable
-	let mut perms = metadata(dest)?.permissions();
+	let mut perms = metadata(file.path())?.permissions();
 	perms.set_mode(0o755);
-	std::fs::set_permissions(dest, perms)?;
+	std::fs::set_permissions(file.path(), perms)?;
+	file.persist(dest).map_err(|e| e.error)?;
+	Ok(())
+}
+
+/// The environment variable used to configure the number of times a failed download is retried.
+pub const POP_DOWNLOAD_RETRIES: &str = "POP_DOWNLOAD_RETRIES";
+const DEFAULT_DOWNLOAD_RETRIES: u32 = 3;
+// The minimum number of bytes downloaded between progress updates.
+const PROGRESS_INTERVAL: u64 = 1_048_576;
+
+/// Streams the contents of a url to a file, resuming an interrupted transfer where possible and
+/// retrying transient failures with exponential backoff.
+///
+/// # Arguments
+/// * `url` - The url of the contents.
+/// * `file` - The file the contents are written to.
+/// * `status` - Used to observe status updates, including the progress of the download.
+async fn stream(url: &str, file: &mut File, status: &impl Status) -> Result<(), Error> {
+	let retries = std::env::var(POP_DOWNLOAD_RETRIES)
+		.ok()
+		.and_then(|r| r.parse().ok())
+		.unwrap_or(DEFAULT_DOWNLOAD_RETRIES);
+	stream_with_retries(url, file, retries, status).await
+}
+
+/// Streams the contents of a url to a file, retrying transient failures up to the specified number
+/// of times.
+///
+/// # Arguments
+/// * `url` - The url of the contents.
+/// * `file` - The file the contents are written to.
+/// * `retries` - The maximum number of retries.
+/// * `status` - Used to observe status updates, including the progress of the download.
+async fn stream_with_retries(
+	url: &str,
+	file: &mut File,
+	retries: u32,
+	status: &impl Status,
+) -> Result<(), Error> {
+	// User agent required when using GitHub API
+	let client = reqwest::ClientBuilder::new().user_agent(APP_USER_AGENT).build()?;
+	let mut attempt = 0;
+	loop {
+		match stream_once(&client, url, file, status).await {
+			Err(Error::HttpError(e)) if attempt < retries && is_transient(&e) => {
+				let delay = Duration::from_secs(1 << attempt.min(5));
+				attempt += 1;
+				status.update(&format!(
+					"Download failed ({e}), retrying in {}s ({attempt}/{retries})...",
+					delay.as_secs()
+				));
+				tokio::time::sleep(delay).await;
+			},
+			result => return result,
+		}
+	}
+}
+
+/// Makes a single attempt at streaming the contents of a url to a file, resuming from the end of
+/// any content already within the file.
+///
+/// # Arguments
+/// * `client` - The client used to make the request.
+/// * `url` - The url of the contents.
+/// * `file` - The file the contents are written to.
+/// * `status` - Used to observe status updates, including the progress of the download.
+async fn stream_once(
+	client: &Client,
+	url: &str,
+	file: &mut File,
+	status: &impl Status,
+) -> Result<(), Error> {
+	let offset = file.seek(SeekFrom::End(0))?;
+	let mut request = client.get(url);
+	if offset > 0 {
+		request = request.header(RANGE, format!("bytes={offset}-"));
+	}
+	let response = request.send().await?;
+	// Any previous attempt already received all content
+	if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+		return Ok(());
+	}
+	let mut response = response.error_for_status()?;
+	let mut downloaded = match response.status() {
+		StatusCode::PARTIAL_CONTENT => offset,
+		_ => {
+			// Range not supported, so start again
+			file.set_len(0)?;
+			file.seek(SeekFrom::Start(0))?;
+			0
+		},
+	};
+	let total = response.content_length().map(|l| l + downloaded);
+	let mut reported = downloaded;
+	status.progress(downloaded, total);
+	while let Some(chunk) = response.chunk().await? {
+		file.write_all(&chunk)?;
+		downloaded += chunk.len() as u64;
+		if downloaded - reported >= PROGRESS_INTERVAL {
+			status.progress(downloaded, total);
+			reported = downloaded;
+		}
+	}
+	if reported != downloaded {
+		status.progress(downloaded, total);
+	}
 	Ok(())
 }
 
+/// Whether a failed request may succeed if retried.
+///
+/// # Arguments
+/// * `error` - The error resulting from the request.
+fn is_transient(error: &reqwest::Error) -> bool {
+	match error.status() {
+		Some(status) => status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+		// Connection failures, timeouts and interrupted responses
+		None => error.is_connect() || error.is_timeout() || error.is_request() || error.is_body(),
+	}
+}
+
 #[cfg(test)]
 pub(super) mod tests {
 	use super::{GitHub::*, Status, *};
-	use crate::target;
+	use crate::{
+		target,
+		test_utils::{isolated, Offline},
+	};
 	use tempfile::tempdir;
 
+	const MIRROR: &str = "https://mirror.example.com";
+
 	#[tokio::test]
 	async fn sourcing_from_archive_works() -> anyhow::Result<()> {
 		let url = "https://github.com/r0gue-io/polkadot/releases/latest/download/polkadot-aarch64-apple-darwin.tar.gz".to_string();
@@ -515,7 +922,7 @@ pub(super) mod tests {
 			vec![name.clone(), "polkadot-execute-worker".into(), "polkadot-prepare-worker".into()];
 		let temp_dir = tempdir()?;
 
-		Source::Archive { url, contents: contents.clone() }
+		Source::Archive { url, contents: contents.clone(), checksum: None }
 			.source(temp_dir.path(), true, &Output, true)
 			.await?;
 		for item in contents {
@@ -581,6 +988,7 @@ pub(super) mod tests {
 			tag_format,
 			archive,
 			contents: contents.map(|n| (n, None)).to_vec(),
+			checksum: None,
 			latest: None,
 		})
 		.source(temp_dir.path(), true, &Output, true)
@@ -610,6 +1018,7 @@ pub(super) mod tests {
 			tag_format,
 			archive,
 			contents: contents.map(|n| (n, Some(format!("{prefix}-{n}")))).to_vec(),
+			checksum: None,
 			latest: None,
 		})
 		.source(temp_dir.path(), true, &Output, true)
@@ -637,6 +1046,7 @@ pub(super) mod tests {
 			tag_format,
 			archive,
 			contents: contents.map(|n| (n, None)).to_vec(),
+			checksum: None,
 			latest: None,
 		})
 		.source(temp_dir.path(), true, &Output, true)
@@ -699,7 +1109,7 @@ pub(super) mod tests {
 		let name = "polkadot";
 		let temp_dir = tempdir()?;
 
-		Source::Url { url, name: name.into() }
+		Source::Url { url, name: name.into(), checksum: None }
 			.source(temp_dir.path(), false, &Output, true)
 			.await?;
 		assert!(temp_dir.path().join(&name).exists());
@@ -715,13 +1125,216 @@ pub(super) mod tests {
 			.map(|b| (b, temp_dir.path().join(b)))
 			.collect();
 
-		from_archive(url, &contents, &Output).await?;
+		from_archive(url, &contents, None, &Output).await?;
 		for (_, file) in contents {
 			assert!(file.exists());
 		}
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn from_archive_verifies_checksum() -> anyhow::Result<()> {
+		use flate2::{write::GzEncoder, Compression};
+		let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+		let mut header = tar::Header::new_gnu();
+		header.set_size(8);
+		header.set_mode(0o755);
+		header.set_cksum();
+		builder.append_data(&mut header, "polkadot", &b"polkadot"[..])?;
+		let archive = builder.into_inner()?.finish()?;
+		let mut server = mockito::Server::new_async().await;
+		server.mock("GET", "/polkadot.tar.gz").with_body(&archive).create_async().await;
+		let url = format!("{}/polkadot.tar.gz", server.url());
+		let temp_dir = tempdir()?;
+		let contents = [("polkadot", temp_dir.path().join("polkadot"))];
+
+		let checksum = Checksum::Sha256(format!("{:x}", Sha256::digest(b"invalid")));
+		assert!(matches!(
+			from_archive(&url, &contents, Some(&checksum), &Output).await,
+			Err(Error::ChecksumMismatch { .. })
+		));
+		assert!(!contents[0].1.exists());
+
+		let checksum = Checksum::Sha256(format!("{:x}", Sha256::digest(&archive)));
+		from_archive(&url, &contents, Some(&checksum), &Output).await?;
+		assert!(contents[0].1.exists());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn stream_resumes_partial_download() -> anyhow::Result<()> {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/archive.tar.gz")
+			.match_header("range", "bytes=4-")
+			.with_status(206)
+			.with_body("o world")
+			.create_async()
+			.await;
+		let mut file = tempfile()?;
+		file.write_all(b"hell")?;
+		let progress = Progress::default();
+
+		stream(&format!("{}/archive.tar.gz", server.url()), &mut file, &progress).await?;
+		mock.assert_async().await;
+
+		let mut contents = String::new();
+		file.seek(SeekFrom::Start(0))?;
+		file.read_to_string(&mut contents)?;
+		assert_eq!(contents, "hello world");
+		assert_eq!(progress.0.lock().unwrap().last(), Some(&(11, Some(11))));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn stream_restarts_when_range_unsupported() -> anyhow::Result<()> {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/archive.tar.gz")
+			.with_body("hello world")
+			.create_async()
+			.await;
+		let mut file = tempfile()?;
+		file.write_all(b"partial")?;
+
+		stream(&format!("{}/archive.tar.gz", server.url()), &mut file, &Output).await?;
+		mock.assert_async().await;
+
+		let mut contents = String::new();
+		file.seek(SeekFrom::Start(0))?;
+		file.read_to_string(&mut contents)?;
+		assert_eq!(contents, "hello world");
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn stream_retries_transient_failures() -> anyhow::Result<()> {
+		let mut server = mockito::Server::new_async().await;
+		let unavailable = server
+			.mock("GET", "/archive.tar.gz")
+			.with_status(503)
+			.expect(2)
+			.create_async()
+			.await;
+		let url = format!("{}/archive.tar.gz", server.url());
+
+		assert!(matches!(
+			stream_with_retries(&url, &mut tempfile()?, 1, &Output).await,
+			Err(Error::HttpError(e)) if e.status() == Some(StatusCode::SERVICE_UNAVAILABLE)
+		));
+		unavailable.assert_async().await;
+
+		// Client errors are not retried
+		let missing = server
+			.mock("GET", "/missing.tar.gz")
+			.with_status(404)
+			.expect(1)
+			.create_async()
+			.await;
+		let url = format!("{}/missing.tar.gz", server.url());
+		assert!(matches!(
+			stream_with_retries(&url, &mut tempfile()?, 1, &Output).await,
+			Err(Error::HttpError(e)) if e.status() == Some(StatusCode::NOT_FOUND)
+		));
+		missing.assert_async().await;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn from_url_verifies_checksum() -> anyhow::Result<()> {
+		let mut server = mockito::Server::new_async().await;
+		server.mock("GET", "/polkadot").with_body("polkadot").create_async().await;
+		let url = format!("{}/polkadot", server.url());
+		let temp_dir = tempdir()?;
+		let path = temp_dir.path().join("polkadot");
+
+		let checksum = Checksum::Sha256(format!("{:x}", Sha256::digest(b"invalid")));
+		assert!(matches!(
+			from_url(&url, &path, Some(&checksum), &Output).await,
+			Err(Error::ChecksumMismatch { url: u, .. }) if u == url
+		));
+		assert!(!path.exists());
+
+		let checksum = Checksum::Sha256(format!("{:X}", Sha256::digest(b"polkadot")));
+		from_url(&url, &path, Some(&checksum), &Output).await?;
+		assert!(path.exists());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn checksum_resolves_from_sha256_file() -> anyhow::Result<()> {
+		let mut server = mockito::Server::new_async().await;
+		let digest = format!("{:x}", Sha256::digest(b"polkadot"));
+		let mock = server
+			.mock("GET", "/download/v1.0/polkadot.tar.gz.sha256")
+			.with_body(format!("{digest}  polkadot.tar.gz\n"))
+			.create_async()
+			.await;
+		let url = format!("{}/download/v1.0/polkadot.tar.gz", server.url());
+
+		assert_eq!(Checksum::Sha256File.resolve(&url).await?, Some(digest));
+		mock.assert_async().await;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn from_url_fails_when_checksum_unpublished() -> anyhow::Result<()> {
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("GET", "/download/v1.0/polkadot")
+			.with_body("polkadot")
+			.create_async()
+			.await;
+		let mock = server
+			.mock("GET", "/download/v1.0/polkadot.sha256")
+			.with_status(404)
+			.expect(2)
+			.create_async()
+			.await;
+		let url = format!("{}/download/v1.0/polkadot", server.url());
+		let temp_dir = tempdir()?;
+		let path = temp_dir.path().join("polkadot");
+		assert_eq!(Checksum::Sha256File.resolve(&url).await?, None);
+
+		let status = Warnings::default();
+		assert!(matches!(
+			from_url(&url, &path, Some(&Checksum::Sha256File), &status).await,
+			Err(Error::ChecksumError(e)) if e.starts_with(&format!("no checksum is published for {url}"))
+		));
+		assert!(!path.exists());
+		assert!(status.0.lock().unwrap().is_empty());
+		mock.assert_async().await;
+		mock.remove_async().await;
+
+		// Any other failure to fetch the published checksum remains an error
+		server
+			.mock("GET", "/download/v1.0/polkadot.sha256")
+			.with_status(500)
+			.create_async()
+			.await;
+		assert!(Checksum::Sha256File.resolve(&url).await.is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn checksum_resolves_from_sha256_sums() -> anyhow::Result<()> {
+		let mut server = mockito::Server::new_async().await;
+		let digest = format!("{:x}", Sha256::digest(b"polkadot"));
+		let other = format!("{:x}", Sha256::digest(b"pop-node"));
+		server
+			.mock("GET", "/download/v1.0/SHA256SUMS")
+			.with_body(format!("{other}  pop-node.tar.gz\n{digest} *polkadot.tar.gz\n"))
+			.create_async()
+			.await;
+		let checksum = Checksum::Sha256Sums("SHA256SUMS".into());
+
+		let url = format!("{}/download/v1.0/polkadot.tar.gz", server.url());
+		assert_eq!(checksum.resolve(&url).await?, Some(digest));
+		let url = format!("{}/download/v1.0/missing.tar.gz", server.url());
+		assert!(matches!(checksum.resolve(&url).await, Err(Error::ChecksumError(..))));
+		Ok(())
+	}
+
 	#[tokio::test]
 	async fn from_git_works() -> anyhow::Result<()> {
 		let url = "https://github.com/hpaluch/rust-hello-world";
@@ -814,22 +1427,176 @@ pub(super) mod tests {
 		let temp_dir = tempdir()?;
 		let path = temp_dir.path().join("polkadot");
 
-		from_url(url, &path, &Output).await?;
+		from_url(url, &path, None, &Output).await?;
 		assert!(path.exists());
 		assert_ne!(metadata(path)?.permissions().mode() & 0o755, 0);
 		Ok(())
 	}
 
+	#[test]
+	fn release_archive_url_works() {
+		let release = |tag: Option<&str>| ReleaseArchive {
+			owner: "r0gue-io".into(),
+			repository: "pop-node".into(),
+			tag: tag.map(|t| t.into()),
+			tag_format: Some("node-{tag}".into()),
+			archive: "pop-node.tar.gz".into(),
+			contents: vec![],
+			checksum: None,
+			latest: None,
+		};
+		assert_eq!(
+			release(Some("v1.0")).url_from(None),
+			"https://github.com/r0gue-io/pop-node/releases/download/node-v1.0/pop-node.tar.gz"
+		);
+		assert_eq!(
+			release(None).url_from(None),
+			"https://github.com/r0gue-io/pop-node/releases/latest/download/pop-node.tar.gz"
+		);
+		assert_eq!(
+			release(Some("v1.0")).url_from(Some(MIRROR)),
+			"https://mirror.example.com/r0gue-io/pop-node/releases/download/node-v1.0/pop-node.tar.gz"
+		);
+		assert_eq!(
+			release(None).url_from(Some(MIRROR)),
+			"https://mirror.example.com/r0gue-io/pop-node/releases/latest/download/pop-node.tar.gz"
+		);
+	}
+
+	#[test]
+	fn source_code_archive_url_works() {
+		let source = SourceCodeArchive {
+			owner: "paritytech".into(),
+			repository: "polkadot-sdk".into(),
+			reference: None,
+			manifest: None,
+			package: "polkadot".into(),
+			artifacts: vec![],
+		};
+		assert_eq!(source.url_from(None), "https://github.com/paritytech/polkadot-sdk");
+		assert_eq!(
+			source.url_from(Some(MIRROR)),
+			"https://mirror.example.com/paritytech/polkadot-sdk"
+		);
+	}
+
+	#[test]
+	fn source_code_archive_urls_works() {
+		assert_eq!(
+			source_code_archive_urls("paritytech", "polkadot-sdk", Some("stable2409"), None),
+			[
+				"https://github.com/paritytech/polkadot-sdk/archive/refs/heads/stable2409.tar.gz",
+				"https://github.com/paritytech/polkadot-sdk/archive/refs/tags/stable2409.tar.gz",
+				"https://github.com/paritytech/polkadot-sdk/archive/stable2409.tar.gz",
+			]
+		);
+		assert_eq!(
+			source_code_archive_urls("paritytech", "polkadot-sdk", Some("stable2409"), Some(MIRROR)),
+			[
+				"https://mirror.example.com/paritytech/polkadot-sdk/archive/refs/heads/stable2409.tar.gz",
+				"https://mirror.example.com/paritytech/polkadot-sdk/archive/refs/tags/stable2409.tar.gz",
+				"https://mirror.example.com/paritytech/polkadot-sdk/archive/stable2409.tar.gz",
+			]
+		);
+		assert_eq!(
+			source_code_archive_urls("paritytech", "polkadot-sdk", None, None),
+			["https://api.github.com/repos/paritytech/polkadot-sdk/tarball"]
+		);
+		assert_eq!(
+			source_code_archive_urls("paritytech", "polkadot-sdk", None, Some(MIRROR)),
+			["https://mirror.example.com/api/repos/paritytech/polkadot-sdk/tarball"]
+		);
+	}
+
+	#[test]
+	fn enabled_works() {
+		assert!(!enabled(None));
+		for value in ["", "0", "false", "FALSE"] {
+			assert!(!enabled(Some(value)), "{value}");
+		}
+		for value in ["1", "true", "TRUE", "yes"] {
+			assert!(enabled(Some(value)), "{value}");
+		}
+	}
+
+	#[test]
+	fn unverified_works() {
+		let url = "https://github.com/r0gue-io/pop-node/releases/download/v1.0/pop-node";
+		let status = Warnings::default();
+		assert!(matches!(
+			unverified(url, false, &status),
+			Err(Error::ChecksumError(e)) if e.contains("POP_ALLOW_UNVERIFIED=1")
+		));
+		assert!(status.0.lock().unwrap().is_empty());
+
+		unverified(url, true, &status).unwrap();
+		assert_eq!(
+			*status.0.lock().unwrap(),
+			[format!(
+				"⚠️ No checksum is published for {url}, so its integrity could not be verified."
+			)]
+		);
+	}
+
+	#[test]
+	fn set_offline_works() {
+		if !isolated("sourcing::tests::set_offline_works") {
+			return;
+		}
+		assert!(!offline());
+		{
+			let _offline = Offline::enable();
+			assert!(offline());
+		}
+		assert!(!offline());
+	}
+
+	#[test]
+	fn build_jobs_works() {
+		assert_eq!(build_jobs(None), 1);
+		assert_eq!(build_jobs(Some("4")), 4);
+		assert_eq!(build_jobs(Some(" 2 ")), 2);
+		assert_eq!(build_jobs(Some("0")), 1);
+		assert_eq!(build_jobs(Some("all")), 1);
+	}
+
 	pub(crate) struct Output;
 	impl Status for Output {
 		fn update(&self, status: &str) {
 			println!("{status}")
 		}
 	}
+
+	/// Records any warnings.
+	#[derive(Default)]
+	pub(crate) struct Warnings(pub(crate) std::sync::Mutex<Vec<String>>);
+
+	impl Status for Warnings {
+		fn update(&self, _: &str) {}
+
+		fn warning(&self, warning: &str) {
+			self.0.lock().unwrap().push(warning.to_string())
+		}
+	}
+
+	/// Records any progress updates.
+	#[derive(Default)]
+	struct Progress(std::sync::Mutex<Vec<(u64, Option<u64>)>>);
+
+	impl Status for Progress {
+		fn update(&self, _: &str) {}
+
+		fn progress(&self, downloaded: u64, total: Option<u64>) {
+			self.0.lock().unwrap().push((downloaded, total))
+		}
+	}
 }
 
 pub mod traits {
-	use crate::{sourcing::Error, GitHub};
+	use crate::{
+		sourcing::{offline, Checksum, Error},
+		GitHub,
+	};
 	use strum::EnumProperty;
 
 	/// The source of a binary.
@@ -839,6 +1606,15 @@ pub mod traits {
 			self.get_str("Binary").expect("expected specification of `Binary` name")
 		}
 
+		/// If applicable, how the checksum of a release artifact is published - i.e. `sha256` for a
+		/// `{artifact}.sha256` file, otherwise the name of a checksums file such as `SHA256SUMS`.
+		fn checksum(&self) -> Option<Checksum> {
+			self.get_str("Checksum").map(|c| match c {
+				"sha256" => Checksum::Sha256File,
+				file => Checksum::Sha256Sums(file.to_string()),
+			})
+		}
+
 		/// The fallback version to be used when the latest version cannot be determined.
 		fn fallback(&self) -> &str {
 			self.get_str("Fallback")
@@ -851,32 +1627,15 @@ pub mod traits {
 				.map(|v| v.parse().expect("expected parachain prerelease value to be true/false"))
 		}
 
-		/// Determine the available releases from the source.
+		/// Determine the available releases from the source, where none are available when offline.
+		/// The fallback version is used when GitHub cannot be reached, but not when the GitHub API
+		/// rate limit has been exceeded.
 		#[allow(async_fn_in_trait)]
 		async fn releases(&self) -> Result<Vec<String>, Error> {
-			let repo = GitHub::parse(self.repository())?;
-			let releases = match repo.releases().await {
-				Ok(releases) => releases,
-				Err(_) => return Ok(vec![self.fallback().to_string()]),
-			};
-			let prerelease = self.prerelease();
-			let tag_format = self.tag_format();
-			Ok(releases
-				.iter()
-				.filter(|r| match prerelease {
-					None => !r.prerelease, // Exclude pre-releases by default
-					Some(prerelease) => r.prerelease == prerelease,
-				})
-				.map(|r| {
-					if let Some(tag_format) = tag_format {
-						// simple for now, could be regex in future
-						let tag_format = tag_format.replace("{tag}", "");
-						r.tag_name.replace(&tag_format, "")
-					} else {
-						r.tag_name.clone()
-					}
-				})
-				.collect())
+			if offline() {
+				return Ok(vec![]);
+			}
+			releases(self, &GitHub::parse(self.repository())?).await
 		}
 
 		/// The repository to be used.
@@ -890,6 +1649,41 @@ pub mod traits {
 		}
 	}
 
+	/// Determine the available releases of a source from its repository.
+	///
+	/// # Arguments
+	/// * `source` - The source of a binary.
+	/// * `repo` - The repository of the source.
+	async fn releases<S: Source + ?Sized>(source: &S, repo: &GitHub) -> Result<Vec<String>, Error> {
+		let releases = match repo.releases().await {
+			Ok(releases) => releases,
+			Err(e) => {
+				if let Some(crate::Error::RateLimited { .. }) = e.downcast_ref() {
+					return Err(e.into());
+				}
+				return Ok(vec![source.fallback().to_string()]);
+			},
+		};
+		let prerelease = source.prerelease();
+		let tag_format = source.tag_format();
+		Ok(releases
+			.iter()
+			.filter(|r| match prerelease {
+				None => !r.prerelease, // Exclude pre-releases by default
+				Some(prerelease) => r.prerelease == prerelease,
+			})
+			.map(|r| {
+				if let Some(tag_format) = tag_format {
+					// simple for now, could be regex in future
+					let tag_format = tag_format.replace("{tag}", "");
+					r.tag_name.replace(&tag_format, "")
+				} else {
+					r.tag_name.clone()
+				}
+			})
+			.collect())
+	}
+
 	/// An attempted conversion into a Source.
 	pub trait TryInto {
 		/// Attempt the conversion.
@@ -906,7 +1700,8 @@ pub mod traits {
 
 	#[cfg(test)]
 	mod tests {
-		use super::Source;
+		use super::{releases, Checksum, GitHub, Source};
+		use crate::test_utils::{isolated, Offline};
 		use strum_macros::{EnumProperty, VariantArray};
 
 		#[derive(EnumProperty, VariantArray)]
@@ -916,9 +1711,16 @@ pub mod traits {
 				Binary = "polkadot",
 				Prerelease = "false",
 				Fallback = "v1.12.0",
-				TagFormat = "polkadot-{tag}"
+				TagFormat = "polkadot-{tag}",
+				Checksum = "sha256"
 			))]
 			Polkadot,
+			#[strum(props(
+				Repository = "https://github.com/r0gue-io/sums",
+				Fallback = "v1.0",
+				Checksum = "SHA256SUMS"
+			))]
+			Sums,
 			#[strum(props(
 				Repository = "https://github.com/r0gue-io/fallback",
 				Fallback = "v1.0"
@@ -933,6 +1735,13 @@ pub mod traits {
 			assert_eq!("polkadot", Chain::Polkadot.binary())
 		}
 
+		#[test]
+		fn checksum_works() {
+			assert_eq!(Chain::Polkadot.checksum(), Some(Checksum::Sha256File));
+			assert_eq!(Chain::Sums.checksum(), Some(Checksum::Sha256Sums("SHA256SUMS".into())));
+			assert_eq!(Chain::Fallback.checksum(), None);
+		}
+
 		#[test]
 		fn fallback_works() {
 			assert_eq!("v1.12.0", Chain::Polkadot.fallback())
@@ -956,6 +1765,40 @@ pub mod traits {
 			Ok(())
 		}
 
+		#[tokio::test]
+		async fn releases_fails_when_rate_limited() -> anyhow::Result<()> {
+			let mut server = mockito::Server::new_async().await;
+			let repo = GitHub::parse(Chain::Fallback.repository())?.with_api(server.url());
+			let mock = server
+				.mock("GET", "/repos/r0gue-io/fallback/releases")
+				.with_status(403)
+				.with_header("x-ratelimit-limit", "60")
+				.with_header("x-ratelimit-remaining", "0")
+				.with_header("x-ratelimit-reset", "0")
+				.create_async()
+				.await;
+			let error = releases(&Chain::Fallback, &repo).await.unwrap_err();
+			assert!(error.to_string().contains("GITHUB_TOKEN"));
+			mock.assert_async().await;
+
+			// The fallback is still used when the repository is otherwise unreachable
+			let repo = GitHub::parse(Chain::Fallback.repository())?.with_api("http://127.0.0.1:1");
+			assert_eq!(releases(&Chain::Fallback, &repo).await?, ["v1.0"]);
+			Ok(())
+		}
+
+		#[tokio::test]
+		async fn releases_are_empty_when_offline() -> anyhow::Result<()> {
+			if !isolated("sourcing::traits::tests::releases_are_empty_when_offline") {
+				return Ok(());
+			}
+			let _offline = Offline::enable();
+			// No request is made to GitHub, so the fallback is not used either
+			assert!(Chain::Polkadot.releases().await?.is_empty());
+			assert!(Chain::Fallback.releases().await?.is_empty());
+			Ok(())
+		}
+
 		#[test]
 		fn repository_works() {
 			assert_eq!("https://github.com/paritytech/polkadot-sdk", Chain::Polkadot.repository())
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: GPL-3.0
+
+//! Utilities for testing functionality which depends on global state.
+
+use crate::sourcing::{set_offline, POP_OFFLINE};
+use std::{env, process::Command};
+
+/// Runs a test within a separate process, so that enabling offline mode cannot leak into any other
+/// tests running concurrently. Returns whether the caller is running within that process and
+/// should therefore proceed with the test.
+///
+/// # Arguments
+/// * `test` - The path of the test within the crate being tested.
+pub fn isolated(test: &str) -> bool {
+	const ISOLATED: &str = "POP_TEST_ISOLATED";
+	if env::var_os(ISOLATED).is_some() {
+		return true;
+	}
+	let output = Command::new(env::current_exe().expect("expected path of the test binary"))
+		.args([test, "--exact"])
+		.env(ISOLATED, "1")
+		.env_remove(POP_OFFLINE)
+		.output()
+		.expect("expected test to be run in a separate process");
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(
+		output.status.success() && stdout.contains("1 passed"),
+		"{stdout}{}",
+		String::from_utf8_lossy(&output.stderr)
+	);
+	false
+}
+
+/// Enables offline mode until dropped.
+pub struct Offline;
+
+impl Offline {
+	/// Enables offline mode.
+	pub fn enable() -> Self {
+		set_offline(true);
+		Self
+	}
+}
+
+impl Drop for Offline {
+	fn drop(&mut self) {
+		set_offline(false)
+	}
+}
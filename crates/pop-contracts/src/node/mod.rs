@@ -76,6 +76,7 @@ impl TryInto for Chain {
 					tag_format: self.tag_format().map(|t| t.into()),
 					archive,
 					contents: vec![(archive_bin_path, Some(self.binary().to_string()))],
+					checksum: self.checksum(),
 					latest,
 				})
 			},
@@ -195,6 +196,7 @@ mod tests {
 					tag_format: expected.tag_format().map(|t| t.into()),
 					archive: archive,
 					contents: vec![(archive_bin_path, Some(binary.name().to_string()))],
+					checksum: None,
 					latest: None,
 				})
 				&&
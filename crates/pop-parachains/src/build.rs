@@ -1,11 +1,16 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::Error;
+use crate::{
+	genesis::{GenesisBuilder, GenesisStorage, StorageEntries, CODE_KEY},
+	Error,
+};
 use anyhow::Result;
 use duct::cmd;
 use pop_common::{manifest::from_path, Profile};
-use serde_json::{json, Value};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 use std::{
+	collections::BTreeMap,
 	fs,
 	path::{Path, PathBuf},
 	str::FromStr,
@@ -185,6 +190,98 @@ pub fn generate_genesis_state_file(
 	Ok(genesis_file)
 }
 
+/// Generates the plain text chain specification for a parachain from its runtime, without
+/// requiring a node binary. The genesis configuration is provided by the `GenesisBuilder` runtime
+/// API.
+///
+/// # Arguments
+/// * `runtime` - The path to the runtime (WASM).
+/// * `plain_chain_spec` - Location of the plain_parachain_spec file to be generated.
+/// * `preset` - The genesis preset of the runtime to be used, defaulting to the default genesis
+///   configuration of the runtime if not specified.
+pub fn generate_plain_chain_spec_from_runtime(
+	runtime: &Path,
+	plain_chain_spec: &Path,
+	preset: Option<&str>,
+) -> Result<(), Error> {
+	let mut builder = GenesisBuilder::new(fs::read(runtime)?)?;
+	let patch = builder.preset(preset)?;
+	let id = preset.unwrap_or("dev");
+	let chain_spec = ChainSpec::plain(&builder.spec_name(), id, builder.code(), patch);
+	fs::write(plain_chain_spec, chain_spec.to_string()?)?;
+	Ok(())
+}
+
+/// Generates a raw chain specification file for a parachain from a plain chain specification
+/// containing its runtime, without requiring a node binary. The genesis state is built by the
+/// `GenesisBuilder` runtime API.
+///
+/// # Arguments
+/// * `plain_chain_spec` - Location of the plain chain specification file.
+/// * `chain_spec_file_name` - The name of the chain specification file to be generated.
+pub fn generate_raw_chain_spec_from_runtime(
+	plain_chain_spec: &Path,
+	chain_spec_file_name: &str,
+) -> Result<PathBuf, Error> {
+	if !plain_chain_spec.exists() {
+		return Err(Error::MissingChainSpec(plain_chain_spec.display().to_string()));
+	}
+	let mut chain_spec = ChainSpec::from(plain_chain_spec)?;
+	let (code, patch) = chain_spec.runtime_genesis()?;
+	let storage = GenesisBuilder::new(code)?.build_state(&patch)?;
+	chain_spec.replace_genesis_with_raw(&storage);
+	let raw_chain_spec = plain_chain_spec.with_file_name(chain_spec_file_name);
+	fs::write(&raw_chain_spec, chain_spec.to_string()?)?;
+	Ok(raw_chain_spec)
+}
+
+/// Export the WebAssembly runtime for the parachain from its raw chain specification, without
+/// requiring a node binary.
+///
+/// # Arguments
+/// * `chain_spec` - Location of the raw chain specification file.
+/// * `wasm_file_name` - The name of the wasm runtime file to be generated.
+pub fn export_wasm_file_from_chain_spec(
+	chain_spec: &Path,
+	wasm_file_name: &str,
+) -> Result<PathBuf, Error> {
+	if !chain_spec.exists() {
+		return Err(Error::MissingChainSpec(chain_spec.display().to_string()));
+	}
+	let storage = ChainSpec::from(chain_spec)?.raw_storage()?;
+	let code = storage
+		.top
+		.get(CODE_KEY)
+		.ok_or_else(|| Error::Config("expected `:code` within the genesis storage".into()))?;
+	let wasm_file = chain_spec.parent().unwrap_or(Path::new("./")).join(wasm_file_name);
+	fs::write(&wasm_file, format!("0x{}", hex::encode(code)))?;
+	Ok(wasm_file)
+}
+
+/// Generate the parachain genesis state from its raw chain specification, without requiring a
+/// node binary.
+///
+/// # Arguments
+/// * `chain_spec` - Location of the raw chain specification file.
+/// * `genesis_file_name` - The name of the genesis state file to be generated.
+pub fn generate_genesis_state_file_from_chain_spec(
+	chain_spec: &Path,
+	genesis_file_name: &str,
+) -> Result<PathBuf, Error> {
+	if !chain_spec.exists() {
+		return Err(Error::MissingChainSpec(chain_spec.display().to_string()));
+	}
+	let storage = ChainSpec::from(chain_spec)?.raw_storage()?;
+	let code = storage
+		.top
+		.get(CODE_KEY)
+		.ok_or_else(|| Error::Config("expected `:code` within the genesis storage".into()))?;
+	let head = GenesisBuilder::new(code.clone())?.genesis_head(&storage);
+	let genesis_file = chain_spec.parent().unwrap_or(Path::new("./")).join(genesis_file_name);
+	fs::write(&genesis_file, format!("0x{}", hex::encode(head)))?;
+	Ok(genesis_file)
+}
+
 /// Checks if a given command exists and can be executed by running it with the "--help" argument.
 fn check_command_exists(binary_path: &Path, command: &str) -> Result<(), Error> {
 	cmd(binary_path, vec![command, "--help"]).stdout_null().run().map_err(|_err| {
@@ -196,8 +293,29 @@ fn check_command_exists(binary_path: &Path, command: &str) -> Result<(), Error>
 	Ok(())
 }
 
+/// A collator of a parachain, as configured at genesis.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Collator {
+	/// The account of the collator.
+	pub account: String,
+	/// The session keys of the collator, keyed by name (e.g. `aura`).
+	pub session_keys: Map<String, Value>,
+}
+
+impl Collator {
+	/// Parses a list of collators from a JSON file, such as
+	/// `[{"account": "5Grw...", "sessionKeys": {"aura": "5Grw..."}}]`.
+	///
+	/// # Arguments
+	/// * `path` - The path to the file.
+	pub fn from_file(path: &Path) -> Result<Vec<Collator>, Error> {
+		Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+	}
+}
+
 /// A chain specification.
-pub struct ChainSpec(Value);
+pub struct ChainSpec(pub(crate) Value);
 impl ChainSpec {
 	/// Parses a chain specification from a path.
 	///
@@ -207,6 +325,71 @@ impl ChainSpec {
 		Ok(ChainSpec(Value::from_str(&std::fs::read_to_string(path)?)?))
 	}
 
+	/// Creates a raw chain specification for a parachain from its genesis storage.
+	///
+	/// # Arguments
+	/// * `name` - The name of the chain.
+	/// * `id` - The identifier of the chain.
+	/// * `para_id` - The parachain identifier.
+	/// * `properties` - The properties of the chain, such as its token symbol and decimals.
+	/// * `storage` - The genesis storage, as hex-encoded keys and values.
+	pub fn raw(
+		name: &str,
+		id: &str,
+		para_id: u32,
+		properties: Value,
+		storage: BTreeMap<String, String>,
+	) -> ChainSpec {
+		ChainSpec(json!({
+			"name": name,
+			"id": id,
+			"chainType": "Local",
+			"bootNodes": [],
+			"telemetryEndpoints": null,
+			"protocolId": id,
+			"properties": properties,
+			"relay_chain": "",
+			"para_id": para_id,
+			"codeSubstitutes": {},
+			"genesis": {
+				"raw": {
+					"top": storage,
+					"childrenDefault": {}
+				}
+			}
+		}))
+	}
+
+	/// Creates a plain chain specification for a parachain from its runtime and a genesis
+	/// configuration patch.
+	///
+	/// # Arguments
+	/// * `name` - The name of the chain.
+	/// * `id` - The identifier of the chain.
+	/// * `code` - The runtime (WASM).
+	/// * `patch` - The patch applied to the default genesis configuration of the runtime.
+	pub fn plain(name: &str, id: &str, code: &[u8], patch: Value) -> ChainSpec {
+		let para_id = patch.pointer("/parachainInfo/parachainId").cloned().unwrap_or_default();
+		ChainSpec(json!({
+			"name": name,
+			"id": id,
+			"chainType": "Development",
+			"bootNodes": [],
+			"telemetryEndpoints": null,
+			"protocolId": null,
+			"properties": {},
+			"relay_chain": "",
+			"para_id": para_id,
+			"codeSubstitutes": {},
+			"genesis": {
+				"runtimeGenesis": {
+					"code": format!("0x{}", hex::encode(code)),
+					"patch": patch
+				}
+			}
+		}))
+	}
+
 	/// Get the chain type from the chain specification.
 	pub fn get_chain_type(&self) -> Option<&str> {
 		self.0.get("chainType").and_then(|v| v.as_str())
@@ -298,6 +481,255 @@ impl ChainSpec {
 		Ok(())
 	}
 
+	/// Replaces the token symbol within the properties of the chain.
+	///
+	/// # Arguments
+	/// * `symbol` - The new token symbol.
+	pub fn replace_token_symbol(&mut self, symbol: &str) -> Result<(), Error> {
+		self.properties_mut()?.insert("tokenSymbol".into(), json!(symbol));
+		Ok(())
+	}
+
+	/// Replaces the token decimals within the properties of the chain.
+	///
+	/// # Arguments
+	/// * `decimals` - The new number of token decimals.
+	pub fn replace_token_decimals(&mut self, decimals: u8) -> Result<(), Error> {
+		self.properties_mut()?.insert("tokenDecimals".into(), json!(decimals));
+		Ok(())
+	}
+
+	/// Replaces the balance of an account at genesis, endowing the account if it has no balance.
+	///
+	/// # Arguments
+	/// * `account` - The account.
+	/// * `amount` - The new balance of the account.
+	pub fn replace_balance(&mut self, account: &str, amount: u128) -> Result<(), Error> {
+		let balances = Self::entry(self.genesis_patch_mut()?, "balances")?;
+		let balances = balances
+			.entry("balances")
+			.or_insert_with(|| json!([]))
+			.as_array_mut()
+			.ok_or_else(|| Error::Config("expected `balances.balances` to be an array".into()))?;
+		let balance = json!([account, amount]);
+		match balances.iter_mut().find(|b| b.get(0).and_then(|a| a.as_str()) == Some(account)) {
+			Some(existing) => *existing = balance,
+			None => balances.push(balance),
+		}
+		Ok(())
+	}
+
+	/// Replaces the sudo key at genesis.
+	///
+	/// # Arguments
+	/// * `account` - The account of the new sudo key.
+	pub fn replace_sudo_key(&mut self, account: &str) -> Result<(), Error> {
+		Self::entry(self.genesis_patch_mut()?, "sudo")?.insert("key".into(), json!(account));
+		Ok(())
+	}
+
+	/// Replaces the collators at genesis: both the invulnerable collators of the collator
+	/// selection and their session keys.
+	///
+	/// # Arguments
+	/// * `collators` - The new collators.
+	pub fn replace_collators(&mut self, collators: &[Collator]) -> Result<(), Error> {
+		let patch = self.genesis_patch_mut()?;
+		let invulnerables: Vec<_> = collators.iter().map(|c| &c.account).collect();
+		Self::entry(patch, "collatorSelection")?
+			.insert("invulnerables".into(), json!(invulnerables));
+		let keys: Vec<_> = collators
+			.iter()
+			.map(|c| json!([c.account, c.account, c.session_keys]))
+			.collect();
+		Self::entry(patch, "session")?.insert("keys".into(), json!(keys));
+		Ok(())
+	}
+
+	/// Sets the value at the specified JSON pointer (e.g. `/properties/tokenSymbol`), creating
+	/// any missing objects along the way.
+	///
+	/// # Arguments
+	/// * `pointer` - The JSON pointer, relative to the root of the chain specification.
+	/// * `value` - The new value.
+	pub fn set(&mut self, pointer: &str, value: Value) -> Result<(), Error> {
+		let invalid = || Error::Config(format!("invalid JSON pointer `{pointer}`"));
+		let path = pointer.strip_prefix('/').ok_or_else(invalid)?;
+		let mut target = &mut self.0;
+		for token in path.split('/') {
+			let token = token.replace("~1", "/").replace("~0", "~");
+			target = match target {
+				Value::Object(map) => map.entry(token).or_insert(Value::Null),
+				Value::Array(array) => {
+					let index: usize = token.parse().map_err(|_| invalid())?;
+					array.get_mut(index).ok_or_else(invalid)?
+				},
+				Value::Null => {
+					*target = json!({});
+					target
+						.as_object_mut()
+						.expect("object created above")
+						.entry(token)
+						.or_insert(Value::Null)
+				},
+				_ => return Err(invalid()),
+			};
+		}
+		*target = value;
+		Ok(())
+	}
+
+	/// Returns the genesis configuration patch of a plain chain specification.
+	fn genesis_patch_mut(&mut self) -> Result<&mut Map<String, Value>, Error> {
+		let genesis = self
+			.0
+			.pointer_mut("/genesis/runtimeGenesis")
+			.and_then(|g| g.as_object_mut())
+			.ok_or_else(|| Error::Config("expected `genesis.runtimeGenesis`".into()))?;
+		let key = if genesis.contains_key("config") { "config" } else { "patch" };
+		genesis
+			.entry(key)
+			.or_insert_with(|| json!({}))
+			.as_object_mut()
+			.ok_or_else(|| Error::Config(format!("expected `genesis.runtimeGenesis.{key}`")))
+	}
+
+	/// Returns the properties of the chain, creating them if missing.
+	fn properties_mut(&mut self) -> Result<&mut Map<String, Value>, Error> {
+		let properties = self
+			.0
+			.as_object_mut()
+			.ok_or_else(|| Error::Config("expected chain specification to be an object".into()))?
+			.entry("properties")
+			.or_insert_with(|| json!({}));
+		if properties.is_null() {
+			*properties = json!({});
+		}
+		properties
+			.as_object_mut()
+			.ok_or_else(|| Error::Config("expected `properties`".into()))
+	}
+
+	/// Returns the configuration of a pallet within a genesis configuration patch, creating it
+	/// if missing.
+	///
+	/// # Arguments
+	/// * `patch` - The genesis configuration patch.
+	/// * `pallet` - The name of the pallet configuration.
+	fn entry<'a>(
+		patch: &'a mut Map<String, Value>,
+		pallet: &str,
+	) -> Result<&'a mut Map<String, Value>, Error> {
+		patch
+			.entry(pallet)
+			.or_insert_with(|| json!({}))
+			.as_object_mut()
+			.ok_or_else(|| Error::Config(format!("expected `{pallet}` to be an object")))
+	}
+
+	/// Replaces a value within the raw genesis storage, removing it if `None`.
+	///
+	/// # Arguments
+	/// * `key` - The storage key.
+	/// * `value` - The new storage value.
+	pub fn replace_raw_storage(&mut self, key: &[u8], value: Option<&[u8]>) -> Result<(), Error> {
+		let storage = self.raw_storage_mut()?;
+		let key = format!("0x{}", hex::encode(key));
+		match value {
+			Some(value) => storage.insert(key, json!(format!("0x{}", hex::encode(value)))),
+			None => storage.remove(&key),
+		};
+		Ok(())
+	}
+
+	/// Removes all values from the raw genesis storage whose keys start with `prefix`.
+	///
+	/// # Arguments
+	/// * `prefix` - The storage key prefix.
+	pub fn remove_raw_storage_prefix(&mut self, prefix: &[u8]) -> Result<(), Error> {
+		let prefix = format!("0x{}", hex::encode(prefix));
+		self.raw_storage_mut()?.retain(|key, _| !key.starts_with(&prefix));
+		Ok(())
+	}
+
+	/// Returns the runtime and genesis configuration patch of a plain chain specification.
+	pub(crate) fn runtime_genesis(&self) -> Result<(Vec<u8>, Value), Error> {
+		let genesis = self
+			.0
+			.pointer("/genesis/runtimeGenesis")
+			.ok_or_else(|| Error::Config("expected `genesis.runtimeGenesis`".into()))?;
+		let code = genesis
+			.get("code")
+			.and_then(|c| c.as_str())
+			.and_then(|c| hex::decode(c.trim_start_matches("0x")).ok())
+			.ok_or_else(|| Error::Config("expected `genesis.runtimeGenesis.code`".into()))?;
+		let patch = genesis.get("patch").or_else(|| genesis.get("config")).cloned();
+		Ok((code, patch.unwrap_or_else(|| json!({}))))
+	}
+
+	/// Replaces the genesis of the chain specification with the specified raw genesis storage.
+	///
+	/// # Arguments
+	/// * `storage` - The genesis storage.
+	fn replace_genesis_with_raw(&mut self, storage: &GenesisStorage) {
+		let encode = |entries: &StorageEntries| -> Map<String, Value> {
+			entries
+				.iter()
+				.map(|(k, v)| {
+					(format!("0x{}", hex::encode(k)), json!(format!("0x{}", hex::encode(v))))
+				})
+				.collect()
+		};
+		let children: Map<String, Value> = storage
+			.children_default
+			.iter()
+			.map(|(child, entries)| (format!("0x{}", hex::encode(child)), json!(encode(entries))))
+			.collect();
+		self.0["genesis"] = json!({
+			"raw": {
+				"top": encode(&storage.top),
+				"childrenDefault": children
+			}
+		});
+	}
+
+	/// Returns the raw genesis storage.
+	pub(crate) fn raw_storage(&self) -> Result<GenesisStorage, Error> {
+		let decode = |entries: &Value| -> Result<StorageEntries, Error> {
+			let invalid = || Error::Config("invalid raw genesis storage".into());
+			let hex = |v: &str| hex::decode(v.trim_start_matches("0x")).map_err(|_| invalid());
+			entries
+				.as_object()
+				.ok_or_else(invalid)?
+				.iter()
+				.map(|(k, v)| Ok((hex(k)?, hex(v.as_str().ok_or_else(invalid)?)?)))
+				.collect()
+		};
+		let top = self
+			.0
+			.pointer("/genesis/raw/top")
+			.ok_or_else(|| Error::Config("expected `genesis.raw.top`".into()))?;
+		let mut storage = GenesisStorage { top: decode(top)?, ..Default::default() };
+		if let Some(children) =
+			self.0.pointer("/genesis/raw/childrenDefault").and_then(|c| c.as_object())
+		{
+			for (child, entries) in children {
+				let child = hex::decode(child.trim_start_matches("0x"))
+					.map_err(|_| Error::Config("invalid raw genesis storage".into()))?;
+				storage.children_default.insert(child, decode(entries)?);
+			}
+		}
+		Ok(storage)
+	}
+
+	/// Returns the raw genesis storage.
+	fn raw_storage_mut(&mut self) -> Result<&mut Map<String, Value>, Error> {
+		self.0
+			.pointer_mut("/genesis/raw/top")
+			.and_then(|s| s.as_object_mut())
+			.ok_or_else(|| Error::Config("expected `genesis.raw.top`".into()))
+	}
+
 	/// Converts the chain specification to a string.
 	pub fn to_string(&self) -> Result<String> {
 		Ok(serde_json::to_string_pretty(&self.0)?)
@@ -744,6 +1176,252 @@ default_command = "pop-node"
 		Ok(())
 	}
 
+	#[test]
+	fn replace_token_properties_works() -> Result<()> {
+		let mut chain_spec = ChainSpec(json!({"properties": null}));
+		chain_spec.replace_token_symbol("POP")?;
+		chain_spec.replace_token_decimals(10)?;
+		assert_eq!(
+			chain_spec.0,
+			json!({"properties": {"tokenSymbol": "POP", "tokenDecimals": 10}})
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn replace_balance_works() -> Result<()> {
+		let mut chain_spec = ChainSpec(json!({"genesis": {"runtimeGenesis": {"patch": {
+			"balances": {"balances": [["alice", 1], ["bob", 2]]}
+		}}}}));
+		chain_spec.replace_balance("bob", 3)?;
+		chain_spec.replace_balance("charlie", u128::MAX)?;
+		assert_eq!(
+			chain_spec.0["genesis"]["runtimeGenesis"]["patch"]["balances"]["balances"],
+			json!([["alice", 1], ["bob", 3], ["charlie", u128::MAX]])
+		);
+		// Balances exceeding 64 bits are preserved
+		assert!(chain_spec.to_string()?.contains(&u128::MAX.to_string()));
+		Ok(())
+	}
+
+	#[test]
+	fn replace_sudo_key_works() -> Result<()> {
+		let mut chain_spec = ChainSpec(json!({"genesis": {"runtimeGenesis": {"config": {}}}}));
+		chain_spec.replace_sudo_key("alice")?;
+		assert_eq!(
+			chain_spec.0["genesis"]["runtimeGenesis"]["config"]["sudo"],
+			json!({"key": "alice"})
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn replace_collators_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let path = temp_dir.path().join("collators.json");
+		write(
+			&path,
+			r#"[{"account": "alice", "sessionKeys": {"aura": "alice-aura"}}, {"account": "bob", "sessionKeys": {"aura": "bob-aura"}}]"#,
+		)?;
+		let collators = Collator::from_file(&path)?;
+		let mut chain_spec = ChainSpec(json!({"genesis": {"runtimeGenesis": {"patch": {
+			"collatorSelection": {"candidacyBond": 16, "invulnerables": ["charlie"]},
+			"session": {"keys": [["charlie", "charlie", {"aura": "charlie-aura"}]]}
+		}}}}));
+		chain_spec.replace_collators(&collators)?;
+		assert_eq!(
+			chain_spec.0["genesis"]["runtimeGenesis"]["patch"],
+			json!({
+				"collatorSelection": {"candidacyBond": 16, "invulnerables": ["alice", "bob"]},
+				"session": {"keys": [
+					["alice", "alice", {"aura": "alice-aura"}],
+					["bob", "bob", {"aura": "bob-aura"}]
+				]}
+			})
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn set_works() -> Result<()> {
+		let mut chain_spec = ChainSpec(json!({"properties": null, "bootNodes": ["a", "b"]}));
+		chain_spec.set("/properties/tokenSymbol", json!("POP"))?;
+		chain_spec.set("/bootNodes/1", json!("c"))?;
+		chain_spec.set("/genesis/runtimeGenesis/patch/a~1b", json!({"c": 1}))?;
+		assert_eq!(
+			chain_spec.0,
+			json!({
+				"properties": {"tokenSymbol": "POP"},
+				"bootNodes": ["a", "c"],
+				"genesis": {"runtimeGenesis": {"patch": {"a/b": {"c": 1}}}}
+			})
+		);
+		for pointer in ["properties", "/bootNodes/2", "/bootNodes/x", "/properties/tokenSymbol/x"] {
+			assert!(matches!(
+				chain_spec.set(pointer, json!(1)),
+				Err(Error::Config(error)) if error == format!("invalid JSON pointer `{pointer}`")
+			));
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn genesis_editing_fails_for_raw_chain_spec() -> Result<()> {
+		let mut chain_spec = ChainSpec::raw("Chain", "chain", 2000, json!({}), BTreeMap::new());
+		for result in [
+			chain_spec.replace_balance("alice", 1),
+			chain_spec.replace_sudo_key("alice"),
+			chain_spec.replace_collators(&[]),
+		] {
+			assert!(
+				matches!(result, Err(Error::Config(error)) if error == "expected `genesis.runtimeGenesis`")
+			);
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn raw_works() -> Result<()> {
+		let storage = BTreeMap::from([("0x3a636f6465".to_string(), "0x0102".to_string())]);
+		let chain_spec =
+			ChainSpec::raw("Pop Network", "pop-fork", 4001, json!({"tokenSymbol": "PAS"}), storage);
+		assert_eq!(chain_spec.get_chain_type(), Some("Local"));
+		assert_eq!(chain_spec.get_parachain_id(), Some(4001));
+		assert_eq!(chain_spec.get_protocol_id(), Some("pop-fork"));
+		assert_eq!(chain_spec.0["name"], json!("Pop Network"));
+		assert_eq!(chain_spec.0["properties"], json!({"tokenSymbol": "PAS"}));
+		assert_eq!(chain_spec.0["genesis"]["raw"]["top"], json!({"0x3a636f6465": "0x0102"}));
+		Ok(())
+	}
+
+	#[test]
+	fn plain_works() -> Result<()> {
+		let patch = json!({"parachainInfo": {"parachainId": 1000}});
+		let mut chain_spec = ChainSpec::plain("template", "dev", &[0, 97, 115, 109], patch.clone());
+		assert_eq!(chain_spec.get_chain_type(), Some("Development"));
+		assert_eq!(chain_spec.get_parachain_id(), Some(1000));
+		assert_eq!(chain_spec.0["genesis"]["runtimeGenesis"]["code"], json!("0x0061736d"));
+		assert_eq!(chain_spec.runtime_genesis()?, (vec![0, 97, 115, 109], patch));
+		chain_spec.replace_para_id(2000)?;
+		assert_eq!(
+			chain_spec.0["genesis"]["runtimeGenesis"]["patch"]["parachainInfo"]["parachainId"],
+			json!(2000)
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn replace_genesis_with_raw_works() -> Result<()> {
+		let mut chain_spec = ChainSpec::plain("template", "dev", &[1], json!({}));
+		let storage = GenesisStorage {
+			top: BTreeMap::from([(CODE_KEY.to_vec(), vec![1, 2]), (vec![10], vec![3])]),
+			children_default: BTreeMap::from([(vec![11], BTreeMap::from([(vec![12], vec![4])]))]),
+		};
+		chain_spec.replace_genesis_with_raw(&storage);
+		assert_eq!(
+			chain_spec.0["genesis"],
+			json!({"raw": {
+				"top": {"0x0a": "0x03", "0x3a636f6465": "0x0102"},
+				"childrenDefault": {"0x0b": {"0x0c": "0x04"}}
+			}})
+		);
+		assert_eq!(chain_spec.raw_storage()?, storage);
+		assert!(matches!(
+			chain_spec.runtime_genesis(),
+			Err(Error::Config(error)) if error == "expected `genesis.runtimeGenesis`"
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn export_wasm_file_from_chain_spec_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let path = temp_dir.path().join("raw-parachain-chainspec.json");
+		let storage = BTreeMap::from([("0x3a636f6465".to_string(), "0x0102".to_string())]);
+		ChainSpec::raw("Chain", "chain", 2000, json!({}), storage).to_file(&path)?;
+		let wasm_file = export_wasm_file_from_chain_spec(&path, "para-2000.wasm")?;
+		assert_eq!(wasm_file, temp_dir.path().join("para-2000.wasm"));
+		assert_eq!(fs::read_to_string(wasm_file)?, "0x0102");
+		Ok(())
+	}
+
+	#[test]
+	fn chain_spec_from_runtime_fails_wrong_inputs() -> Result<()> {
+		let temp_dir = tempdir()?;
+		assert!(matches!(
+			generate_plain_chain_spec_from_runtime(
+				Path::new("./runtime.wasm"),
+				&temp_dir.path().join("plain-parachain-chainspec.json"),
+				None
+			),
+			Err(Error::IO(..))
+		));
+		let runtime = temp_dir.path().join("runtime.wasm");
+		write(&runtime, [0, 97, 115, 109])?;
+		assert!(matches!(
+			generate_plain_chain_spec_from_runtime(
+				&runtime,
+				&temp_dir.path().join("plain-parachain-chainspec.json"),
+				None
+			),
+			Err(Error::GenesisBuilderError(..))
+		));
+		assert!(matches!(
+			generate_raw_chain_spec_from_runtime(
+				Path::new("./plain-parachain-chainspec.json"),
+				"raw-parachain-chainspec.json"
+			),
+			Err(Error::MissingChainSpec(..))
+		));
+		assert!(matches!(
+			export_wasm_file_from_chain_spec(
+				Path::new("./raw-parachain-chainspec"),
+				"para-2001-wasm"
+			),
+			Err(Error::MissingChainSpec(..))
+		));
+		assert!(matches!(
+			generate_genesis_state_file_from_chain_spec(
+				Path::new("./raw-parachain-chainspec"),
+				"para-2001-genesis-state"
+			),
+			Err(Error::MissingChainSpec(..))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn replace_raw_storage_works() -> Result<()> {
+		let storage = BTreeMap::from([
+			("0x0a01".to_string(), "0x01".to_string()),
+			("0x0a02".to_string(), "0x02".to_string()),
+			("0x0b01".to_string(), "0x03".to_string()),
+		]);
+		let mut chain_spec = ChainSpec::raw("Chain", "chain", 2000, json!({}), storage);
+		chain_spec.replace_raw_storage(&[0x0b, 0x01], Some(&[0x04]))?;
+		chain_spec.replace_raw_storage(&[0x0c], Some(&[0x05, 0x06]))?;
+		chain_spec.replace_raw_storage(&[0x0a, 0x01], None)?;
+		assert_eq!(
+			chain_spec.0["genesis"]["raw"]["top"],
+			json!({"0x0a02": "0x02", "0x0b01": "0x04", "0x0c": "0x0506"})
+		);
+		chain_spec.remove_raw_storage_prefix(&[0x0a])?;
+		assert_eq!(
+			chain_spec.0["genesis"]["raw"]["top"],
+			json!({"0x0b01": "0x04", "0x0c": "0x0506"})
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn replace_raw_storage_fails() -> Result<()> {
+		let mut chain_spec = ChainSpec(json!({"genesis": {"runtimeGenesis": {}}}));
+		assert!(
+			matches!(chain_spec.replace_raw_storage(&[0x0a], None), Err(Error::Config(error)) if error == "expected `genesis.raw.top`")
+		);
+		Ok(())
+	}
+
 	#[test]
 	fn check_command_exists_fails() -> Result<()> {
 		let binary_path = PathBuf::from("/bin");
@@ -0,0 +1,292 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	genesis::GenesisBuilder,
+	runtime::{compressed_runtime, Runtime},
+};
+use duct::cmd;
+use serde::{Deserialize, Serialize};
+use sp_crypto_hashing::blake2_256;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+use subxt::Metadata;
+
+/// The pinned container image used to build runtimes deterministically.
+pub const SRTOOL_IMAGE: &str = "docker.io/paritytech/srtool:1.81.0";
+/// The supported container engines, in order of preference.
+const CONTAINER_ENGINES: [&str; 2] = ["docker", "podman"];
+
+/// The means by which a runtime is built deterministically.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Builder {
+	/// Within a pinned container image, via a container engine such as `docker` or `podman`.
+	Container {
+		/// The container engine.
+		engine: String,
+		/// The container image.
+		image: String,
+	},
+	/// With the toolchain of the project, as resolved by `rustup` (e.g. via
+	/// `rust-toolchain.toml`). The build is only reproducible using the same toolchain.
+	Toolchain {
+		/// The version of the toolchain, as reported by `rustc --version`.
+		version: String,
+	},
+}
+
+impl Builder {
+	/// Detects how a runtime can be built deterministically, preferring a pinned container image
+	/// when a container engine is available.
+	///
+	/// # Arguments
+	/// * `project` - The path to the project.
+	pub fn detect(project: &Path) -> Result<Builder, Error> {
+		if let Some(engine) = CONTAINER_ENGINES.into_iter().find(|e| available(e)) {
+			return Ok(Builder::Container {
+				engine: engine.to_string(),
+				image: SRTOOL_IMAGE.to_string(),
+			});
+		}
+		let version = cmd("rustc", ["--version"]).dir(project).read()?;
+		Ok(Builder::Toolchain { version: version.trim().to_string() })
+	}
+
+	/// Builds a runtime in release mode, returning the path to the resulting compressed runtime
+	/// (WASM).
+	///
+	/// # Arguments
+	/// * `project` - The path to the project.
+	/// * `runtime` - The runtime.
+	pub fn build(&self, project: &Path, runtime: &Runtime) -> Result<PathBuf, Error> {
+		let package = runtime.package.as_str();
+		let target = match self {
+			Builder::Container { engine, image } => {
+				let project = project.canonicalize()?;
+				let path = runtime.path.canonicalize()?;
+				let runtime_dir = path.strip_prefix(&project).map_err(|_| {
+					Error::Config(format!("the runtime must be within {}", project.display()))
+				})?;
+				cmd(engine, container_args(image, &project, runtime_dir, package))
+					.dir(&project)
+					.run()?;
+				container_target(&project, runtime_dir)
+			},
+			Builder::Toolchain { .. } => {
+				cmd("cargo", ["build", "--release", "--package", package]).dir(project).run()?;
+				project.join("target/release")
+			},
+		};
+		compressed_runtime(&target, package)
+	}
+}
+
+/// A report of a deterministic runtime build, containing the details required to verify the
+/// build and to propose an upgrade to the runtime.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildReport {
+	/// The package of the runtime.
+	pub package: String,
+	/// How the runtime was built.
+	pub builder: Builder,
+	/// The path to the compressed runtime.
+	pub wasm: PathBuf,
+	/// The size of the compressed runtime, in bytes.
+	pub size: usize,
+	/// The BLAKE2-256 hash of the compressed runtime.
+	#[serde(rename = "blake2_256")]
+	pub blake2_256: String,
+	/// The name of the runtime, as per its `spec_name`.
+	pub spec_name: String,
+	/// The version of the runtime, as per its `spec_version`.
+	pub spec_version: u32,
+	/// The state version used by the runtime, as per its `system_version`.
+	pub system_version: u8,
+	/// The `System::authorize_upgrade` call authorizing an upgrade to the runtime.
+	pub authorize_upgrade: AuthorizeUpgrade,
+}
+
+/// A `System::authorize_upgrade` call, as required by a governance proposal to upgrade a runtime.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizeUpgrade {
+	/// The encoded call.
+	pub call: String,
+	/// The BLAKE2-256 hash of the encoded call.
+	pub call_hash: String,
+}
+
+impl BuildReport {
+	/// Creates a report of a runtime build, by inspecting the resulting runtime.
+	///
+	/// # Arguments
+	/// * `package` - The package of the runtime.
+	/// * `builder` - How the runtime was built.
+	/// * `wasm` - The path to the compressed runtime.
+	pub fn new(package: &str, builder: Builder, wasm: PathBuf) -> Result<Self, Error> {
+		let code = fs::read(&wasm)?;
+		let code_hash = blake2_256(&code);
+		let mut runtime = GenesisBuilder::new(code)?;
+		let call = authorize_upgrade(&runtime.metadata()?, code_hash)?;
+		Ok(Self {
+			package: package.to_string(),
+			builder,
+			size: runtime.code().len(),
+			wasm,
+			blake2_256: format!("0x{}", hex::encode(code_hash)),
+			spec_name: runtime.spec_name(),
+			spec_version: runtime.spec_version(),
+			system_version: runtime.system_version(),
+			authorize_upgrade: AuthorizeUpgrade {
+				call: format!("0x{}", hex::encode(&call)),
+				call_hash: format!("0x{}", hex::encode(blake2_256(&call))),
+			},
+		})
+	}
+
+	/// Writes the report to a file, as JSON.
+	///
+	/// # Arguments
+	/// * `path` - The path to the file.
+	pub fn to_file(&self, path: &Path) -> Result<(), Error> {
+		fs::write(path, serde_json::to_string_pretty(self)?)?;
+		Ok(())
+	}
+}
+
+/// Whether a container engine is available, with its daemon (if any) running.
+///
+/// # Arguments
+/// * `engine` - The container engine.
+fn available(engine: &str) -> bool {
+	cmd(engine, ["info"])
+		.stdout_null()
+		.stderr_null()
+		.unchecked()
+		.run()
+		.is_ok_and(|o| o.status.success())
+}
+
+/// The target directory of a runtime built within a container image. The image builds within the
+/// runtime crate, to a dedicated target directory.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+/// * `runtime_dir` - The path to the runtime crate, relative to the project.
+fn container_target(project: &Path, runtime_dir: &Path) -> PathBuf {
+	project.join(runtime_dir).join("target/srtool/release")
+}
+
+/// The arguments used to build a runtime within a container image.
+///
+/// # Arguments
+/// * `image` - The container image.
+/// * `project` - The (absolute) path to the project.
+/// * `runtime_dir` - The path to the runtime crate, relative to the project.
+/// * `package` - The package of the runtime.
+fn container_args(image: &str, project: &Path, runtime_dir: &Path, package: &str) -> Vec<String> {
+	[
+		"run",
+		"--rm",
+		"-e",
+		&format!("PACKAGE={package}"),
+		"-e",
+		&format!("RUNTIME_DIR={}", runtime_dir.display()),
+		"-e",
+		"PROFILE=release",
+		"-v",
+		&format!("{}:/build", project.display()),
+		image,
+		"build",
+		"--app",
+	]
+	.map(String::from)
+	.to_vec()
+}
+
+/// Encodes a `System::authorize_upgrade` call authorizing an upgrade to the specified code.
+///
+/// # Arguments
+/// * `metadata` - The metadata of the runtime.
+/// * `code_hash` - The hash of the code.
+fn authorize_upgrade(metadata: &Metadata, code_hash: [u8; 32]) -> Result<Vec<u8>, Error> {
+	let call = metadata
+		.pallet_by_name("System")
+		.and_then(|p| Some((p.index(), p.call_variant_by_name("authorize_upgrade")?.index)))
+		.ok_or_else(|| {
+			Error::Config("the runtime does not provide `System::authorize_upgrade`".into())
+		})?;
+	Ok([&[call.0, call.1][..], &code_hash].concat())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use std::fs::write;
+	use tempfile::tempdir;
+
+	#[test]
+	fn container_args_works() {
+		assert_eq!(
+			container_args(
+				SRTOOL_IMAGE,
+				Path::new("/pop"),
+				Path::new("runtime"),
+				"parachain-template-runtime"
+			)
+			.join(" "),
+			format!(
+				"run --rm -e PACKAGE=parachain-template-runtime -e RUNTIME_DIR=runtime -e \
+				 PROFILE=release -v /pop:/build {SRTOOL_IMAGE} build --app"
+			)
+		);
+	}
+
+	#[test]
+	fn container_target_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let project = temp_dir.path();
+		let target = container_target(project, Path::new("runtime"));
+		assert_eq!(target, project.join("runtime/target/srtool/release"));
+		assert_eq!(container_target(project, Path::new("")), project.join("target/srtool/release"));
+
+		let wasm = target.join("wbuild/pop-runtime/pop_runtime.compact.compressed.wasm");
+		fs::create_dir_all(wasm.parent().unwrap())?;
+		write(&wasm, [0])?;
+		assert_eq!(compressed_runtime(&target, "pop-runtime")?, wasm);
+		Ok(())
+	}
+
+	#[test]
+	fn build_report_fails_with_invalid_runtime() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let wasm = temp_dir.path().join("runtime.wasm");
+		write(&wasm, [0u8, 97, 115, 109])?;
+		let builder = Builder::Toolchain { version: "rustc 1.81.0".into() };
+		assert!(matches!(
+			BuildReport::new("runtime", builder, wasm),
+			Err(Error::GenesisBuilderError(e)) if e.starts_with("invalid runtime")
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn builder_serialization_works() -> Result<()> {
+		let builder = Builder::Container { engine: "docker".into(), image: SRTOOL_IMAGE.into() };
+		assert_eq!(
+			serde_json::to_value(&builder)?,
+			serde_json::json!({ "container": { "engine": "docker", "image": SRTOOL_IMAGE } })
+		);
+		let builder = Builder::Toolchain { version: "rustc 1.81.0".into() };
+		assert_eq!(
+			serde_json::to_value(&builder)?,
+			serde_json::json!({ "toolchain": { "version": "rustc 1.81.0" } })
+		);
+		Ok(())
+	}
+}
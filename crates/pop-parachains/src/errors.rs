@@ -17,6 +17,10 @@ pub enum Error {
 	CurrentDirAccess,
 	#[error("Failed to parse the endowment value")]
 	EndowmentError,
+	#[error("Genesis builder error: {0}")]
+	GenesisBuilderError(String),
+	#[error("The `{0}` hook failed: {1}")]
+	Hook(String, String),
 	#[error("IO error: {0}")]
 	IO(#[from] std::io::Error),
 	#[error("JSON error: {0}")]
@@ -33,14 +37,28 @@ pub enum Error {
 	PalletDirCreation,
 	#[error("Invalid path")]
 	PathError,
+	#[error("RPC error: {0}")]
+	RpcError(#[from] subxt::Error),
+	#[error("Registration failed: {0}")]
+	RegistrationError(String),
 	#[error("Failed to execute rustfmt")]
 	RustfmtError(std::io::Error),
 	#[error("Template error: {0}")]
 	SourcingError(#[from] pop_common::sourcing::Error),
+	#[error("Failed to decode storage: {0}")]
+	StorageDecodingError(String),
+	#[error("Timed out waiting for {0}")]
+	Timeout(String),
 	#[error("Toml error: {0}")]
 	TomlError(#[from] toml_edit::de::Error),
+	#[error("Transaction failed: {0}")]
+	TransactionError(String),
 	#[error("Unsupported command: {0}")]
 	UnsupportedCommand(String),
+	#[error("Runtime upgrade failed: {0}")]
+	UpgradeError(String),
 	#[error("Failed to locate the workspace")]
 	WorkspaceLocate,
+	#[error("YAML error: {0}")]
+	YamlError(#[from] serde_yaml::Error),
 }
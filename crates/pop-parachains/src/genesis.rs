@@ -0,0 +1,342 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::errors::Error;
+use serde_json::Value;
+use smoldot::{
+	executor::{
+		host::{Config, HostVmPrototype},
+		runtime_call::{self, RuntimeCall},
+		storage_diff::TrieDiff,
+		vm::ExecHint,
+		DEFAULT_HEAP_PAGES,
+	},
+	header::{DigestRef, Header},
+	trie::{self, HashFunction, Nibble, TrieEntryVersion, EMPTY_BLAKE2_TRIE_MERKLE_VALUE},
+};
+use std::{collections::BTreeMap, iter};
+use subxt::{
+	ext::codec::{Decode, Encode},
+	Metadata,
+};
+
+/// The storage key of the runtime code.
+pub const CODE_KEY: &[u8] = b":code";
+/// The prefix of the storage keys of the roots of default child tries.
+const CHILD_STORAGE_PREFIX: &[u8] = b":child_storage:default:";
+
+/// Storage entries, keyed by storage key.
+pub type StorageEntries = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// The genesis storage of a chain.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GenesisStorage {
+	/// The entries of the main trie.
+	pub top: StorageEntries,
+	/// The entries of each default child trie, keyed by the (unprefixed) storage key of the
+	/// child trie.
+	pub children_default: BTreeMap<Vec<u8>, StorageEntries>,
+}
+
+/// Builds the genesis state of a chain from its runtime, via the `GenesisBuilder` runtime API
+/// (`get_preset`/`build_state`), without requiring a node binary.
+pub struct GenesisBuilder {
+	code: Vec<u8>,
+	vm: Option<HostVmPrototype>,
+}
+
+impl GenesisBuilder {
+	/// Prepares the runtime for execution.
+	///
+	/// # Arguments
+	/// * `code` - The runtime (WASM), which may be compressed.
+	pub fn new(code: Vec<u8>) -> Result<Self, Error> {
+		let vm = HostVmPrototype::new(Config {
+			module: &code,
+			heap_pages: DEFAULT_HEAP_PAGES,
+			exec_hint: ExecHint::CompileAheadOfTime,
+			allow_unresolved_imports: true,
+		})
+		.map_err(|e| Error::GenesisBuilderError(format!("invalid runtime: {e}")))?;
+		Ok(Self { code, vm: Some(vm) })
+	}
+
+	/// The runtime code.
+	pub fn code(&self) -> &[u8] {
+		&self.code
+	}
+
+	/// The name of the runtime, as per its `spec_name`.
+	pub fn spec_name(&self) -> String {
+		self.vm().runtime_version().decode().spec_name.to_string()
+	}
+
+	/// The version of the runtime, as per its `spec_version`.
+	pub fn spec_version(&self) -> u32 {
+		self.vm().runtime_version().decode().spec_version
+	}
+
+	/// The state version used by the runtime, as per its `system_version` (formerly
+	/// `state_version`).
+	pub fn system_version(&self) -> u8 {
+		match self.vm().runtime_version().decode().state_version {
+			Some(TrieEntryVersion::V1) => 1,
+			_ => 0,
+		}
+	}
+
+	/// The names of the genesis presets provided by the runtime.
+	pub fn presets(&mut self) -> Result<Vec<String>, Error> {
+		let (output, _) = self.call("GenesisBuilder_preset_names", &[])?;
+		let presets = Vec::<Vec<u8>>::decode(&mut output.as_slice()).map_err(subxt::Error::from)?;
+		Ok(presets.into_iter().map(|p| String::from_utf8_lossy(&p).into_owned()).collect())
+	}
+
+	/// The metadata of the runtime, preferring version 15 when supported.
+	pub fn metadata(&mut self) -> Result<Metadata, Error> {
+		let metadata = match self
+			.call("Metadata_metadata_at_version", &15u32.encode())
+			.ok()
+			.and_then(|(output, _)| Option::<Vec<u8>>::decode(&mut output.as_slice()).ok())
+		{
+			Some(Some(metadata)) => metadata,
+			// Older runtimes only provide their default metadata version
+			_ => {
+				let (output, _) = self.call("Metadata_metadata", &[])?;
+				Vec::<u8>::decode(&mut output.as_slice()).map_err(subxt::Error::from)?
+			},
+		};
+		Ok(Metadata::decode(&mut metadata.as_slice()).map_err(subxt::Error::from)?)
+	}
+
+	/// The genesis configuration of the specified preset, or the default genesis configuration
+	/// of the runtime when no preset is specified.
+	///
+	/// # Arguments
+	/// * `preset` - The name of the preset.
+	pub fn preset(&mut self, preset: Option<&str>) -> Result<Value, Error> {
+		let id = preset.map(|p| p.as_bytes().to_vec());
+		let (output, _) = self.call("GenesisBuilder_get_preset", &id.encode())?;
+		let Some(config) =
+			Option::<Vec<u8>>::decode(&mut output.as_slice()).map_err(subxt::Error::from)?
+		else {
+			return Err(Error::GenesisBuilderError(format!(
+				"the runtime does not provide a `{}` preset. Available presets: {}",
+				preset.unwrap_or("default"),
+				self.presets()?.join(", ")
+			)));
+		};
+		Ok(serde_json::from_slice(&config)?)
+	}
+
+	/// Builds the genesis storage from a genesis configuration patch, which is applied to the
+	/// default genesis configuration of the runtime. The runtime code is included in the storage.
+	///
+	/// # Arguments
+	/// * `patch` - The patch applied to the default genesis configuration.
+	pub fn build_state(&mut self, patch: &Value) -> Result<GenesisStorage, Error> {
+		let mut config = self.preset(None)?;
+		merge(&mut config, patch);
+		let (output, mut storage) =
+			self.call("GenesisBuilder_build_state", &serde_json::to_vec(&config)?.encode())?;
+		if let Err(e) =
+			Result::<(), String>::decode(&mut output.as_slice()).map_err(subxt::Error::from)?
+		{
+			return Err(Error::GenesisBuilderError(e));
+		}
+		storage.top.insert(CODE_KEY.to_vec(), self.code.clone());
+		Ok(storage)
+	}
+
+	/// The genesis head (the encoded genesis block header) of a chain with the specified genesis
+	/// storage, as required to register a parachain.
+	///
+	/// # Arguments
+	/// * `storage` - The genesis storage.
+	pub fn genesis_head(&self, storage: &GenesisStorage) -> Vec<u8> {
+		let version = self.vm().runtime_version().decode().state_version;
+		genesis_head(storage, version.unwrap_or(TrieEntryVersion::V0))
+	}
+
+	/// The prepared runtime.
+	fn vm(&self) -> &HostVmPrototype {
+		self.vm.as_ref().expect("the runtime is restored after each call")
+	}
+
+	/// Calls a runtime function against empty storage, returning its output along with any
+	/// storage written.
+	///
+	/// # Arguments
+	/// * `function` - The name of the runtime function.
+	/// * `parameter` - The encoded parameter of the function.
+	fn call(
+		&mut self,
+		function: &str,
+		parameter: &[u8],
+	) -> Result<(Vec<u8>, GenesisStorage), Error> {
+		let vm = self.vm.take().expect("the runtime is restored after each call");
+		let error = |e: String| Error::GenesisBuilderError(format!("`{function}` failed: {e}"));
+		let mut call = match runtime_call::run(runtime_call::Config {
+			virtual_machine: vm,
+			function_to_call: function,
+			parameter: iter::once(parameter),
+			storage_main_trie_changes: TrieDiff::empty(),
+			max_log_level: 0,
+			calculate_trie_changes: false,
+		}) {
+			Ok(call) => call,
+			Err((e, vm)) => {
+				self.vm = Some(vm);
+				return Err(error(e.to_string()));
+			},
+		};
+		loop {
+			call = match call {
+				RuntimeCall::Finished(Ok(success)) => {
+					let output = success.virtual_machine.value().as_ref().to_vec();
+					let changes = &success.storage_changes;
+					let entries = |changes: &mut dyn Iterator<Item = (&[u8], Option<&[u8]>)>| {
+						changes
+							.filter_map(|(k, v)| Some((k.to_vec(), v?.to_vec())))
+							.collect::<StorageEntries>()
+					};
+					let storage = GenesisStorage {
+						top: entries(&mut changes.main_trie_storage_changes_iter_unordered()),
+						children_default: changes
+							.tries_with_storage_changes_unordered()
+							.map(|child| {
+								let mut changes =
+									changes.child_trie_storage_changes_iter_unordered(child);
+								(child.to_vec(), entries(&mut changes))
+							})
+							.collect(),
+					};
+					self.vm = Some(success.virtual_machine.into_prototype());
+					return Ok((output, storage));
+				},
+				RuntimeCall::Finished(Err(e)) => {
+					self.vm = Some(e.prototype);
+					return Err(error(e.detail.to_string()));
+				},
+				// The storage is initially empty
+				RuntimeCall::StorageGet(request) =>
+					request.inject_value(None::<(iter::Empty<Vec<u8>>, TrieEntryVersion)>),
+				RuntimeCall::ClosestDescendantMerkleValue(request) => request.resume_unknown(),
+				RuntimeCall::NextKey(request) => request.inject_key(None::<iter::Empty<Nibble>>),
+				RuntimeCall::SignatureVerification(request) => request.verify_and_resume(),
+				RuntimeCall::LogEmit(request) => request.resume(),
+				RuntimeCall::OffchainStorageSet(request) => request.resume(),
+				RuntimeCall::Offchain(request) => {
+					self.vm = Some(request.into_prototype());
+					return Err(error("offchain functionality is not supported".into()));
+				},
+			}
+		}
+	}
+}
+
+/// The genesis head (the encoded genesis block header) of a chain with the specified genesis
+/// storage.
+///
+/// # Arguments
+/// * `storage` - The genesis storage.
+/// * `version` - The state version of the runtime.
+fn genesis_head(storage: &GenesisStorage, version: TrieEntryVersion) -> Vec<u8> {
+	let root = |entries: &StorageEntries| {
+		let entries: Vec<_> = entries.iter().collect();
+		trie::trie_root(version, HashFunction::Blake2, &entries)
+	};
+	// The roots of any child tries are stored within the main trie
+	let mut top = storage.top.clone();
+	for (child, entries) in &storage.children_default {
+		top.insert([CHILD_STORAGE_PREFIX, child].concat(), root(entries).to_vec());
+	}
+	Header {
+		parent_hash: [0; 32],
+		number: 0,
+		state_root: root(&top),
+		extrinsics_root: EMPTY_BLAKE2_TRIE_MERKLE_VALUE,
+		digest: DigestRef::empty().into(),
+	}
+	.scale_encoding_vec(4)
+}
+
+/// Merges a JSON patch into a JSON value, recursively merging objects and replacing any other
+/// values.
+///
+/// # Arguments
+/// * `value` - The value to be patched.
+/// * `patch` - The patch.
+fn merge(value: &mut Value, patch: &Value) {
+	match (value, patch) {
+		(Value::Object(value), Value::Object(patch)) =>
+			for (key, patch) in patch {
+				merge(value.entry(key).or_insert(Value::Null), patch);
+			},
+		(value, patch) => *value = patch.clone(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn new_fails_with_invalid_runtime() {
+		assert!(matches!(
+			GenesisBuilder::new(vec![0, 97, 115, 109]),
+			Err(Error::GenesisBuilderError(e)) if e.starts_with("invalid runtime")
+		));
+	}
+
+	#[test]
+	fn genesis_head_works() {
+		let empty = genesis_head(&GenesisStorage::default(), TrieEntryVersion::V1);
+		let expected = [
+			&[0u8; 32][..],
+			&[0],
+			&EMPTY_BLAKE2_TRIE_MERKLE_VALUE,
+			&EMPTY_BLAKE2_TRIE_MERKLE_VALUE,
+			&[0],
+		]
+		.concat();
+		assert_eq!(empty, expected);
+
+		let mut storage = GenesisStorage::default();
+		storage.top.insert(CODE_KEY.to_vec(), vec![1, 2, 3]);
+		let head = genesis_head(&storage, TrieEntryVersion::V1);
+		assert_eq!(head.len(), expected.len());
+		assert_ne!(head, expected);
+		// Child tries contribute to the state root
+		storage
+			.children_default
+			.insert(b"contracts".to_vec(), [(vec![1], vec![2])].into());
+		assert_ne!(genesis_head(&storage, TrieEntryVersion::V1), head);
+	}
+
+	#[test]
+	fn merge_works() {
+		let mut config = json!({
+			"balances": { "balances": [["alice", 1]], "devAccounts": null },
+			"parachainInfo": { "parachainId": 1000 },
+			"sudo": { "key": null }
+		});
+		merge(
+			&mut config,
+			&json!({
+				"balances": { "balances": [["bob", 2]] },
+				"parachainInfo": { "parachainId": 2000 },
+				"polkadotXcm": { "safeXcmVersion": 4 }
+			}),
+		);
+		assert_eq!(
+			config,
+			json!({
+				"balances": { "balances": [["bob", 2]], "devAccounts": null },
+				"parachainInfo": { "parachainId": 2000 },
+				"sudo": { "key": null },
+				"polkadotXcm": { "safeXcmVersion": 4 }
+			})
+		);
+	}
+}
@@ -0,0 +1,634 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	build::ChainSpec,
+	errors::Error,
+	genesis::{GenesisBuilder, GenesisStorage, CODE_KEY},
+};
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use sp_crypto_hashing::{blake2_256, twox_128};
+use std::collections::BTreeMap;
+use subxt::{
+	ext::{
+		codec::Encode,
+		scale_value::{self, Composite, Primitive, ValueDef},
+	},
+	metadata::types::{StorageEntryType, StorageHasher},
+	utils::AccountId32,
+	Metadata,
+};
+
+/// The well-known storage items decoded from the genesis storage of a raw chain specification,
+/// as pallet and storage item names.
+const STORAGE_ITEMS: [(&str, &str); 4] = [
+	("System", "Account"),
+	("Sudo", "Key"),
+	("ParachainInfo", "ParachainId"),
+	("Aura", "Authorities"),
+];
+
+/// A summary of a chain specification, suitable for inspecting and comparing chain
+/// specifications.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Inspection {
+	/// The top-level fields of the chain specification, other than its genesis. Any code
+	/// substitutes are replaced with their hashes.
+	#[serde(flatten)]
+	pub fields: Map<String, Value>,
+	/// The BLAKE2-256 hash of the runtime code, if any.
+	pub code_hash: Option<String>,
+	/// The genesis configuration of a plain chain specification, or the well-known storage items
+	/// of a raw chain specification, decoded using the metadata of its runtime.
+	pub genesis: Value,
+	/// A summary of the full genesis storage of a raw chain specification, covering any storage
+	/// items which are not decoded.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<StorageSummary>,
+}
+
+/// A summary of the genesis storage of a raw chain specification.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageSummary {
+	/// The BLAKE2-256 hash of all storage entries, including those of child tries, sorted by key.
+	pub digest: String,
+	/// The number of storage entries.
+	pub entries: usize,
+	/// The BLAKE2-256 hash of the value of each storage entry, keyed by the storage key of any
+	/// child trie and the key of the entry.
+	#[serde(skip)]
+	hashes: BTreeMap<(Vec<u8>, Vec<u8>), [u8; 32]>,
+}
+
+impl StorageSummary {
+	/// Summarises genesis storage.
+	///
+	/// # Arguments
+	/// * `storage` - The genesis storage.
+	fn new(storage: &GenesisStorage) -> Self {
+		let entries: Vec<_> = storage
+			.top
+			.iter()
+			.map(|(key, value)| (&[][..], key, value))
+			.chain(storage.children_default.iter().flat_map(|(child, entries)| {
+				entries.iter().map(move |(key, value)| (child.as_slice(), key, value))
+			}))
+			.collect();
+		Self {
+			digest: format!("0x{}", hex::encode(blake2_256(&entries.encode()))),
+			entries: entries.len(),
+			hashes: entries
+				.iter()
+				.map(|(child, key, value)| ((child.to_vec(), key.to_vec()), blake2_256(value)))
+				.collect(),
+		}
+	}
+
+	/// The changes between this genesis storage and another.
+	///
+	/// # Arguments
+	/// * `other` - The genesis storage to compare against.
+	pub fn changes(&self, other: &StorageSummary) -> StorageChanges {
+		let mut changes = StorageChanges::default();
+		for (key, hash) in &self.hashes {
+			match other.hashes.get(key) {
+				Some(other) if other != hash => changes.changed += 1,
+				Some(_) => {},
+				None => changes.removed += 1,
+			}
+		}
+		changes.added = other.hashes.keys().filter(|k| !self.hashes.contains_key(*k)).count();
+		changes
+	}
+}
+
+/// The number of storage entries added, removed and changed between two genesis storages.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StorageChanges {
+	/// The number of entries added.
+	pub added: usize,
+	/// The number of entries removed.
+	pub removed: usize,
+	/// The number of entries whose values changed.
+	pub changed: usize,
+}
+
+impl Inspection {
+	/// The differences between this inspection and another, such as of the same chain before and
+	/// after a runtime upgrade. Any difference in the genesis storage of raw chain specifications
+	/// is reported via the digest of the storage, including storage items which are not decoded.
+	///
+	/// # Arguments
+	/// * `other` - The inspection to compare against.
+	pub fn diff(&self, other: &Inspection) -> Result<Vec<Difference>, Error> {
+		let mut differences = vec![];
+		diff("", &serde_json::to_value(self)?, &serde_json::to_value(other)?, &mut differences);
+		Ok(differences)
+	}
+}
+
+/// A difference between two chain specifications.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difference {
+	/// The location of the difference, as a JSON pointer.
+	pub path: String,
+	/// The value within the first chain specification, if any.
+	pub left: Option<Value>,
+	/// The value within the second chain specification, if any.
+	pub right: Option<Value>,
+}
+
+impl ChainSpec {
+	/// Inspects the chain specification. The well-known storage items of a raw chain
+	/// specification are decoded using the metadata of the runtime contained within it.
+	pub fn inspect(&self) -> Result<Inspection, Error> {
+		let mut fields = self
+			.0
+			.as_object()
+			.cloned()
+			.ok_or_else(|| Error::Config("expected a JSON object".into()))?;
+		let genesis = fields.remove("genesis").unwrap_or_default();
+		if let Some(Value::Object(substitutes)) = fields.get_mut("codeSubstitutes") {
+			for code in substitutes.values_mut() {
+				if let Some(bytes) =
+					code.as_str().and_then(|c| hex::decode(c.trim_start_matches("0x")).ok())
+				{
+					*code = json!(code_hash(&bytes));
+				}
+			}
+		}
+
+		let (code, genesis, storage) = if genesis.get("raw").is_some() {
+			let storage = self.raw_storage()?;
+			let code = storage.top.get(CODE_KEY).cloned().ok_or_else(|| {
+				Error::Config("expected the runtime code within `genesis.raw.top`".into())
+			})?;
+			let metadata = GenesisBuilder::new(code.clone())?.metadata()?;
+			let genesis = decode_storage(&storage, &metadata)?;
+			(Some(code), genesis, Some(StorageSummary::new(&storage)))
+		} else if genesis.get("runtimeGenesis").is_some() {
+			let (code, config) = self.runtime_genesis()?;
+			(Some(code), config, None)
+		} else {
+			(None, genesis, None)
+		};
+		Ok(Inspection { fields, code_hash: code.as_deref().map(code_hash), genesis, storage })
+	}
+}
+
+/// The BLAKE2-256 hash of runtime code, hex-encoded.
+///
+/// # Arguments
+/// * `code` - The runtime code.
+fn code_hash(code: &[u8]) -> String {
+	format!("0x{}", hex::encode(blake2_256(code)))
+}
+
+/// Collects the differences between two JSON values, recursing into objects and arrays.
+///
+/// # Arguments
+/// * `path` - The location of the values, as a JSON pointer.
+/// * `left` - The first value.
+/// * `right` - The second value.
+/// * `differences` - The differences found.
+fn diff(path: &str, left: &Value, right: &Value, differences: &mut Vec<Difference>) {
+	let child = |key: &str| format!("{path}/{}", key.replace('~', "~0").replace('/', "~1"));
+	match (left, right) {
+		(Value::Object(left), Value::Object(right)) => {
+			for (key, value) in left {
+				match right.get(key) {
+					Some(other) => diff(&child(key), value, other, differences),
+					None => differences.push(Difference {
+						path: child(key),
+						left: Some(value.clone()),
+						right: None,
+					}),
+				}
+			}
+			for (key, value) in right.iter().filter(|(k, _)| !left.contains_key(*k)) {
+				differences.push(Difference {
+					path: child(key),
+					left: None,
+					right: Some(value.clone()),
+				});
+			}
+		},
+		(Value::Array(left), Value::Array(right)) =>
+			for i in 0..left.len().max(right.len()) {
+				match (left.get(i), right.get(i)) {
+					(Some(value), Some(other)) =>
+						diff(&child(&i.to_string()), value, other, differences),
+					(value, other) => differences.push(Difference {
+						path: child(&i.to_string()),
+						left: value.cloned(),
+						right: other.cloned(),
+					}),
+				}
+			},
+		(left, right) if left != right => differences.push(Difference {
+			path: path.to_string(),
+			left: Some(left.clone()),
+			right: Some(right.clone()),
+		}),
+		_ => {},
+	}
+}
+
+/// Decodes the well-known storage items within genesis storage, keyed by pallet and storage
+/// item name. Storage items which are not present are omitted.
+///
+/// # Arguments
+/// * `storage` - The genesis storage.
+/// * `metadata` - The metadata of the runtime.
+fn decode_storage(storage: &GenesisStorage, metadata: &Metadata) -> Result<Value, Error> {
+	let types = metadata.types();
+	let mut decoded = Map::new();
+	for (pallet, item) in STORAGE_ITEMS {
+		let Some(entry) = metadata
+			.pallet_by_name(pallet)
+			.and_then(|p| p.storage())
+			.and_then(|s| s.entry_by_name(item))
+		else {
+			continue;
+		};
+		let prefix = [twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat();
+		let value = match entry.entry_type() {
+			StorageEntryType::Plain(ty) => match storage.top.get(&prefix) {
+				Some(value) => decode(value, *ty, types)?,
+				None => continue,
+			},
+			StorageEntryType::Map { hashers, key_ty, value_ty } => {
+				let mut entries = Map::new();
+				for (key, value) in storage
+					.top
+					.range(prefix.clone()..)
+					.take_while(|(key, _)| key.starts_with(&prefix))
+				{
+					let key = match hashers.as_slice() {
+						[hasher] => decode_key(&key[prefix.len()..], hasher, *key_ty, types)?,
+						_ => format!("0x{}", hex::encode(&key[prefix.len()..])),
+					};
+					entries.insert(key, decode(value, *value_ty, types)?);
+				}
+				if entries.is_empty() {
+					continue;
+				}
+				Value::Object(entries)
+			},
+		};
+		decoded.entry(pallet).or_insert_with(|| json!({}))[item] = value;
+	}
+	Ok(Value::Object(decoded))
+}
+
+/// Decodes the key of a storage map entry, which is returned hex-encoded when it cannot be
+/// recovered from its hash.
+///
+/// # Arguments
+/// * `key` - The hashed key, without the prefix of the storage item.
+/// * `hasher` - The hasher of the storage map.
+/// * `ty` - The type of the key.
+/// * `types` - The type registry of the runtime.
+fn decode_key(
+	key: &[u8],
+	hasher: &StorageHasher,
+	ty: u32,
+	types: &PortableRegistry,
+) -> Result<String, Error> {
+	let offset = match hasher {
+		StorageHasher::Blake2_128Concat => 16,
+		StorageHasher::Twox64Concat => 8,
+		StorageHasher::Identity => 0,
+		_ => return Ok(format!("0x{}", hex::encode(key))),
+	};
+	let key = key.get(offset..).ok_or_else(|| {
+		Error::StorageDecodingError(format!("invalid key 0x{}", hex::encode(key)))
+	})?;
+	Ok(match decode(key, ty, types)? {
+		Value::String(key) => key,
+		key => key.to_string(),
+	})
+}
+
+/// Decodes a SCALE-encoded value into a human-readable JSON value.
+///
+/// # Arguments
+/// * `bytes` - The encoded value.
+/// * `ty` - The type of the value.
+/// * `types` - The type registry of the runtime.
+fn decode(mut bytes: &[u8], ty: u32, types: &PortableRegistry) -> Result<Value, Error> {
+	let value = scale_value::scale::decode_as_type(&mut bytes, ty, types)
+		.map_err(|e| Error::StorageDecodingError(e.to_string()))?;
+	Ok(to_json(&value, types))
+}
+
+/// Converts a decoded value into a human-readable JSON value: accounts and public keys are
+/// SS58-encoded, other bytes are hex-encoded and single-field structs are unwrapped.
+///
+/// # Arguments
+/// * `value` - The decoded value, along with its type.
+/// * `types` - The type registry of the runtime.
+fn to_json(value: &scale_value::Value<u32>, types: &PortableRegistry) -> Value {
+	let ty = types.resolve(value.context);
+	if let Some(bytes) = bytes(value, types) {
+		let account = ty
+			.and_then(|t| t.path.ident())
+			.is_some_and(|i| i == "AccountId32" || i == "Public");
+		return match <[u8; 32]>::try_from(bytes.as_slice()) {
+			Ok(bytes) if account => json!(AccountId32(bytes).to_string()),
+			_ => json!(format!("0x{}", hex::encode(bytes))),
+		};
+	}
+	let composite = |values: &Composite<u32>| match values {
+		Composite::Named(fields) =>
+			Value::Object(fields.iter().map(|(n, v)| (n.clone(), to_json(v, types))).collect()),
+		Composite::Unnamed(fields) =>
+			Value::Array(fields.iter().map(|v| to_json(v, types)).collect()),
+	};
+	match &value.value {
+		ValueDef::Composite(Composite::Unnamed(fields))
+			if fields.len() == 1 &&
+				ty.is_some_and(|t| matches!(t.type_def, TypeDef::Composite(_))) =>
+			to_json(&fields[0], types),
+		ValueDef::Composite(values) => composite(values),
+		ValueDef::Variant(variant) => match &variant.values {
+			values if values.is_empty() => json!(variant.name),
+			Composite::Unnamed(fields) if fields.len() == 1 =>
+				json!({ variant.name.as_str(): to_json(&fields[0], types) }),
+			values => json!({ variant.name.as_str(): composite(values) }),
+		},
+		ValueDef::BitSequence(bits) =>
+			json!(bits.iter().map(|b| if b { '1' } else { '0' }).collect::<String>()),
+		ValueDef::Primitive(primitive) => match primitive {
+			Primitive::Bool(b) => json!(b),
+			Primitive::Char(c) => json!(c.to_string()),
+			Primitive::String(s) => json!(s),
+			Primitive::U128(n) => json!(n),
+			Primitive::I128(n) => json!(n),
+			Primitive::U256(n) | Primitive::I256(n) => json!(format!("0x{}", hex::encode(n))),
+		},
+	}
+}
+
+/// The bytes of a decoded value, when it is a sequence or array of bytes, possibly wrapped within
+/// single-field structs.
+///
+/// # Arguments
+/// * `value` - The decoded value, along with its type.
+/// * `types` - The type registry of the runtime.
+fn bytes(value: &scale_value::Value<u32>, types: &PortableRegistry) -> Option<Vec<u8>> {
+	let is_byte = |ty: u32| {
+		types
+			.resolve(ty)
+			.is_some_and(|t| matches!(t.type_def, TypeDef::Primitive(TypeDefPrimitive::U8)))
+	};
+	let ValueDef::Composite(values) = &value.value else {
+		return None;
+	};
+	match &types.resolve(value.context)?.type_def {
+		TypeDef::Composite(_) if values.len() == 1 => bytes(values.values().next()?, types),
+		TypeDef::Sequence(s) if is_byte(s.type_param.id) => primitive_bytes(values),
+		TypeDef::Array(a) if is_byte(a.type_param.id) => primitive_bytes(values),
+		_ => None,
+	}
+}
+
+/// The bytes of a composite value consisting of byte primitives.
+///
+/// # Arguments
+/// * `values` - The composite value.
+fn primitive_bytes(values: &Composite<u32>) -> Option<Vec<u8>> {
+	values
+		.values()
+		.map(|v| match v.value {
+			ValueDef::Primitive(Primitive::U128(b)) => u8::try_from(b).ok(),
+			_ => None,
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use scale_info::{MetaType, Registry, TypeInfo};
+	use sp_crypto_hashing::blake2_128;
+
+	#[allow(dead_code)]
+	#[derive(TypeInfo)]
+	struct AccountData {
+		free: u128,
+		flags: Flags,
+	}
+
+	#[allow(dead_code)]
+	#[derive(TypeInfo)]
+	struct Flags(u128);
+
+	#[allow(dead_code)]
+	#[derive(TypeInfo)]
+	enum Status {
+		Active,
+		Limited(u32),
+	}
+
+	/// Registers a type, returning the type registry along with the identifier of the type.
+	fn register<T: TypeInfo + 'static>() -> (PortableRegistry, u32) {
+		let mut registry = Registry::new();
+		let id = registry.register_type(&MetaType::new::<T>()).id;
+		(registry.into(), id)
+	}
+
+	fn plain_chain_spec(code: &str, balance: u128) -> ChainSpec {
+		ChainSpec(json!({
+			"name": "Pop",
+			"id": "pop",
+			"codeSubstitutes": { "1": "0x0102" },
+			"genesis": { "runtimeGenesis": {
+				"code": code,
+				"patch": { "balances": { "balances": [["alice", balance]] } }
+			}}
+		}))
+	}
+
+	#[test]
+	fn inspect_works() -> Result<()> {
+		let inspection = plain_chain_spec("0x0001", 1).inspect()?;
+		assert_eq!(
+			inspection,
+			Inspection {
+				fields: json!({
+					"name": "Pop",
+					"id": "pop",
+					"codeSubstitutes": { "1": code_hash(&[1, 2]) }
+				})
+				.as_object()
+				.cloned()
+				.unwrap(),
+				code_hash: Some(code_hash(&[0, 1])),
+				genesis: json!({ "balances": { "balances": [["alice", 1]] } }),
+				storage: None,
+			}
+		);
+		// Genesis in other formats is included as is
+		let chain_spec = ChainSpec(json!({ "genesis": { "runtime": { "system": {} } } }));
+		assert_eq!(chain_spec.inspect()?.genesis, json!({ "runtime": { "system": {} } }));
+		Ok(())
+	}
+
+	#[test]
+	fn inspect_fails_without_raw_code() {
+		let chain_spec = ChainSpec::raw("Pop", "pop", 2000, json!({}), Default::default());
+		assert!(matches!(
+			chain_spec.inspect(),
+			Err(Error::Config(e)) if e == "expected the runtime code within `genesis.raw.top`"
+		));
+	}
+
+	#[test]
+	fn diff_works() -> Result<()> {
+		let left = plain_chain_spec("0x0001", 1).inspect()?;
+		let mut right = plain_chain_spec("0x0002", u128::MAX);
+		right.0["name"] = json!("Pop Testnet");
+		right.0["bootNodes"] = json!(["/ip4/127.0.0.1/tcp/30333"]);
+		right.0["genesis"]["runtimeGenesis"]["patch"]["balances"]["balances"]
+			.as_array_mut()
+			.unwrap()
+			.push(json!(["bob", 2]));
+		right.0.as_object_mut().unwrap().remove("codeSubstitutes");
+		let right = right.inspect()?;
+
+		assert!(left.diff(&left)?.is_empty());
+		assert_eq!(
+			left.diff(&right)?,
+			vec![
+				Difference {
+					path: "/name".into(),
+					left: Some(json!("Pop")),
+					right: Some(json!("Pop Testnet"))
+				},
+				Difference {
+					path: "/codeSubstitutes".into(),
+					left: Some(json!({ "1": code_hash(&[1, 2]) })),
+					right: None
+				},
+				Difference {
+					path: "/codeHash".into(),
+					left: Some(json!(code_hash(&[0, 1]))),
+					right: Some(json!(code_hash(&[0, 2])))
+				},
+				Difference {
+					path: "/genesis/balances/balances/0/1".into(),
+					left: Some(json!(1)),
+					right: Some(json!(u128::MAX))
+				},
+				Difference {
+					path: "/genesis/balances/balances/1".into(),
+					left: None,
+					right: Some(json!(["bob", 2]))
+				},
+				Difference {
+					path: "/bootNodes".into(),
+					left: None,
+					right: Some(json!(["/ip4/127.0.0.1/tcp/30333"]))
+				},
+			]
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn storage_summary_works() -> Result<()> {
+		let mut storage = GenesisStorage::default();
+		storage.top.insert(b"a".to_vec(), vec![1]);
+		storage.top.insert(b"b".to_vec(), vec![2]);
+		storage
+			.children_default
+			.insert(b"child".to_vec(), [(b"a".to_vec(), vec![3])].into());
+		let left = StorageSummary::new(&storage);
+		assert_eq!(left.entries, 3);
+		assert_eq!(left.changes(&left), StorageChanges::default());
+		assert_eq!(StorageSummary::new(&storage.clone()).digest, left.digest);
+
+		// Changes to storage which is not decoded are reflected by the digest
+		storage.top.remove(b"a".as_slice());
+		storage.top.insert(b"b".to_vec(), vec![4]);
+		storage.top.insert(b"c".to_vec(), vec![5]);
+		storage.top.insert(b"d".to_vec(), vec![6]);
+		let right = StorageSummary::new(&storage);
+		assert_ne!(right.digest, left.digest);
+		assert_eq!(right.entries, 4);
+		assert_eq!(left.changes(&right), StorageChanges { added: 2, removed: 1, changed: 1 });
+
+		let inspection = |storage| Inspection {
+			fields: Map::new(),
+			code_hash: None,
+			genesis: json!({}),
+			storage: Some(storage),
+		};
+		assert_eq!(
+			inspection(left.clone()).diff(&inspection(right.clone()))?,
+			vec![
+				Difference {
+					path: "/storage/digest".into(),
+					left: Some(json!(left.digest)),
+					right: Some(json!(right.digest))
+				},
+				Difference {
+					path: "/storage/entries".into(),
+					left: Some(json!(3)),
+					right: Some(json!(4))
+				},
+			]
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn decode_works() -> Result<()> {
+		let alice = AccountId32([1; 32]);
+		let (types, ty) = register::<AccountId32>();
+		assert_eq!(decode(&alice.encode(), ty, &types)?, json!(alice.to_string()));
+
+		let (types, ty) = register::<Vec<u8>>();
+		assert_eq!(decode(&vec![1u8, 2].encode(), ty, &types)?, json!("0x0102"));
+
+		let (types, ty) = register::<Vec<u32>>();
+		assert_eq!(decode(&vec![7u32].encode(), ty, &types)?, json!([7]));
+
+		let (types, ty) = register::<AccountData>();
+		assert_eq!(
+			decode(&(u128::MAX, 2u128).encode(), ty, &types)?,
+			json!({ "free": u128::MAX, "flags": 2 })
+		);
+
+		let (types, ty) = register::<Vec<Status>>();
+		let encoded = [vec![2u8 << 2], vec![0u8], vec![1u8], 5u32.encode()].concat();
+		assert_eq!(decode(&encoded, ty, &types)?, json!(["Active", { "Limited": 5 }]));
+		Ok(())
+	}
+
+	#[test]
+	fn decode_key_works() -> Result<()> {
+		let alice = AccountId32([1; 32]);
+		let (types, ty) = register::<AccountId32>();
+		let key = [blake2_128(&alice.encode()).to_vec(), alice.encode()].concat();
+		assert_eq!(
+			decode_key(&key, &StorageHasher::Blake2_128Concat, ty, &types)?,
+			alice.to_string()
+		);
+		let (types, ty) = register::<u32>();
+		let key = [vec![0; 8], 2000u32.encode()].concat();
+		assert_eq!(decode_key(&key, &StorageHasher::Twox64Concat, ty, &types)?, "2000");
+		// Hashed keys cannot be decoded
+		assert_eq!(decode_key(&[1, 2], &StorageHasher::Blake2_256, ty, &types)?, "0x0102");
+		assert!(matches!(
+			decode_key(&[1, 2], &StorageHasher::Blake2_128Concat, ty, &types),
+			Err(Error::StorageDecodingError(_))
+		));
+		Ok(())
+	}
+}
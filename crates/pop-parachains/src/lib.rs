@@ -2,24 +2,42 @@
 
 #![doc = include_str!("../README.md")]
 mod build;
+mod deterministic;
 mod errors;
 mod generator;
+mod genesis;
+mod inspect;
 mod new_pallet;
 mod new_parachain;
+mod register;
+mod runtime;
 mod templates;
 mod up;
+mod upgrade;
 mod utils;
 
 pub use build::{
-	binary_path, build_parachain, export_wasm_file, generate_genesis_state_file,
-	generate_plain_chain_spec, generate_raw_chain_spec, is_supported, ChainSpec,
+	binary_path, build_parachain, export_wasm_file, export_wasm_file_from_chain_spec,
+	generate_genesis_state_file, generate_genesis_state_file_from_chain_spec,
+	generate_plain_chain_spec, generate_plain_chain_spec_from_runtime, generate_raw_chain_spec,
+	generate_raw_chain_spec_from_runtime, is_supported, ChainSpec, Collator,
 };
+pub use deterministic::{AuthorizeUpgrade, BuildReport, Builder, SRTOOL_IMAGE};
 pub use errors::Error;
+pub use genesis::{GenesisBuilder, GenesisStorage};
 pub use indexmap::IndexSet;
+pub use inspect::{Difference, Inspection, StorageChanges, StorageSummary};
 pub use new_pallet::{create_pallet_template, new_pallet_options::*, TemplatePalletConfig};
 pub use new_parachain::instantiate_template_dir;
+pub use register::{read_genesis_artifact, Registration, RegistrationMethod};
+pub use runtime::{max_code_size, Runtime, RuntimeArtifacts, DEFAULT_MAX_CODE_SIZE};
 pub use templates::{Config, Parachain, Provider};
-pub use up::Zombienet;
+pub use up::{
+	check, generate_network_config, Fork, Hooks, HrmpChannel, LogFilter, LogFollower, LogLevel,
+	LogLine, NetworkEntry, NetworkInfo, NetworkSnapshot, NodeEntry, NodeInfo, ParachainInfo,
+	Problem, Readiness, Registry, RelayChainInfo, Zombienet, NETWORK_JSON,
+};
+pub use upgrade::{runtime_wasm, RuntimeUpgrade, UpgradeMethod, UpgradeOutcome};
 pub use utils::helpers::is_initial_endowment_valid;
 /// Information about the Node. External export from Zombienet-SDK.
 pub use zombienet_sdk::NetworkNode;
@@ -0,0 +1,353 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	utils::extrinsics::{signer, submit, sudo},
+};
+use pop_common::Status;
+use std::{fs::read, path::Path, time::Duration};
+use subxt::{
+	dynamic::{storage, tx, Value},
+	ext::{
+		codec::Decode,
+		scale_value::{At, ValueDef},
+	},
+	storage::Storage,
+	OnlineClient, PolkadotConfig,
+};
+use subxt_signer::sr25519::Keypair;
+use tokio::time::{timeout_at, Instant};
+use url::Url;
+
+/// The number of parts into which the coretime of a core is divided, as per `PartsOf57600`.
+const CORE_PARTS: u128 = 57_600;
+
+/// The method used to register a parachain on a relay chain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RegistrationMethod {
+	/// The parachain is initialized via `Sudo`, using
+	/// `ParasSudoWrapper::sudo_schedule_para_initialize`, and assigned a core via
+	/// `Coretime::assign_core` where available. Used when the signer is the sudo account of the
+	/// relay chain, as is the case for local relay chains.
+	Sudo,
+	/// The parachain identifier is reserved via `Registrar::reserve` and then the parachain
+	/// registered via `Registrar::register`, onboarding it as an on-demand parachain
+	/// (parathread). Coretime is not acquired, although on-demand orders can be placed via
+	/// [`Registration::place_order`].
+	Registrar,
+}
+
+impl RegistrationMethod {
+	/// The lifecycle of the parachain once onboarded via the method.
+	pub fn onboarded(&self) -> &'static str {
+		match self {
+			RegistrationMethod::Sudo => "Parachain",
+			RegistrationMethod::Registrar => "Parathread",
+		}
+	}
+}
+
+/// The registration of a parachain on a running relay chain, using the genesis artifacts
+/// generated via `pop build spec`.
+pub struct Registration {
+	client: OnlineClient<PolkadotConfig>,
+	signer: Keypair,
+	para_id: u32,
+	genesis_head: Vec<u8>,
+	validation_code: Vec<u8>,
+}
+
+impl Registration {
+	/// Connects to a running relay chain in preparation for registering a parachain.
+	///
+	/// # Arguments
+	/// * `url` - The websocket endpoint of a node of the relay chain.
+	/// * `suri` - The secret URI of the account used to register the parachain (e.g. "//Alice").
+	/// * `para_id` - The identifier of the parachain.
+	/// * `genesis_head` - The genesis state (head data) of the parachain.
+	/// * `validation_code` - The genesis code (WASM) of the parachain.
+	pub async fn new(
+		url: &Url,
+		suri: &str,
+		para_id: u32,
+		genesis_head: Vec<u8>,
+		validation_code: Vec<u8>,
+	) -> Result<Self, Error> {
+		let signer = signer(suri)?;
+		let client = OnlineClient::from_insecure_url(url.as_str()).await?;
+		Ok(Self { client, signer, para_id, genesis_head, validation_code })
+	}
+
+	/// The method used to register the parachain, based on the pallets available on the relay
+	/// chain and whether the signer is its sudo account.
+	pub async fn method(&self) -> Result<RegistrationMethod, Error> {
+		if self.has_call("ParasSudoWrapper", "sudo_schedule_para_initialize") {
+			if let Some(key) = self.fetch(storage("Sudo", "Key", vec![])).await? {
+				if key == self.signer.public_key().0 {
+					return Ok(RegistrationMethod::Sudo);
+				}
+			}
+		}
+		match self.has_call("Registrar", "register") {
+			true => Ok(RegistrationMethod::Registrar),
+			false => Err(Error::RegistrationError(
+				"the relay chain does not support registering parachains".into(),
+			)),
+		}
+	}
+
+	/// Registers the parachain, returning the method used. Registration only schedules the
+	/// parachain to be onboarded, which can be tracked via [`Self::wait_until_onboarded`].
+	///
+	/// # Arguments
+	/// * `status` - Used to observe status updates.
+	pub async fn register(&self, status: &impl Status) -> Result<RegistrationMethod, Error> {
+		if self.lifecycle().await?.is_some() {
+			return Err(Error::RegistrationError(format!(
+				"parachain {} is already registered",
+				self.para_id
+			)));
+		}
+		let para_id = Value::u128(self.para_id.into());
+		let method = self.method().await?;
+		match method {
+			RegistrationMethod::Sudo => {
+				status.update(
+					"Initializing via `ParasSudoWrapper::sudo_schedule_para_initialize`...",
+				);
+				let genesis = Value::named_composite([
+					("genesis_head", Value::from_bytes(&self.genesis_head)),
+					("validation_code", Value::from_bytes(&self.validation_code)),
+					("para_kind", Value::bool(true)),
+				]);
+				let initialize =
+					tx("ParasSudoWrapper", "sudo_schedule_para_initialize", vec![para_id, genesis]);
+				submit(&self.client, &sudo(initialize), &self.signer).await?;
+
+				// Assign an additional core to the parachain, where coretime is available
+				if self.has_call("Coretime", "assign_core") {
+					let core = self.cores().await?;
+					status.update(&format!("Assigning core {core} via `Coretime::assign_core`..."));
+					let cores =
+						tx("Configuration", "set_coretime_cores", vec![Value::u128(core + 1)]);
+					submit(&self.client, &sudo(cores), &self.signer).await?;
+					let assignment = Value::unnamed_composite([
+						Value::unnamed_variant("Task", [Value::u128(self.para_id.into())]),
+						Value::u128(CORE_PARTS),
+					]);
+					let assign = tx(
+						"Coretime",
+						"assign_core",
+						vec![
+							Value::u128(core),
+							Value::u128(0),
+							Value::unnamed_composite([assignment]),
+							Value::unnamed_variant("None", []),
+						],
+					);
+					submit(&self.client, &sudo(assign), &self.signer).await?;
+				}
+			},
+			RegistrationMethod::Registrar => {
+				let key = vec![Value::u128(self.para_id.into())];
+				let reserved = self.fetch(storage("Registrar", "Paras", key)).await?;
+				// The manager is the first field of the registration info
+				if !reserved.is_some_and(|info| info.starts_with(&self.signer.public_key().0)) {
+					let next = self
+						.fetch(storage("Registrar", "NextFreeParaId", vec![]))
+						.await?
+						.map(|id| u32::decode(&mut id.as_slice()))
+						.transpose()
+						.map_err(subxt::Error::from)?
+						.unwrap_or_default();
+					if next != self.para_id {
+						return Err(Error::RegistrationError(format!(
+							"parachain {} is not reserved by the signer and the next identifier to be reserved is {next}",
+							self.para_id
+						)));
+					}
+					status.update("Reserving identifier via `Registrar::reserve`...");
+					submit(
+						&self.client,
+						&tx("Registrar", "reserve", Vec::<Value>::new()),
+						&self.signer,
+					)
+					.await?;
+				}
+				status.update("Registering via `Registrar::register`...");
+				let register = tx(
+					"Registrar",
+					"register",
+					vec![
+						para_id,
+						Value::from_bytes(&self.genesis_head),
+						Value::from_bytes(&self.validation_code),
+					],
+				);
+				submit(&self.client, &register, &self.signer).await?;
+			},
+		}
+		Ok(method)
+	}
+
+	/// Waits until the parachain has been onboarded, as per the lifecycle expected from the
+	/// registration method. Onboarding typically takes two sessions.
+	///
+	/// # Arguments
+	/// * `method` - The method used to register the parachain.
+	/// * `timeout` - The maximum time to wait.
+	/// * `status` - Used to observe status updates.
+	pub async fn wait_until_onboarded(
+		&self,
+		method: RegistrationMethod,
+		timeout: Duration,
+		status: &impl Status,
+	) -> Result<(), Error> {
+		let description = format!("parachain {} to be onboarded", self.para_id);
+		let deadline = Instant::now() + timeout;
+		let mut blocks = self.client.blocks().subscribe_best().await?;
+		loop {
+			let block = timeout_at(deadline, blocks.next())
+				.await
+				.map_err(|_| Error::Timeout(description.clone()))?
+				.ok_or_else(|| Error::RegistrationError("the block subscription ended".into()))??;
+			let lifecycle = self
+				.lifecycle_at(block.storage())
+				.await?
+				.unwrap_or_else(|| "Unregistered".into());
+			status.update(&format!("Block {}: {lifecycle}...", block.number()));
+			if lifecycle == method.onboarded() {
+				return Ok(());
+			}
+		}
+	}
+
+	/// Places an on-demand order for the parachain to produce a block, via
+	/// `OnDemand::place_order_allow_death` (`OnDemandAssignmentProvider` on older relay chains).
+	/// Only applicable to parachains onboarded as on-demand parachains (parathreads).
+	///
+	/// # Arguments
+	/// * `max_amount` - The maximum amount to pay for the order.
+	/// * `status` - Used to observe status updates.
+	pub async fn place_order(&self, max_amount: u128, status: &impl Status) -> Result<(), Error> {
+		let pallet = ["OnDemand", "OnDemandAssignmentProvider"]
+			.into_iter()
+			.find(|pallet| self.has_call(pallet, "place_order_allow_death"))
+			.ok_or_else(|| {
+				Error::RegistrationError("the relay chain does not support on-demand orders".into())
+			})?;
+		status.update(&format!("Placing an order via `{pallet}::place_order_allow_death`..."));
+		let order = tx(
+			pallet,
+			"place_order_allow_death",
+			vec![Value::u128(max_amount), Value::u128(self.para_id.into())],
+		);
+		submit(&self.client, &order, &self.signer).await?;
+		Ok(())
+	}
+
+	/// The current lifecycle of the parachain, if registered.
+	pub async fn lifecycle(&self) -> Result<Option<String>, Error> {
+		self.lifecycle_at(self.client.storage().at_latest().await?).await
+	}
+
+	/// The lifecycle of the parachain at a particular block, if registered.
+	///
+	/// # Arguments
+	/// * `at` - The storage of the relay chain at the block.
+	async fn lifecycle_at(
+		&self,
+		at: Storage<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+	) -> Result<Option<String>, Error> {
+		let key = vec![Value::u128(self.para_id.into())];
+		let Some(lifecycle) = at.fetch(&storage("Paras", "ParaLifecycles", key)).await? else {
+			return Ok(None);
+		};
+		match lifecycle.to_value().map_err(subxt::Error::from)?.value {
+			ValueDef::Variant(variant) => Ok(Some(variant.name)),
+			_ => Err(Error::RegistrationError("unexpected parachain lifecycle".into())),
+		}
+	}
+
+	/// The number of cores currently configured on the relay chain.
+	async fn cores(&self) -> Result<u128, Error> {
+		let config = storage("Configuration", "ActiveConfig", vec![]);
+		let config = self.client.storage().at_latest().await?.fetch_or_default(&config).await?;
+		let config = config.to_value().map_err(subxt::Error::from)?;
+		config
+			.at("scheduler_params")
+			.and_then(|params| params.at("num_cores"))
+			.or_else(|| config.at("coretime_cores"))
+			.and_then(|cores| cores.as_u128())
+			.ok_or_else(|| {
+				Error::RegistrationError("the number of cores could not be determined".into())
+			})
+	}
+
+	/// Whether the specified call is available on the relay chain.
+	///
+	/// # Arguments
+	/// * `pallet` - The name of the pallet.
+	/// * `call` - The name of the call.
+	fn has_call(&self, pallet: &str, call: &str) -> bool {
+		self.client
+			.metadata()
+			.pallet_by_name(pallet)
+			.is_some_and(|p| p.call_variant_by_name(call).is_some())
+	}
+
+	/// Fetches the encoded value of a storage item at the latest block, if present.
+	///
+	/// # Arguments
+	/// * `address` - The address of the storage item.
+	async fn fetch(
+		&self,
+		address: subxt::storage::DynamicAddress<Vec<Value>>,
+	) -> Result<Option<Vec<u8>>, Error> {
+		let value = self.client.storage().at_latest().await?.fetch(&address).await?;
+		Ok(value.map(|v| v.encoded().to_vec()))
+	}
+}
+
+/// Reads a genesis artifact generated via `pop build spec`, which are hex-encoded, or the raw
+/// artifact otherwise.
+///
+/// # Arguments
+/// * `path` - The path to the artifact.
+pub fn read_genesis_artifact(path: &Path) -> Result<Vec<u8>, Error> {
+	let contents = read(path)?;
+	match std::str::from_utf8(&contents).ok().and_then(|c| c.trim().strip_prefix("0x")) {
+		Some(hex) => hex::decode(hex).map_err(|e| {
+			Error::Config(format!("invalid genesis artifact {}: {e}", path.display()))
+		}),
+		None => Ok(contents),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use std::fs::write;
+	use tempfile::tempdir;
+
+	#[test]
+	fn read_genesis_artifact_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let hex = temp_dir.path().join("para-2000-genesis-state");
+		write(&hex, "0x00010203\n")?;
+		assert_eq!(read_genesis_artifact(&hex)?, [0, 1, 2, 3]);
+		let raw = temp_dir.path().join("para-2000.wasm");
+		write(&raw, [0, 97, 115, 109])?;
+		assert_eq!(read_genesis_artifact(&raw)?, [0, 97, 115, 109]);
+		write(&hex, "0xinvalid")?;
+		assert!(matches!(read_genesis_artifact(&hex), Err(Error::Config(..))));
+		Ok(())
+	}
+
+	#[test]
+	fn onboarded_works() {
+		assert_eq!(RegistrationMethod::Sudo.onboarded(), "Parachain");
+		assert_eq!(RegistrationMethod::Registrar.onboarded(), "Parathread");
+	}
+}
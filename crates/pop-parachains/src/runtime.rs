@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::errors::Error;
+use duct::cmd;
+use pop_common::{manifest::from_path, Profile};
+use serde_json::Value;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+use subxt::{dynamic::storage, ext::scale_value::At, OnlineClient, PolkadotConfig};
+use url::Url;
+
+/// The maximum code size of a parachain on Polkadot, Kusama and their testnets (3 MiB), used when
+/// the configuration of the relay chain is not known.
+pub const DEFAULT_MAX_CODE_SIZE: u32 = 3 * 1024 * 1024;
+/// The directory of the runtime crate within a project, by convention.
+const RUNTIME_DIR: &str = "runtime";
+/// The build dependency of a crate which builds a runtime.
+const WASM_BUILDER: &str = "substrate-wasm-builder";
+
+/// The runtime crate of a project.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Runtime {
+	/// The name of the package.
+	pub package: String,
+	/// The path to the crate.
+	pub path: PathBuf,
+}
+
+impl Runtime {
+	/// Locates the runtime of a project: the workspace member which builds a runtime via
+	/// `substrate-wasm-builder`, as per the workspace metadata, or otherwise the crate within
+	/// `runtime/` by convention.
+	///
+	/// # Arguments
+	/// * `project` - The path to the project.
+	/// * `package` - The package of the runtime, when a project contains multiple runtimes.
+	pub fn locate(project: &Path, package: Option<&str>) -> Result<Runtime, Error> {
+		// The convention is used when the workspace metadata is unavailable
+		let packages = cmd("cargo", ["metadata", "--format-version", "1", "--no-deps"])
+			.dir(project)
+			.stderr_null()
+			.read()
+			.ok()
+			.and_then(|metadata| serde_json::from_str(&metadata).ok())
+			.map(|metadata| workspace_packages(&metadata))
+			.unwrap_or_default();
+		if let Some(package) = package {
+			return packages
+				.into_iter()
+				.map(|(runtime, _)| runtime)
+				.find(|r| r.package == package)
+				.ok_or_else(|| Error::Config(format!("no package named `{package}` was found")));
+		}
+
+		let convention = project.join(RUNTIME_DIR);
+		let mut runtimes: Vec<_> = packages
+			.into_iter()
+			.filter_map(|(runtime, wasm)| wasm.then_some(runtime))
+			.collect();
+		match runtimes.len() {
+			0 => {
+				let manifest = from_path(Some(&convention))?;
+				let package = manifest.package.map(|p| p.name).ok_or_else(|| {
+					Error::Config(format!("no runtime package was found within {RUNTIME_DIR}/"))
+				})?;
+				Ok(Runtime { package, path: convention })
+			},
+			1 => Ok(runtimes.remove(0)),
+			_ => {
+				let convention = convention.canonicalize().ok();
+				match runtimes.iter().position(|r| Some(&r.path) == convention.as_ref()) {
+					Some(i) => Ok(runtimes.remove(i)),
+					None => Err(Error::Config(format!(
+						"multiple runtimes were found ({}), please specify one",
+						runtimes.iter().map(|r| r.package.as_str()).collect::<Vec<_>>().join(", ")
+					))),
+				}
+			},
+		}
+	}
+
+	/// Builds only the runtime, returning the resulting artifacts.
+	///
+	/// # Arguments
+	/// * `project` - The path to the project.
+	/// * `profile` - The build profile.
+	pub fn build(&self, project: &Path, profile: &Profile) -> Result<RuntimeArtifacts, Error> {
+		let mut args = vec!["build", "--package", &self.package];
+		if matches!(profile, Profile::Release) {
+			args.push("--release");
+		}
+		cmd("cargo", args).dir(project).run()?;
+		RuntimeArtifacts::locate(&profile.target_directory(project), &self.package)
+	}
+}
+
+/// The artifacts of a runtime, as output by `substrate-wasm-builder`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuntimeArtifacts {
+	/// The compressed runtime, as used on-chain.
+	pub compressed: PathBuf,
+	/// The size of the compressed runtime, in bytes.
+	pub compressed_size: u64,
+	/// The uncompressed (compact) runtime.
+	pub uncompressed: PathBuf,
+	/// The size of the uncompressed runtime, in bytes.
+	pub uncompressed_size: u64,
+}
+
+impl RuntimeArtifacts {
+	/// Locates the artifacts of a runtime.
+	///
+	/// # Arguments
+	/// * `target` - The target directory of the build profile, such as `target/release`.
+	/// * `package` - The package of the runtime.
+	pub fn locate(target: &Path, package: &str) -> Result<Self, Error> {
+		let compressed = compressed_runtime(target, package)?;
+		let uncompressed =
+			compressed.with_file_name(format!("{}.compact.wasm", file_stem(package)));
+		if !uncompressed.exists() {
+			return Err(Error::Config(format!(
+				"no runtime was found at {}",
+				uncompressed.display()
+			)));
+		}
+		Ok(Self {
+			compressed_size: fs::metadata(&compressed)?.len(),
+			compressed,
+			uncompressed_size: fs::metadata(&uncompressed)?.len(),
+			uncompressed,
+		})
+	}
+}
+
+/// The maximum code size of a parachain accepted by a running relay chain, as per its active
+/// configuration.
+///
+/// # Arguments
+/// * `url` - The websocket endpoint of a node of the relay chain.
+pub async fn max_code_size(url: &Url) -> Result<u32, Error> {
+	let client = OnlineClient::<PolkadotConfig>::from_insecure_url(url.as_str()).await?;
+	let config = storage("Configuration", "ActiveConfig", vec![]);
+	let config = client.storage().at_latest().await?.fetch_or_default(&config).await?;
+	let config = config.to_value().map_err(subxt::Error::from)?;
+	config
+		.at("max_code_size")
+		.and_then(|size| size.as_u128())
+		.and_then(|size| u32::try_from(size).ok())
+		.ok_or_else(|| {
+			Error::Config("the maximum code size of the relay chain could not be determined".into())
+		})
+}
+
+/// Locates the compressed runtime of a package, as output by `substrate-wasm-builder`.
+///
+/// # Arguments
+/// * `target` - The target directory of the build profile, such as `target/release`.
+/// * `package` - The package of the runtime.
+pub(crate) fn compressed_runtime(target: &Path, package: &str) -> Result<PathBuf, Error> {
+	let path = target
+		.join("wbuild")
+		.join(package)
+		.join(format!("{}.compact.compressed.wasm", file_stem(package)));
+	match path.exists() {
+		true => Ok(path),
+		false => Err(Error::Config(format!("no runtime was found at {}", path.display()))),
+	}
+}
+
+/// The file stem of the runtimes output for a package.
+///
+/// # Arguments
+/// * `package` - The package of the runtime.
+fn file_stem(package: &str) -> String {
+	package.replace('-', "_")
+}
+
+/// The packages of a workspace, along with whether each builds a runtime.
+///
+/// # Arguments
+/// * `metadata` - The workspace metadata, as output by `cargo metadata`.
+fn workspace_packages(metadata: &Value) -> Vec<(Runtime, bool)> {
+	let Some(packages) = metadata.get("packages").and_then(|p| p.as_array()) else {
+		return vec![];
+	};
+	packages
+		.iter()
+		.filter_map(|package| {
+			let name = package.get("name")?.as_str()?;
+			let path = Path::new(package.get("manifest_path")?.as_str()?).parent()?;
+			let wasm = package.get("dependencies").and_then(|d| d.as_array()).is_some_and(|d| {
+				d.iter().any(|d| {
+					d.get("name").and_then(|n| n.as_str()) == Some(WASM_BUILDER) &&
+						d.get("kind").and_then(|k| k.as_str()) == Some("build")
+				})
+			});
+			Some((Runtime { package: name.to_string(), path: path.to_path_buf() }, wasm))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use serde_json::json;
+	use std::fs::{create_dir_all, write};
+	use tempfile::tempdir;
+
+	fn add_crate(project: &Path, dir: &str, name: &str, runtime: bool) -> Result<()> {
+		create_dir_all(project.join(dir).join("src"))?;
+		let mut manifest = format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n");
+		if runtime {
+			manifest.push_str(&format!("\n[build-dependencies]\n{WASM_BUILDER} = \"24\"\n"));
+		}
+		write(project.join(dir).join("Cargo.toml"), manifest)?;
+		write(project.join(dir).join("src/lib.rs"), "")?;
+		Ok(())
+	}
+
+	#[test]
+	fn locate_works_by_convention() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let project = temp_dir.path();
+		assert!(matches!(Runtime::locate(project, None), Err(Error::CommonError(_))));
+		add_crate(project, "runtime", "parachain-template-runtime", false)?;
+		assert_eq!(
+			Runtime::locate(project, None)?,
+			Runtime { package: "parachain-template-runtime".into(), path: project.join("runtime") }
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn locate_works_via_workspace_metadata() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let project = temp_dir.path().canonicalize()?;
+		write(
+			project.join("Cargo.toml"),
+			"[workspace]\nmembers = [\"node\", \"runtimes/*\"]\nresolver = \"2\"\n",
+		)?;
+		add_crate(&project, "node", "pop-node", false)?;
+		add_crate(&project, "runtimes/devnet", "devnet-runtime", true)?;
+		let devnet =
+			Runtime { package: "devnet-runtime".into(), path: project.join("runtimes/devnet") };
+		assert_eq!(Runtime::locate(&project, None)?, devnet);
+
+		add_crate(&project, "runtimes/mainnet", "mainnet-runtime", true)?;
+		assert!(matches!(
+			Runtime::locate(&project, None),
+			Err(Error::Config(e)) if e == "multiple runtimes were found (devnet-runtime, mainnet-runtime), please specify one"
+		));
+		assert_eq!(Runtime::locate(&project, Some("devnet-runtime"))?, devnet);
+		assert!(matches!(
+			Runtime::locate(&project, Some("testnet-runtime")),
+			Err(Error::Config(e)) if e == "no package named `testnet-runtime` was found"
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn runtime_artifacts_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let target = temp_dir.path();
+		assert!(matches!(
+			RuntimeArtifacts::locate(target, "parachain-template-runtime"),
+			Err(Error::Config(e)) if e.starts_with("no runtime was found")
+		));
+		let wbuild = target.join("wbuild/parachain-template-runtime");
+		create_dir_all(&wbuild)?;
+		let compressed = wbuild.join("parachain_template_runtime.compact.compressed.wasm");
+		write(&compressed, [0u8; 2])?;
+		assert_eq!(compressed_runtime(target, "parachain-template-runtime")?, compressed);
+		let uncompressed = wbuild.join("parachain_template_runtime.compact.wasm");
+		write(&uncompressed, [0u8; 5])?;
+		assert_eq!(
+			RuntimeArtifacts::locate(target, "parachain-template-runtime")?,
+			RuntimeArtifacts { compressed, compressed_size: 2, uncompressed, uncompressed_size: 5 }
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn workspace_packages_works() {
+		let metadata = json!({
+			"packages": [
+				{
+					"name": "pop-node",
+					"manifest_path": "/pop/node/Cargo.toml",
+					"dependencies": [{ "name": WASM_BUILDER, "kind": null }]
+				},
+				{
+					"name": "pop-runtime",
+					"manifest_path": "/pop/runtime/Cargo.toml",
+					"dependencies": [{ "name": WASM_BUILDER, "kind": "build" }]
+				}
+			]
+		});
+		assert_eq!(
+			workspace_packages(&metadata),
+			vec![
+				(Runtime { package: "pop-node".into(), path: PathBuf::from("/pop/node") }, false),
+				(
+					Runtime { package: "pop-runtime".into(), path: PathBuf::from("/pop/runtime") },
+					true
+				),
+			]
+		);
+		assert!(workspace_packages(&json!({})).is_empty());
+	}
+}
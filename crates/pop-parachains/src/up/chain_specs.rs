@@ -22,7 +22,8 @@ pub(super) enum Runtime {
 		Repository = "https://github.com/r0gue-io/polkadot-runtimes",
 		Binary = "chain-spec-generator",
 		Chain = "kusama-local",
-		Fallback = "v1.2.7"
+		Fallback = "v1.2.7",
+		Checksum = "sha256"
 	))]
 	Kusama,
 	/// Paseo.
@@ -30,7 +31,8 @@ pub(super) enum Runtime {
 		Repository = "https://github.com/r0gue-io/paseo-runtimes",
 		Binary = "chain-spec-generator",
 		Chain = "paseo-local",
-		Fallback = "v1.2.6"
+		Fallback = "v1.2.6",
+		Checksum = "sha256"
 	))]
 	Paseo,
 	/// Polkadot.
@@ -38,7 +40,8 @@ pub(super) enum Runtime {
 		Repository = "https://github.com/r0gue-io/polkadot-runtimes",
 		Binary = "chain-spec-generator",
 		Chain = "polkadot-local",
-		Fallback = "v1.2.7"
+		Fallback = "v1.2.7",
+		Checksum = "sha256"
 	))]
 	Polkadot,
 }
@@ -61,6 +64,7 @@ impl TryInto for &Runtime {
 			tag_format: self.tag_format().map(|t| t.into()),
 			archive: format!("{binary}-{}.tar.gz", target()?),
 			contents: vec![(binary, Some(format!("{name}-{binary}")))],
+			checksum: self.checksum(),
 			latest,
 		}))
 	}
@@ -68,7 +72,7 @@ impl TryInto for &Runtime {
 
 impl Runtime {
 	/// The chain spec identifier.
-	fn chain(&self) -> &'static str {
+	pub(super) fn chain(&self) -> &'static str {
 		self.get_str("Chain").expect("expected specification of `Chain`")
 	}
 
@@ -106,6 +110,7 @@ pub(super) async fn chain_spec_generator(
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use pop_common::sourcing::Checksum;
 	use tempfile::tempdir;
 
 	#[tokio::test]
@@ -125,6 +130,7 @@ mod tests {
 					tag_format: None,
 					archive: format!("chain-spec-generator-{}.tar.gz", target()?),
 					contents: ["chain-spec-generator"].map(|b| (b, Some(format!("kusama-{b}").to_string()))).to_vec(),
+					checksum: Some(Checksum::Sha256File),
 					latest: binary.latest().map(|l| l.to_string()),
 				}) &&
 				cache == temp_dir.path()
@@ -149,6 +155,7 @@ mod tests {
 					tag_format: None,
 					archive: format!("chain-spec-generator-{}.tar.gz", target()?),
 					contents: ["chain-spec-generator"].map(|b| (b, Some(format!("paseo-{b}").to_string()))).to_vec(),
+					checksum: Some(Checksum::Sha256File),
 					latest: binary.latest().map(|l| l.to_string()),
 				}) &&
 				cache == temp_dir.path()
@@ -173,6 +180,7 @@ mod tests {
 					tag_format: None,
 					archive: format!("chain-spec-generator-{}.tar.gz", target()?),
 					contents: ["chain-spec-generator"].map(|b| (b, Some(format!("polkadot-{b}").to_string()))).to_vec(),
+					checksum: Some(Checksum::Sha256File),
 					latest: binary.latest().map(|l| l.to_string()),
 				}) &&
 				cache == temp_dir.path()
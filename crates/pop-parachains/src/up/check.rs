@@ -0,0 +1,567 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{
+	chain_specs::Runtime, hooks::Hooks, parachains::Parachain, relay::RelayChain,
+	NetworkConfiguration, Repository,
+};
+use crate::errors::Error;
+use pop_common::sourcing::traits::Source as _;
+use std::{
+	collections::HashMap,
+	fmt::{Display, Formatter},
+	ops::Range,
+	path::Path,
+};
+use strum::VariantArray as _;
+use toml_edit::{ImDocument, Item, Table};
+
+/// The relay chains supported natively by the relay chain binary.
+const RELAY_CHAINS: [&str; 2] = ["rococo-local", "westend-local"];
+/// The decorators which can be forced on a parachain.
+const FORCE_DECORATORS: [&str; 1] = ["generic-evm"];
+
+/// A problem identified within a network configuration file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Problem {
+	/// The line and column of the problem, each starting from 1. Only known for TOML network
+	/// configuration files.
+	pub position: Option<(usize, usize)>,
+	/// A description of the problem.
+	pub message: String,
+}
+
+impl Display for Problem {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self.position {
+			Some((line, column)) => write!(f, "{line}:{column}: {}", self.message),
+			None => write!(f, "{}", self.message),
+		}
+	}
+}
+
+/// Statically validates a network configuration file, without sourcing any binaries, returning
+/// all problems identified. Files in the zombienet JSON/YAML format are converted to TOML before
+/// being checked, so the positions of any problems are omitted.
+///
+/// # Arguments
+/// * `file` - The network configuration file.
+/// * `parachains` - The parachain(s) specified as arguments, which may provide commands.
+pub fn check(file: &Path, parachains: Option<&Vec<String>>) -> Result<Vec<Problem>, Error> {
+	if !file.exists() {
+		return Err(Error::Config(format!("The {file:?} configuration file was not found")));
+	}
+	let contents = std::fs::read_to_string(file)?;
+	let (contents, toml) = match file.extension().and_then(|e| e.to_str()) {
+		Some("json") =>
+			(NetworkConfiguration::document(serde_json::from_str(&contents)?)?.to_string(), false),
+		Some("yaml" | "yml") =>
+			(NetworkConfiguration::document(serde_yaml::from_str(&contents)?)?.to_string(), false),
+		_ => (contents, true),
+	};
+	let packages = match parachains {
+		Some(parachains) => parachains
+			.iter()
+			.map(|url| Repository::parse(url).map(|r| r.package))
+			.collect::<Result<Vec<_>, _>>()?,
+		None => vec![],
+	};
+	let mut checker = Checker { contents: &contents, toml, packages, problems: vec![] };
+	match ImDocument::parse(contents.as_str()) {
+		Ok(document) => checker.check(document.as_table()),
+		Err(e) => checker.report(e.span(), e.message()),
+	}
+	Ok(checker.problems)
+}
+
+/// Checks a network configuration, accumulating any problems.
+struct Checker<'a> {
+	/// The contents of the network configuration file, used to resolve positions.
+	contents: &'a str,
+	/// Whether the contents are those of the network configuration file, rather than converted
+	/// to TOML, and can therefore be used to resolve positions.
+	toml: bool,
+	/// The packages of any parachains specified as arguments.
+	packages: Vec<String>,
+	/// The problems identified.
+	problems: Vec<Problem>,
+}
+
+impl Checker<'_> {
+	fn check(&mut self, config: &Table) {
+		// Relay chain
+		match config.get("relaychain") {
+			Some(Item::Table(relay_chain)) => self.relay_chain(relay_chain),
+			Some(item) => self.report(span(item), "expected `relaychain` to be a table"),
+			None => self.report(Some(0..0), "expected `relaychain`"),
+		}
+		let mut names = HashMap::new();
+		if let Some(nodes) = config.get("relaychain").and_then(|r| r.get("nodes")) {
+			self.names(nodes, &mut names);
+		}
+
+		// Parachains
+		let mut ids = HashMap::new();
+		match config.get("parachains") {
+			Some(Item::ArrayOfTables(parachains)) =>
+				for parachain in parachains {
+					self.parachain(parachain, &mut ids);
+					if let Some(collators) = parachain.get("collators") {
+						self.names(collators, &mut names);
+					}
+				},
+			Some(item) => self.report(span(item), "expected `parachains` to be an array of tables"),
+			None => {},
+		}
+
+		// HRMP channels
+		match config.get("hrmp_channels") {
+			Some(Item::ArrayOfTables(channels)) =>
+				for channel in channels {
+					self.hrmp_channel(channel, &ids);
+				},
+			Some(Item::Value(value)) if value.as_array().is_some_and(|a| a.is_empty()) => {},
+			Some(item) =>
+				self.report(span(item), "expected `hrmp_channels` to be an array of tables"),
+			None => {},
+		}
+
+		// Hooks
+		if let Some(item) = config.get("pop").and_then(|p| p.get("hooks")) {
+			let hooks = item
+				.as_table()
+				.ok_or_else(|| Error::Config("expected `pop.hooks` to be a table".into()))
+				.and_then(Hooks::from);
+			match hooks {
+				Ok(hooks) =>
+					for id in hooks.on_parachain_producing.keys().filter(|id| !ids.contains_key(id))
+					{
+						self.report(
+							span(item),
+							format!("the `on_parachain_producing` hook references unknown parachain {id}"),
+						);
+					},
+				Err(Error::Config(message)) => self.report(span(item), message),
+				Err(e) => self.report(span(item), e.to_string()),
+			}
+		}
+	}
+
+	fn relay_chain(&mut self, relay_chain: &Table) {
+		let custom_chain_spec = ["chain_spec_path", "chain_spec_command"]
+			.iter()
+			.any(|k| relay_chain.contains_key(k));
+		match relay_chain.get("chain") {
+			Some(Item::Value(chain)) => match chain.as_str() {
+				Some(name) if !custom_chain_spec && !valid_relay_chain(name) => self.report(
+					chain.span(),
+					format!(
+						"unknown relay chain `{name}`: expected one of {}",
+						relay_chains().join(", ")
+					),
+				),
+				Some(_) => {},
+				None => self.report(chain.span(), "expected `chain` to be a string"),
+			},
+			Some(item) => self.report(span(item), "expected `chain` to be a string"),
+			None => {},
+		}
+		let mut commands = vec![];
+		if let Some(item) = relay_chain.get("default_command") {
+			commands.push(item);
+		}
+		match relay_chain.get("nodes") {
+			Some(Item::ArrayOfTables(nodes)) if !nodes.is_empty() =>
+				commands.extend(nodes.iter().filter_map(|n| n.get("command"))),
+			Some(item) => self.report(span(item), "expected relay chain `nodes` to be specified"),
+			None => self.report(relay_chain.span(), "expected relay chain `nodes` to be specified"),
+		}
+		for item in commands {
+			match item.as_str() {
+				Some(command) if !valid_relay_command(command) => self.report(
+					span(item),
+					format!("the relay chain command is unsupported: {command}"),
+				),
+				Some(_) => {},
+				None => self.report(span(item), "expected `command` to be a string"),
+			}
+		}
+	}
+
+	fn parachain(&mut self, parachain: &Table, ids: &mut HashMap<u32, usize>) {
+		// Identifier
+		match parachain.get("id") {
+			Some(item) => match item.as_integer().and_then(|i| u32::try_from(i).ok()) {
+				Some(id) => {
+					let position = span(item).map_or(0, |s| s.start);
+					if let Some(existing) = ids.insert(id, position) {
+						let first = self.first_specified(existing);
+						self.report(span(item), format!("duplicate parachain id {id}{first}"));
+					}
+				},
+				None => self.report(span(item), "expected `id` to be a parachain identifier"),
+			},
+			None => self.report(parachain.span(), "expected `parachain` to have `id`"),
+		}
+
+		// Collators
+		let collators = match parachain.get("collators") {
+			Some(Item::ArrayOfTables(collators)) => collators.iter().collect(),
+			Some(item) => {
+				self.report(span(item), "expected `collators` to be an array of tables");
+				vec![]
+			},
+			None => parachain.get("collator").and_then(|c| c.as_table()).into_iter().collect(),
+		};
+		if collators.is_empty() {
+			self.report(parachain.span(), "expected parachain to specify at least one collator");
+		}
+
+		// Commands
+		let mut commands: Vec<_> = collators.iter().filter_map(|c| c.get("command")).collect();
+		if let Some(item) = parachain.get("default_command") {
+			commands.insert(0, item);
+		}
+		for item in commands {
+			match item.as_str() {
+				Some(command) if !self.valid_parachain_command(command) => self.report(
+					span(item),
+					format!("the `{command}` command cannot be resolved to a source. Are you missing a `--parachain` argument?"),
+				),
+				Some(_) => {},
+				None => self.report(span(item), "expected `command` to be a string"),
+			}
+		}
+
+		// Decorator
+		if let Some(item) = parachain.get("force_decorator") {
+			match item.as_str() {
+				Some(decorator) if !FORCE_DECORATORS.contains(&decorator) => self.report(
+					span(item),
+					format!(
+						"unknown `force_decorator` `{decorator}`: expected one of {}",
+						FORCE_DECORATORS.join(", ")
+					),
+				),
+				Some(_) => {},
+				None => self.report(span(item), "expected `force_decorator` to be a string"),
+			}
+		}
+	}
+
+	fn hrmp_channel(&mut self, channel: &Table, ids: &HashMap<u32, usize>) {
+		let mut para_ids = vec![];
+		for key in ["sender", "recipient"] {
+			match channel.get(key) {
+				Some(item) => match item.as_integer().and_then(|i| u32::try_from(i).ok()) {
+					Some(id) if !ids.contains_key(&id) => self.report(
+						span(item),
+						format!("the HRMP channel references unknown parachain {id}"),
+					),
+					Some(id) => para_ids.push(id),
+					None => self.report(
+						span(item),
+						format!("expected `{key}` to be a parachain identifier"),
+					),
+				},
+				None => self.report(
+					channel.span(),
+					format!("expected HRMP channel to have a `{key}` parachain"),
+				),
+			}
+		}
+		if let [sender, recipient] = para_ids[..] {
+			if sender == recipient {
+				self.report(
+					channel.span(),
+					"expected HRMP channel to be between different parachains",
+				);
+			}
+		}
+	}
+
+	/// Checks that node names are unique across the network.
+	fn names(&mut self, nodes: &Item, names: &mut HashMap<String, usize>) {
+		let Some(nodes) = nodes.as_array_of_tables() else {
+			return;
+		};
+		for node in nodes {
+			match node.get("name") {
+				Some(item) => match item.as_str() {
+					Some(name) => {
+						let position = span(item).map_or(0, |s| s.start);
+						if let Some(existing) = names.insert(name.to_string(), position) {
+							let first = self.first_specified(existing);
+							self.report(span(item), format!("duplicate node name `{name}`{first}"));
+						}
+					},
+					None => self.report(span(item), "expected `name` to be a string"),
+				},
+				None => self.report(node.span(), "expected node to have a `name`"),
+			}
+		}
+	}
+
+	/// Whether a parachain command can be resolved to a source.
+	fn valid_parachain_command(&self, command: &str) -> bool {
+		let command = command.to_lowercase();
+		Parachain::VARIANTS.iter().any(|p| p.binary() == command) ||
+			self.packages.contains(&command) ||
+			["./", "../", "/"].iter().any(|p| command.starts_with(p))
+	}
+
+	/// Records a problem at the specified location.
+	fn report(&mut self, span: Option<Range<usize>>, message: impl Into<String>) {
+		let position = self.position(span.map_or(0, |s| s.start));
+		self.problems.push(Problem { position, message: message.into() });
+	}
+
+	/// Describes where a duplicated value was first specified, if known.
+	fn first_specified(&self, offset: usize) -> String {
+		self.position(offset)
+			.map(|(line, _)| format!(", first specified on line {line}"))
+			.unwrap_or_default()
+	}
+
+	/// Resolves the line and column of an offset within the network configuration file, if the
+	/// file is TOML.
+	fn position(&self, offset: usize) -> Option<(usize, usize)> {
+		if !self.toml {
+			return None;
+		}
+		let offset = offset.min(self.contents.len());
+		let preceding = &self.contents[..offset];
+		let line = preceding.matches('\n').count() + 1;
+		let column = preceding.rfind('\n').map_or(offset, |i| offset - i - 1) + 1;
+		Some((line, column))
+	}
+}
+
+/// The location of an item within a network configuration file.
+fn span(item: &Item) -> Option<Range<usize>> {
+	match item {
+		Item::Value(value) => value.span(),
+		Item::Table(table) => table.span(),
+		Item::ArrayOfTables(tables) => tables.span(),
+		Item::None => None,
+	}
+}
+
+/// The relay chains which can be launched.
+pub(super) fn relay_chains() -> Vec<&'static str> {
+	RELAY_CHAINS
+		.into_iter()
+		.chain(Runtime::VARIANTS.iter().map(|r| r.chain()))
+		.collect()
+}
+
+/// Whether a relay chain can be launched.
+pub(super) fn valid_relay_chain(chain: &str) -> bool {
+	let chain = chain.to_lowercase();
+	RELAY_CHAINS.contains(&chain.as_str()) ||
+		Runtime::VARIANTS.iter().any(|r| chain.ends_with(r.chain()))
+}
+
+/// Whether a relay chain command can be resolved to a source.
+fn valid_relay_command(command: &str) -> bool {
+	RelayChain::VARIANTS
+		.iter()
+		.any(|r| command.to_lowercase().ends_with(r.binary()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use std::io::Write;
+	use tempfile::Builder;
+
+	fn check_str(contents: &str, parachains: Option<&Vec<String>>) -> Result<Vec<String>> {
+		let config = Builder::new().suffix(".toml").tempfile()?;
+		write!(config.as_file(), "{contents}")?;
+		Ok(check(config.path(), parachains)?.iter().map(|p| p.to_string()).collect())
+	}
+
+	#[test]
+	fn check_fails_when_missing() {
+		assert!(matches!(check(Path::new("missing.toml"), None), Err(Error::Config(..))));
+	}
+
+	#[test]
+	fn check_fails_when_malformed_json() -> Result<()> {
+		let config = Builder::new().suffix(".json").tempfile()?;
+		assert!(matches!(check(config.path(), None), Err(Error::JsonError(..))));
+		Ok(())
+	}
+
+	#[test]
+	fn check_supports_json_and_yaml() -> Result<()> {
+		let config = Builder::new().suffix(".json").tempfile()?;
+		write!(
+			config.as_file(),
+			r#"{{
+	"relaychain": {{ "chain": "paseo-local", "nodes": [{{ "name": "alice" }}] }},
+	"parachains": [
+		{{ "id": 2000, "collators": [{{ "name": "alice", "command": "pop-node" }}] }},
+		{{ "id": 2000, "collators": [{{ "name": "bob", "command": "unknown" }}] }}
+	]
+}}"#
+		)?;
+		let problems: Vec<_> = check(config.path(), None)?.iter().map(|p| p.to_string()).collect();
+		assert_eq!(problems.len(), 3, "{problems:?}");
+		assert!(problems.contains(&"duplicate parachain id 2000".to_string()));
+		assert!(problems.contains(&"duplicate node name `alice`".to_string()));
+
+		let config = Builder::new().suffix(".yaml").tempfile()?;
+		write!(
+			config.as_file(),
+			"relaychain:\n  chain: paseo-local\n  nodes:\n    - name: alice\nparachains:\n  - id: 2000\n    collators:\n      - name: collator\n        command: pop-node\n"
+		)?;
+		assert_eq!(check(config.path(), None)?, vec![]);
+		Ok(())
+	}
+
+	#[test]
+	fn check_accepts_valid_config() -> Result<()> {
+		let problems = check_str(
+			r#"[relaychain]
+chain = "paseo-local"
+default_command = "polkadot"
+
+[[relaychain.nodes]]
+name = "alice"
+
+[[parachains]]
+id = 1000
+chain = "asset-hub-paseo-local"
+
+[[parachains.collators]]
+name = "asset-hub"
+command = "polkadot-parachain"
+
+[[parachains]]
+id = 2000
+default_command = "pop-node"
+
+[parachains.collator]
+name = "pop"
+
+[[parachains]]
+id = 2001
+default_command = "parachain-template-node"
+force_decorator = "generic-evm"
+
+[[parachains.collators]]
+name = "collator"
+
+[[hrmp_channels]]
+sender = 2000
+recipient = 2001
+
+[pop.hooks]
+on_ready = "./seed.sh"
+on_parachain_producing = { 2000 = "./seed-pop.sh" }
+"#,
+			Some(&vec!["https://github.com/org/parachain-template-node".to_string()]),
+		)?;
+		assert!(problems.is_empty(), "{problems:?}");
+		Ok(())
+	}
+
+	#[test]
+	fn check_reports_syntax_errors() -> Result<()> {
+		let problems = check_str("[relaychain]\nchain = \n", None)?;
+		assert_eq!(problems.len(), 1);
+		assert!(problems[0].starts_with("2:"), "{problems:?}");
+		Ok(())
+	}
+
+	#[test]
+	fn check_reports_missing_relay_chain() -> Result<()> {
+		assert_eq!(check_str("", None)?, ["1:1: expected `relaychain`"]);
+		Ok(())
+	}
+
+	#[test]
+	fn check_reports_all_problems() -> Result<()> {
+		let problems = check_str(
+			r#"[relaychain]
+chain = "paseo"
+default_command = "kusama"
+
+[[relaychain.nodes]]
+name = "alice"
+
+[[parachains]]
+id = 2000
+default_command = "pop-node"
+force_decorator = "moonbeam"
+
+[[parachains.collators]]
+name = "alice"
+
+[[parachains]]
+id = 2000
+default_command = "unknown-node"
+
+[pop.hooks]
+on_parachain_producing = { 3000 = "./seed.sh" }
+"#,
+			None,
+		)?;
+		assert_eq!(
+			problems,
+			[
+				"2:9: unknown relay chain `paseo`: expected one of rococo-local, westend-local, kusama-local, paseo-local, polkadot-local",
+				"3:19: the relay chain command is unsupported: kusama",
+				"11:19: unknown `force_decorator` `moonbeam`: expected one of generic-evm",
+				"14:8: duplicate node name `alice`, first specified on line 6",
+				"17:6: duplicate parachain id 2000, first specified on line 9",
+				"16:1: expected parachain to specify at least one collator",
+				"18:19: the `unknown-node` command cannot be resolved to a source. Are you missing a `--parachain` argument?",
+				"20:1: the `on_parachain_producing` hook references unknown parachain 3000",
+			]
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn check_reports_invalid_hrmp_channels() -> Result<()> {
+		let problems = check_str(
+			r#"[relaychain]
+chain = "paseo-local"
+
+[[relaychain.nodes]]
+name = "alice"
+
+[[parachains]]
+id = 2000
+default_command = "pop-node"
+
+[[parachains.collators]]
+name = "pop"
+
+[[hrmp_channels]]
+sender = 2000
+recipient = 1000
+
+[[hrmp_channels]]
+sender = 2000
+recipient = 2000
+
+[[hrmp_channels]]
+sender = "2000"
+"#,
+			None,
+		)?;
+		assert_eq!(
+			problems,
+			[
+				"16:13: the HRMP channel references unknown parachain 1000",
+				"18:1: expected HRMP channel to be between different parachains",
+				"23:10: expected `sender` to be a parachain identifier",
+				"22:1: expected HRMP channel to have a `recipient` parachain",
+			]
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,342 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{errors::Error, ChainSpec};
+use pop_common::Status;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sp_crypto_hashing::twox_128;
+use std::{
+	collections::BTreeMap,
+	fs::{create_dir_all, read_to_string, write},
+	path::{Path, PathBuf},
+	str::FromStr,
+};
+use subxt::{
+	backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+	ext::{
+		codec::Encode,
+		futures::{stream, StreamExt, TryStreamExt},
+	},
+	utils::H256,
+	PolkadotConfig,
+};
+use subxt_signer::{sr25519::Keypair, SecretUri};
+use url::Url;
+
+/// The number of storage keys requested per page.
+const PAGE_SIZE: u32 = 1_000;
+/// The number of storage values requested concurrently.
+const CONCURRENCY: usize = 64;
+
+/// A live chain to be forked into a local network, specified as `id=url`, with the block to be
+/// forked optionally specified as #fragment (e.g. 'wss://rpc.example.io#0x1234...'). Defaults to
+/// the latest finalized block when not specified.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fork {
+	/// The identifier of the parachain within the local network.
+	pub para_id: u32,
+	/// The RPC endpoint of the live chain.
+	pub url: Url,
+	/// The hash of the block to be forked.
+	pub at: Option<H256>,
+}
+
+impl FromStr for Fork {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid = |reason: &str| Error::Config(format!("invalid fork `{s}`: {reason}"));
+		let (para_id, url) = s.split_once('=').ok_or_else(|| invalid("expected `id=url`"))?;
+		let para_id = para_id.trim().parse().map_err(|_| invalid("invalid parachain id"))?;
+		let mut url = Url::parse(url.trim()).map_err(|_| invalid("invalid url"))?;
+		let at = match url.fragment() {
+			Some(hash) => Some(parse_hash(hash).ok_or_else(|| invalid("invalid block hash"))?),
+			None => None,
+		};
+		url.set_fragment(None);
+		Ok(Fork { para_id, url, at })
+	}
+}
+
+impl Fork {
+	/// Snapshots the state of the live chain via RPC and writes it to a raw chain specification
+	/// within `dir`, returning its path. Snapshots are reused for subsequent forks of the same
+	/// block.
+	///
+	/// The forked chain is adapted to the local network: the parachain identifier is replaced,
+	/// the state of `ParachainSystem` is reset as per genesis and the Aura authorities are
+	/// replaced by the specified collators, until the next session.
+	///
+	/// # Arguments
+	/// * `relay_chain` - The relay chain of the local network.
+	/// * `collators` - The names of the collators of the parachain within the local network.
+	/// * `dir` - The directory used to store snapshots.
+	/// * `status` - Used to observe status updates.
+	pub async fn chain_spec(
+		&self,
+		relay_chain: &str,
+		collators: &[&str],
+		dir: &Path,
+		status: &impl Status,
+	) -> Result<PathBuf, Error> {
+		// Snapshot the state of the chain, unless already available
+		create_dir_all(dir)?;
+		let snapshot_path = |at: H256| dir.join(format!("{at:?}.json"));
+		let (at, snapshot) = match self.at.filter(|at| snapshot_path(*at).exists()) {
+			Some(at) => (at, serde_json::from_str(&read_to_string(snapshot_path(at))?)?),
+			None => {
+				status.update(&format!("Connecting to {}...", self.url));
+				let rpc = LegacyRpcMethods::<PolkadotConfig>::new(
+					RpcClient::from_insecure_url(self.url.as_str()).await?,
+				);
+				let at = match self.at {
+					Some(at) => at,
+					None => rpc.chain_get_finalized_head().await?,
+				};
+				let path = snapshot_path(at);
+				let snapshot = match path.exists() {
+					true => serde_json::from_str(&read_to_string(&path)?)?,
+					false => {
+						let snapshot = Snapshot::new(&rpc, at, status).await?;
+						write(&path, serde_json::to_string(&snapshot)?)?;
+						snapshot
+					},
+				};
+				(at, snapshot)
+			},
+		};
+
+		// Generate a chain specification for the local network
+		let chain_spec = snapshot.chain_spec(self.para_id, relay_chain, collators)?;
+		let path = dir.join(format!("{at:?}-{}.json", self.para_id));
+		chain_spec.to_file(&path)?;
+		Ok(path)
+	}
+}
+
+/// The state of a live chain at a particular block.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Snapshot {
+	/// The name of the chain.
+	name: String,
+	/// The properties of the chain.
+	properties: Value,
+	/// The storage of the chain, as hex-encoded keys and values.
+	storage: BTreeMap<String, String>,
+}
+
+impl Snapshot {
+	/// Snapshots the state of a live chain at the specified block.
+	///
+	/// # Arguments
+	/// * `rpc` - The RPC methods of the live chain.
+	/// * `at` - The hash of the block.
+	/// * `status` - Used to observe status updates.
+	async fn new(
+		rpc: &LegacyRpcMethods<PolkadotConfig>,
+		at: H256,
+		status: &impl Status,
+	) -> Result<Self, Error> {
+		let name = rpc.system_chain().await?;
+		let properties = serde_json::to_value(rpc.system_properties().await?)?;
+		let mut storage = BTreeMap::new();
+		let mut start_key = None;
+		loop {
+			let keys =
+				rpc.state_get_keys_paged(&[], PAGE_SIZE, start_key.as_deref(), Some(at)).await?;
+			let values: Vec<_> = stream::iter(&keys)
+				.map(|key| rpc.state_get_storage(key, Some(at)))
+				.buffered(CONCURRENCY)
+				.try_collect()
+				.await?;
+			for (key, value) in keys.iter().zip(values) {
+				if let Some(value) = value {
+					storage.insert(
+						format!("0x{}", hex::encode(key)),
+						format!("0x{}", hex::encode(value)),
+					);
+				}
+			}
+			status.update(&format!("Snapshotted {} storage items at {at:?}...", storage.len()));
+			if keys.len() < PAGE_SIZE as usize {
+				break;
+			}
+			start_key = keys.last().cloned();
+		}
+		Ok(Self { name, properties, storage })
+	}
+
+	/// Generates a raw chain specification from the snapshot, adapted to the local network.
+	///
+	/// # Arguments
+	/// * `para_id` - The identifier of the parachain within the local network.
+	/// * `relay_chain` - The relay chain of the local network.
+	/// * `collators` - The names of the collators of the parachain within the local network.
+	fn chain_spec(
+		&self,
+		para_id: u32,
+		relay_chain: &str,
+		collators: &[&str],
+	) -> Result<ChainSpec, Error> {
+		let id = format!("{}-fork", self.name.to_lowercase().replace(' ', "-"));
+		let mut chain_spec = ChainSpec::raw(
+			&format!("{} (fork)", self.name),
+			&id,
+			para_id,
+			self.properties.clone(),
+			self.storage.clone(),
+		);
+		chain_spec.replace_relay_chain(relay_chain)?;
+		chain_spec.replace_raw_storage(
+			&storage_key("ParachainInfo", "ParachainId"),
+			Some(&para_id.to_le_bytes()),
+		)?;
+		chain_spec.remove_raw_storage_prefix(&twox_128(b"ParachainSystem"))?;
+		let authorities = authorities(collators)?;
+		for pallet in ["Aura", "AuraExt"] {
+			let key = storage_key(pallet, "Authorities");
+			if self.storage.contains_key(&format!("0x{}", hex::encode(&key))) {
+				chain_spec.replace_raw_storage(&key, Some(&authorities))?;
+			}
+		}
+		Ok(chain_spec)
+	}
+}
+
+/// The SCALE-encoded Aura authorities of the specified collators, whose keys are derived from
+/// their names as per zombienet (e.g. `//Alice` for `alice`).
+///
+/// # Arguments
+/// * `collators` - The names of the collators.
+fn authorities(collators: &[&str]) -> Result<Vec<u8>, Error> {
+	let mut authorities = Vec::with_capacity(collators.len());
+	for collator in collators {
+		let mut name = collator.chars();
+		let seed = match name.next() {
+			Some(first) => format!("//{}{}", first.to_uppercase(), name.as_str()),
+			None => return Err(Error::Config("expected collator to have a name".into())),
+		};
+		let uri = SecretUri::from_str(&seed)
+			.map_err(|e| Error::Config(format!("invalid collator seed `{seed}`: {e}")))?;
+		let keypair = Keypair::from_uri(&uri)
+			.map_err(|e| Error::Config(format!("invalid collator seed `{seed}`: {e}")))?;
+		authorities.push(keypair.public_key().0);
+	}
+	Ok(authorities.encode())
+}
+
+/// The storage key of a storage value within a pallet.
+fn storage_key(pallet: &str, item: &str) -> Vec<u8> {
+	[twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()
+}
+
+/// Parses a hex-encoded block hash.
+fn parse_hash(hash: &str) -> Option<H256> {
+	let bytes = hex::decode(hash.trim_start_matches("0x")).ok()?;
+	(bytes.len() == 32).then(|| H256::from_slice(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use serde_json::json;
+	use tempfile::tempdir;
+
+	const HASH: &str = "0x2a3b1d8e1d4f6d0b2a3b1d8e1d4f6d0b2a3b1d8e1d4f6d0b2a3b1d8e1d4f6d0b";
+	// The public key of `//Alice`.
+	const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+	const PARACHAIN_ID: &str = "0x0d715f2646c8f85767b5d2764bb2782604a74d81251e398fd8a0a4d55023bb3f";
+	const AURA_AUTHORITIES: &str =
+		"0x57f8dc2f5ab09467896f47300f0424385e0621c4869aa60c02be9adcc98a0d1d";
+
+	fn snapshot() -> Snapshot {
+		Snapshot {
+			name: "Pop Network".to_string(),
+			properties: json!({"tokenSymbol": "PAS", "tokenDecimals": 10}),
+			storage: BTreeMap::from([
+				("0x3a636f6465".to_string(), "0x0102".to_string()),
+				(PARACHAIN_ID.to_string(), "0xe8030000".to_string()),
+				(AURA_AUTHORITIES.to_string(), "0x00".to_string()),
+				(format!("0x{}01", hex::encode(twox_128(b"ParachainSystem"))), "0x01".to_string()),
+			]),
+		}
+	}
+
+	#[test]
+	fn fork_from_str_works() -> Result<()> {
+		assert_eq!(
+			"4001=wss://rpc.example.io".parse::<Fork>()?,
+			Fork { para_id: 4001, url: Url::parse("wss://rpc.example.io")?, at: None }
+		);
+		assert_eq!(
+			format!("2000=ws://127.0.0.1:9944#{HASH}").parse::<Fork>()?,
+			Fork { para_id: 2000, url: Url::parse("ws://127.0.0.1:9944")?, at: parse_hash(HASH) }
+		);
+		for fork in [
+			"wss://rpc.example.io",
+			"pop=wss://rpc.example.io",
+			"2000=rpc",
+			"2000=wss://rpc.example.io#0x01",
+		] {
+			assert!(matches!(fork.parse::<Fork>(), Err(Error::Config(..))), "{fork}");
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn storage_key_works() {
+		assert_eq!(
+			format!("0x{}", hex::encode(storage_key("ParachainInfo", "ParachainId"))),
+			PARACHAIN_ID
+		);
+		assert_eq!(
+			format!("0x{}", hex::encode(storage_key("Aura", "Authorities"))),
+			AURA_AUTHORITIES
+		);
+	}
+
+	#[test]
+	fn authorities_works() -> Result<()> {
+		let authorities = authorities(&["alice", "collator-01"])?;
+		assert_eq!(authorities.len(), 1 + 2 * 32);
+		assert_eq!(authorities[0], 2 << 2);
+		assert_eq!(hex::encode(&authorities[1..33]), ALICE);
+		assert!(super::authorities(&[""]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn chain_spec_adapts_snapshot() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let path = temp_dir.path().join("chain-spec.json");
+		snapshot().chain_spec(2000, "paseo-local", &["alice"])?.to_file(&path)?;
+		let chain_spec = ChainSpec::from(&path)?;
+		assert_eq!(chain_spec.get_parachain_id(), Some(2000));
+		assert_eq!(chain_spec.get_relay_chain(), Some("paseo-local"));
+		assert_eq!(chain_spec.get_protocol_id(), Some("pop-network-fork"));
+		let chain_spec: Value = serde_json::from_str(&read_to_string(&path)?)?;
+		assert_eq!(chain_spec["name"], json!("Pop Network (fork)"));
+		assert_eq!(
+			chain_spec["genesis"]["raw"]["top"],
+			json!({
+				"0x3a636f6465": "0x0102",
+				PARACHAIN_ID: "0xd0070000",
+				AURA_AUTHORITIES: format!("0x04{ALICE}"),
+			})
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn chain_spec_reuses_snapshot() -> Result<()> {
+		let temp_dir = tempdir()?;
+		write(temp_dir.path().join(format!("{HASH}.json")), serde_json::to_string(&snapshot())?)?;
+		// The endpoint is not used, as a snapshot of the block is already available
+		let fork: Fork = format!("2000=ws://127.0.0.1:1#{HASH}").parse()?;
+		let path = fork.chain_spec("paseo-local", &["alice"], temp_dir.path(), &()).await?;
+		assert_eq!(path, temp_dir.path().join(format!("{HASH}-2000.json")));
+		assert_eq!(ChainSpec::from(&path)?.get_parachain_id(), Some(2000));
+		Ok(())
+	}
+}
@@ -0,0 +1,343 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{
+	first,
+	network::{NetworkInfo, NETWORK_JSON},
+	BEST_BLOCK_METRIC,
+};
+use crate::errors::Error;
+use duct::cmd;
+use std::collections::BTreeMap;
+use tokio::task::JoinSet;
+use toml_edit::{Item, Table};
+use zombienet_sdk::{LocalFileSystem, Network, NetworkNode};
+
+/// Commands to be run at points within the lifecycle of a network, as configured within the
+/// `[pop.hooks]` table of a network configuration file:
+///
+/// ```toml
+/// [pop.hooks]
+/// on_ready = "./scripts/seed.sh"
+/// on_shutdown = ["./scripts/report.sh", "echo done"]
+///
+/// [pop.hooks.on_block]
+/// 10 = "./scripts/e2e.sh"
+///
+/// [pop.hooks.on_parachain_producing]
+/// 2000 = "./scripts/seed-parachain.sh"
+/// ```
+///
+/// Hooks are run via `sh -c`, with the endpoints of the network provided as environment variables
+/// (see [`Hooks::env`]).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Hooks {
+	/// Commands run once the network has launched.
+	pub on_ready: Vec<String>,
+	/// Commands run once the relay chain reaches a block number, keyed by block number.
+	pub on_block: BTreeMap<u32, Vec<String>>,
+	/// Commands run once a parachain produces its first block, keyed by parachain identifier.
+	pub on_parachain_producing: BTreeMap<u32, Vec<String>>,
+	/// Commands run when the network is being torn down.
+	pub on_shutdown: Vec<String>,
+}
+
+impl Hooks {
+	/// Parses the hooks configured within a `[pop.hooks]` table.
+	///
+	/// # Arguments
+	/// * `table` - The `[pop.hooks]` table.
+	pub(crate) fn from(table: &Table) -> Result<Self, Error> {
+		let mut hooks = Hooks::default();
+		for (key, item) in table.iter() {
+			match key {
+				"on_ready" => hooks.on_ready = commands(key, item)?,
+				"on_shutdown" => hooks.on_shutdown = commands(key, item)?,
+				"on_block" => hooks.on_block = keyed(key, item)?,
+				"on_parachain_producing" => hooks.on_parachain_producing = keyed(key, item)?,
+				_ => return Err(Error::Config(format!("unknown hook `pop.hooks.{key}`"))),
+			}
+		}
+		Ok(hooks)
+	}
+
+	/// Whether no hooks are configured.
+	pub fn is_empty(&self) -> bool {
+		self.on_ready.is_empty() &&
+			self.on_block.is_empty() &&
+			self.on_parachain_producing.is_empty() &&
+			self.on_shutdown.is_empty()
+	}
+
+	/// The environment variables provided to hooks, describing the endpoints of the network:
+	/// - `POP_NETWORK_DIR`: the base directory of the network.
+	/// - `POP_NETWORK_JSON`: the description of the network (see [`NetworkInfo`]).
+	/// - `POP_RELAY_WS_URI`: the websocket endpoint of the first relay chain node.
+	/// - `POP_PARA_{ID}_WS_URI`: the websocket endpoint of the first collator of each parachain.
+	/// - `POP_NODE_{NAME}_WS_URI`: the websocket endpoint of each node, with the name of the node
+	///   uppercased and any non-alphanumeric characters replaced by `_`.
+	///
+	/// # Arguments
+	/// * `network` - The description of the network.
+	pub fn env(network: &NetworkInfo) -> Vec<(String, String)> {
+		let mut env = vec![
+			("POP_NETWORK_DIR".to_string(), network.base_dir.display().to_string()),
+			(
+				"POP_NETWORK_JSON".to_string(),
+				network.base_dir.join(NETWORK_JSON).display().to_string(),
+			),
+		];
+		if let Some(node) = network.relay_chain.nodes.first() {
+			env.push(("POP_RELAY_WS_URI".to_string(), node.ws_uri.clone()));
+		}
+		for parachain in &network.parachains {
+			if let Some(node) = parachain.collators.first() {
+				env.push((format!("POP_PARA_{}_WS_URI", parachain.para_id), node.ws_uri.clone()));
+			}
+		}
+		for node in network.nodes() {
+			let name: String = node
+				.name
+				.chars()
+				.map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+				.collect();
+			env.push((format!("POP_NODE_{name}_WS_URI"), node.ws_uri.clone()));
+		}
+		env
+	}
+
+	/// Runs the `on_ready` hooks, along with the `on_block` and `on_parachain_producing` hooks
+	/// once their conditions are observed on the network. Completes once all hooks have run,
+	/// returning the first error encountered.
+	///
+	/// # Arguments
+	/// * `network` - The running network.
+	/// * `env` - The environment variables provided to hooks.
+	pub async fn run(
+		&self,
+		network: &Network<LocalFileSystem>,
+		env: &[(String, String)],
+	) -> Result<(), Error> {
+		let mut hooks = JoinSet::new();
+		hooks.spawn(execute(self.on_ready.clone(), env.to_vec()));
+
+		// Block production is observed via the first node of each chain
+		if !self.on_block.is_empty() {
+			let node = first(network.relaychain().nodes())
+				.ok_or_else(|| Error::Config("expected relay chain nodes".into()))?;
+			for (&block, commands) in &self.on_block {
+				hooks.spawn(after_block(node.clone(), block, commands.clone(), env.to_vec()));
+			}
+		}
+		for (&id, commands) in &self.on_parachain_producing {
+			let node = network
+				.parachains()
+				.into_iter()
+				.find(|p| p.para_id() == id)
+				.and_then(|p| first(p.collators()))
+				.ok_or_else(|| Error::Config(format!("expected collators for parachain {id}")))?;
+			hooks.spawn(after_block(node.clone(), 1, commands.clone(), env.to_vec()));
+		}
+
+		let mut result = Ok(());
+		while let Some(outcome) = hooks.join_next().await {
+			let outcome = outcome.map_err(|e| Error::AnyhowError(e.into())).and_then(|r| r);
+			if result.is_ok() {
+				result = outcome;
+			}
+		}
+		result
+	}
+
+	/// Runs the `on_shutdown` hooks.
+	///
+	/// # Arguments
+	/// * `env` - The environment variables provided to hooks.
+	pub async fn shutdown(&self, env: &[(String, String)]) -> Result<(), Error> {
+		execute(self.on_shutdown.clone(), env.to_vec()).await
+	}
+}
+
+/// Waits until a node reports the specified best block before running commands.
+///
+/// # Arguments
+/// * `node` - The node to be observed.
+/// * `block` - The block number to wait for.
+/// * `commands` - The commands to be run.
+/// * `env` - The environment variables provided to the commands.
+async fn after_block(
+	node: NetworkNode,
+	block: u32,
+	commands: Vec<String>,
+	env: Vec<(String, String)>,
+) -> Result<(), Error> {
+	node.wait_metric(BEST_BLOCK_METRIC, |b| b >= block as f64).await?;
+	execute(commands, env).await
+}
+
+/// Runs commands sequentially via the shell, stopping at the first failure.
+///
+/// # Arguments
+/// * `commands` - The commands to be run.
+/// * `env` - The environment variables provided to the commands.
+async fn execute(commands: Vec<String>, env: Vec<(String, String)>) -> Result<(), Error> {
+	for command in commands {
+		let env = env.clone();
+		tokio::task::spawn_blocking(move || {
+			let mut expression = cmd("sh", ["-c", &command]);
+			for (key, value) in env {
+				expression = expression.env(key, value);
+			}
+			expression.run().map_err(|e| Error::Hook(command.clone(), e.to_string()))
+		})
+		.await
+		.map_err(|e| Error::AnyhowError(e.into()))??;
+	}
+	Ok(())
+}
+
+/// Parses a hook specified as either a single command or an array of commands.
+fn commands(key: &str, item: &Item) -> Result<Vec<String>, Error> {
+	let invalid =
+		|| Error::Config(format!("expected `{key}` hook to be a command or array of commands"));
+	if let Some(command) = item.as_str() {
+		return Ok(vec![command.to_string()]);
+	}
+	item.as_array()
+		.ok_or_else(invalid)?
+		.iter()
+		.map(|c| c.as_str().map(String::from).ok_or_else(invalid))
+		.collect()
+}
+
+/// Parses a table of hooks keyed by number.
+fn keyed(key: &str, item: &Item) -> Result<BTreeMap<u32, Vec<String>>, Error> {
+	let table = item
+		.as_table_like()
+		.ok_or_else(|| Error::Config(format!("expected `{key}` hook to be a table")))?;
+	table
+		.iter()
+		.map(|(number, item)| {
+			let number = number.parse().map_err(|_| {
+				Error::Config(format!(
+					"expected `{key}` hook to be keyed by number, found `{number}`"
+				))
+			})?;
+			Ok((number, commands(key, item)?))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::up::network::{NodeInfo, ParachainInfo, RelayChainInfo};
+	use anyhow::Result;
+	use std::{fs::read_to_string, path::PathBuf};
+	use tempfile::tempdir;
+	use toml_edit::DocumentMut;
+
+	fn hooks(toml: &str) -> Result<Hooks, Error> {
+		let document: DocumentMut = toml.parse().unwrap();
+		Hooks::from(document["pop"]["hooks"].as_table().unwrap())
+	}
+
+	#[test]
+	fn from_works() -> Result<()> {
+		let hooks = hooks(
+			r#"
+[pop.hooks]
+on_ready = "./seed.sh"
+on_shutdown = ["./report.sh", "echo done"]
+
+[pop.hooks.on_block]
+10 = "./e2e.sh"
+
+[pop.hooks.on_parachain_producing]
+2000 = ["./seed-parachain.sh"]
+"#,
+		)?;
+		assert_eq!(
+			hooks,
+			Hooks {
+				on_ready: vec!["./seed.sh".into()],
+				on_block: BTreeMap::from([(10, vec!["./e2e.sh".into()])]),
+				on_parachain_producing: BTreeMap::from([(
+					2000,
+					vec!["./seed-parachain.sh".into()]
+				)]),
+				on_shutdown: vec!["./report.sh".into(), "echo done".into()],
+			}
+		);
+		assert!(!hooks.is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn from_fails_when_invalid() {
+		for (toml, message) in [
+			("[pop.hooks]\non_start = \"ls\"", "unknown hook `pop.hooks.on_start`"),
+			(
+				"[pop.hooks]\non_ready = 1",
+				"expected `on_ready` hook to be a command or array of commands",
+			),
+			("[pop.hooks]\non_block = \"ls\"", "expected `on_block` hook to be a table"),
+			(
+				"[pop.hooks.on_block]\nlatest = \"ls\"",
+				"expected `on_block` hook to be keyed by number, found `latest`",
+			),
+		] {
+			assert!(matches!(hooks(toml), Err(Error::Config(m)) if m == message), "{toml}");
+		}
+	}
+
+	#[test]
+	fn env_works() {
+		let node = |name: &str, port: u16| NodeInfo {
+			name: name.to_string(),
+			ws_uri: format!("ws://127.0.0.1:{port}"),
+			rpc_port: Some(port),
+			prometheus_port: None,
+			p2p_port: None,
+			log_path: PathBuf::new(),
+			command: String::new(),
+			args: vec![],
+		};
+		let network = NetworkInfo {
+			base_dir: PathBuf::from("/tmp/zombie-1"),
+			relay_chain: RelayChainInfo {
+				chain: "paseo-local".to_string(),
+				nodes: vec![node("alice", 9944), node("bob", 9945)],
+			},
+			parachains: vec![ParachainInfo {
+				para_id: 2000,
+				chain_id: None,
+				collators: vec![node("collator-01", 9946)],
+			}],
+		};
+		let env = Hooks::env(&network);
+		let get = |key: &str| env.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+		assert_eq!(get("POP_NETWORK_DIR"), Some("/tmp/zombie-1"));
+		assert_eq!(get("POP_NETWORK_JSON"), Some("/tmp/zombie-1/network.json"));
+		assert_eq!(get("POP_RELAY_WS_URI"), Some("ws://127.0.0.1:9944"));
+		assert_eq!(get("POP_PARA_2000_WS_URI"), Some("ws://127.0.0.1:9946"));
+		assert_eq!(get("POP_NODE_BOB_WS_URI"), Some("ws://127.0.0.1:9945"));
+		assert_eq!(get("POP_NODE_COLLATOR_01_WS_URI"), Some("ws://127.0.0.1:9946"));
+	}
+
+	#[tokio::test]
+	async fn shutdown_runs_hooks_with_env() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let output = temp_dir.path().join("output");
+		let hooks = Hooks {
+			on_shutdown: vec![format!("echo $POP_RELAY_WS_URI > {}", output.display())],
+			..Default::default()
+		};
+		let env = vec![("POP_RELAY_WS_URI".to_string(), "ws://127.0.0.1:9944".to_string())];
+		hooks.shutdown(&env).await?;
+		assert_eq!(read_to_string(output)?, "ws://127.0.0.1:9944\n");
+
+		let hooks = Hooks { on_shutdown: vec!["exit 1".into()], ..Default::default() };
+		assert!(matches!(hooks.shutdown(&env).await, Err(Error::Hook(c, _)) if c == "exit 1"));
+		Ok(())
+	}
+}
@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::errors::Error;
+use clap::ValueEnum;
+use regex::Regex;
+use std::{
+	fs::File,
+	io::{BufRead, BufReader},
+	path::PathBuf,
+};
+
+/// The level of a node log line.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, ValueEnum)]
+pub enum LogLevel {
+	/// Trace messages.
+	Trace,
+	/// Debug messages.
+	Debug,
+	/// Informational messages.
+	Info,
+	/// Warnings.
+	Warn,
+	/// Errors.
+	Error,
+}
+
+impl LogLevel {
+	/// The level of a log line, as output by a node (e.g. `2024-07-01 12:00:00.000  WARN main
+	/// ...`), if any.
+	///
+	/// # Arguments
+	/// * `line` - The log line.
+	pub fn of(line: &str) -> Option<Self> {
+		// The level follows the date and time of the line
+		line.split_whitespace().nth(2).and_then(|token| match token {
+			"TRACE" => Some(LogLevel::Trace),
+			"DEBUG" => Some(LogLevel::Debug),
+			"INFO" => Some(LogLevel::Info),
+			"WARN" => Some(LogLevel::Warn),
+			"ERROR" => Some(LogLevel::Error),
+			_ => None,
+		})
+	}
+}
+
+/// A filter of node log lines.
+#[derive(Clone, Debug, Default)]
+pub struct LogFilter {
+	/// A pattern which lines must match.
+	pattern: Option<Regex>,
+	/// The minimum level of lines.
+	level: Option<LogLevel>,
+}
+
+impl LogFilter {
+	/// Creates a filter of node log lines. Lines without a level, such as those output when a
+	/// node panics, are included regardless of the level specified.
+	///
+	/// # Arguments
+	/// * `pattern` - A regular expression which lines must match.
+	/// * `level` - The minimum level of lines.
+	pub fn new(pattern: Option<&str>, level: Option<LogLevel>) -> Result<Self, Error> {
+		let pattern = pattern
+			.map(Regex::new)
+			.transpose()
+			.map_err(|e| Error::Config(format!("invalid pattern: {e}")))?;
+		Ok(Self { pattern, level })
+	}
+
+	/// Whether the specified log line is included by the filter.
+	///
+	/// # Arguments
+	/// * `line` - The log line.
+	pub fn includes(&self, line: &str) -> bool {
+		if self.pattern.as_ref().is_some_and(|p| !p.is_match(line)) {
+			return false;
+		}
+		match (self.level, LogLevel::of(line)) {
+			(Some(minimum), Some(level)) => level >= minimum,
+			_ => true,
+		}
+	}
+}
+
+/// A line of the log of a node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogLine {
+	/// The name of the node.
+	pub node: String,
+	/// The contents of the line.
+	pub line: String,
+}
+
+/// Follows the logs of a set of nodes, reading any lines as they are written.
+pub struct LogFollower {
+	logs: Vec<Log>,
+	filter: LogFilter,
+}
+
+impl LogFollower {
+	/// Starts following the logs of the specified nodes, from the start of each log.
+	///
+	/// # Arguments
+	/// * `nodes` - The names of the nodes and the paths of their logs.
+	/// * `filter` - The filter applied to the lines read.
+	pub fn new(nodes: impl IntoIterator<Item = (String, PathBuf)>, filter: LogFilter) -> Self {
+		let logs = nodes
+			.into_iter()
+			.map(|(node, path)| Log { node, path, reader: None, partial: String::new() })
+			.collect();
+		Self { logs, filter }
+	}
+
+	/// Reads any complete lines written since the last read, which are included by the filter,
+	/// in the order of the nodes specified.
+	pub fn read(&mut self) -> Result<Vec<LogLine>, Error> {
+		let mut lines = Vec::new();
+		for log in &mut self.logs {
+			for line in log.read()? {
+				if self.filter.includes(&line) {
+					lines.push(LogLine { node: log.node.clone(), line });
+				}
+			}
+		}
+		Ok(lines)
+	}
+}
+
+/// The log of a node.
+struct Log {
+	/// The name of the node.
+	node: String,
+	/// The path of the log.
+	path: PathBuf,
+	/// The reader of the log, once it exists.
+	reader: Option<BufReader<File>>,
+	/// Any incomplete line read.
+	partial: String,
+}
+
+impl Log {
+	/// Reads any complete lines written since the last read.
+	fn read(&mut self) -> Result<Vec<String>, Error> {
+		// The log is opened once it exists
+		if self.reader.is_none() && self.path.exists() {
+			self.reader = Some(BufReader::new(File::open(&self.path)?));
+		}
+		let Some(reader) = self.reader.as_mut() else {
+			return Ok(vec![]);
+		};
+		let mut lines = Vec::new();
+		loop {
+			let read = reader.read_line(&mut self.partial)?;
+			if read == 0 || !self.partial.ends_with('\n') {
+				break;
+			}
+			lines.push(self.partial.trim_end().to_string());
+			self.partial.clear();
+		}
+		Ok(lines)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use std::{fs::OpenOptions, io::Write};
+	use tempfile::tempdir;
+
+	const INFO: &str = "2024-07-01 12:00:00.000  INFO main sc_cli::runner: Parity Polkadot";
+	const WARN: &str =
+		"2024-07-01 12:00:01.000  WARN main sc_service::config: Using default protocol ID";
+	const PANIC: &str = "Thread 'tokio-runtime-worker' panicked at 'attempt to divide by zero'";
+
+	fn append(path: &PathBuf, contents: &str) -> Result<()> {
+		let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+		file.write_all(contents.as_bytes())?;
+		Ok(())
+	}
+
+	#[test]
+	fn log_level_works() {
+		assert_eq!(LogLevel::of(INFO), Some(LogLevel::Info));
+		assert_eq!(LogLevel::of(WARN), Some(LogLevel::Warn));
+		assert_eq!(LogLevel::of(PANIC), None);
+		assert_eq!(LogLevel::of(""), None);
+	}
+
+	#[test]
+	fn log_filter_works() -> Result<()> {
+		assert!([INFO, WARN, PANIC].iter().all(|l| LogFilter::default().includes(l)));
+
+		let filter = LogFilter::new(None, Some(LogLevel::Warn))?;
+		assert!(!filter.includes(INFO));
+		assert!(filter.includes(WARN));
+		assert!(filter.includes(PANIC));
+
+		let filter = LogFilter::new(Some("panicked|Polkadot"), None)?;
+		assert!(filter.includes(INFO));
+		assert!(!filter.includes(WARN));
+		assert!(filter.includes(PANIC));
+
+		assert!(matches!(LogFilter::new(Some("("), None), Err(Error::Config(..))));
+		Ok(())
+	}
+
+	#[test]
+	fn log_follower_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let alice = temp_dir.path().join("alice.log");
+		let bob = temp_dir.path().join("bob.log");
+		append(&alice, &format!("{INFO}\n"))?;
+		let nodes = [("alice".to_string(), alice.clone()), ("bob".to_string(), bob.clone())];
+
+		let line = |node: &str, line: &str| LogLine { node: node.into(), line: line.into() };
+		let mut follower = LogFollower::new(nodes, LogFilter::default());
+		assert_eq!(follower.read()?, [line("alice", INFO)]);
+		assert!(follower.read()?.is_empty());
+
+		// Subsequent lines are read once complete, including those of logs created later
+		append(&alice, &format!("{WARN}\n{PANIC}"))?;
+		append(&bob, &format!("{INFO}\n"))?;
+		assert_eq!(follower.read()?, [line("alice", WARN), line("bob", INFO)]);
+		append(&alice, "\n")?;
+		assert_eq!(follower.read()?, [line("alice", PANIC)]);
+		assert!(follower.read()?.is_empty());
+		Ok(())
+	}
+}
@@ -7,20 +7,97 @@ pub use pop_common::{
 	git::{GitHub, Repository},
 	sourcing::{Binary, GitHub::*, Source, Source::*},
 };
+use pop_common::{
+	sourcing::{self, offline},
+	Status,
+};
 use std::{
 	fmt::Debug,
 	fs::write,
 	iter::once,
 	path::{Path, PathBuf},
+	str::FromStr,
+	time::Duration,
 };
 use symlink::{remove_symlink_file, symlink_file};
 use tempfile::{Builder, NamedTempFile};
+use tokio::time::{timeout_at, Instant};
 use toml_edit::{value, ArrayOfTables, DocumentMut, Formatted, Item, Table, Value};
-use zombienet_sdk::{LocalFileSystem, Network, NetworkConfig, NetworkConfigExt};
+use zombienet_sdk::{LocalFileSystem, Network, NetworkConfig, NetworkConfigExt, NetworkNode};
 
 mod chain_specs;
+mod check;
+mod fork;
+mod hooks;
+mod logs;
+mod network;
 mod parachains;
+mod project;
+mod registry;
 mod relay;
+mod snapshot;
+
+pub use check::{check, Problem};
+pub use fork::Fork;
+pub use hooks::Hooks;
+pub use logs::{LogFilter, LogFollower, LogLevel, LogLine};
+pub use network::{NetworkInfo, NodeInfo, ParachainInfo, RelayChainInfo, NETWORK_JSON};
+pub use project::generate_network_config;
+pub use registry::{NetworkEntry, NodeEntry, Registry};
+pub use snapshot::NetworkSnapshot;
+
+/// The metric reporting the best block number of a node.
+const BEST_BLOCK_METRIC: &str = "block_height{status=\"best\"}";
+/// The metric reporting the finalized block number of a node.
+const FINALIZED_BLOCK_METRIC: &str = "block_height{status=\"finalized\"}";
+
+/// The condition a launched network is to satisfy before being considered ready for use.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Readiness {
+	/// The relay chain has produced the specified number of blocks, and each parachain has
+	/// produced a block.
+	Best(u32),
+	/// The relay chain has finalized the specified number of blocks, and each parachain has
+	/// finalized a block.
+	Finalized(u32),
+}
+
+impl Readiness {
+	/// The metric observed and the number of relay chain blocks required.
+	fn metric(&self) -> (&'static str, u32) {
+		match self {
+			Readiness::Best(blocks) => (BEST_BLOCK_METRIC, *blocks),
+			Readiness::Finalized(blocks) => (FINALIZED_BLOCK_METRIC, *blocks),
+		}
+	}
+}
+
+/// The default maximum number of messages in an HRMP channel.
+const HRMP_MAX_CAPACITY: i64 = 8;
+/// The default maximum size of a message in an HRMP channel.
+const HRMP_MAX_MESSAGE_SIZE: i64 = 512;
+
+/// An HRMP channel between two parachains, specified as `sender:recipient` (e.g. "2000:1000").
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HrmpChannel {
+	/// The identifier of the sending parachain.
+	pub sender: u32,
+	/// The identifier of the receiving parachain.
+	pub recipient: u32,
+}
+
+impl FromStr for HrmpChannel {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid =
+			|| Error::Config(format!("invalid HRMP channel `{s}`: expected `sender:recipient`"));
+		let (sender, recipient) = s.split_once(':').ok_or_else(invalid)?;
+		let sender = sender.trim().parse().map_err(|_| invalid())?;
+		let recipient = recipient.trim().parse().map_err(|_| invalid())?;
+		Ok(HrmpChannel { sender, recipient })
+	}
+}
 
 /// Configuration to launch a local network.
 pub struct Zombienet {
@@ -30,10 +107,13 @@ pub struct Zombienet {
 	relay_chain: RelayChain,
 	/// The configuration required to launch parachains.
 	parachains: IndexMap<u32, Parachain>,
+	/// The hooks to be run during the lifecycle of the network.
+	hooks: Hooks,
 }
 
 impl Zombienet {
-	/// Initializes the configuration for launching a local network.
+	/// Initializes the configuration for launching a local network. When offline, binaries are
+	/// resolved purely from the cache, failing if any required artifacts are missing.
 	///
 	/// # Arguments
 	/// * `cache` - The location used for caching binaries.
@@ -58,6 +138,7 @@ impl Zombienet {
 	) -> Result<Self, Error> {
 		// Parse network config
 		let network_config = NetworkConfiguration::from(network_config)?;
+		let hooks = network_config.hooks()?;
 		// Determine relay and parachain requirements based on arguments and config
 		let relay_chain = Self::relay_chain(
 			relay_chain_version,
@@ -84,7 +165,20 @@ impl Zombienet {
 			cache,
 		)
 		.await?;
-		Ok(Self { network_config, relay_chain, parachains })
+		let mut zombienet = Self { network_config, relay_chain, parachains, hooks };
+		// Ensure all binaries to be sourced are already cached when offline
+		if offline() {
+			let missing: Vec<_> = zombienet
+				.binaries()
+				.filter(|b| !b.local())
+				.flat_map(|b| b.missing())
+				.map(|p| p.display().to_string())
+				.collect();
+			if !missing.is_empty() {
+				return Err(sourcing::Error::Offline(missing).into());
+			}
+		}
+		Ok(zombienet)
 	}
 
 	/// The binaries required to launch the network.
@@ -164,7 +258,7 @@ impl Zombienet {
 				&command,
 				system_parachain_version,
 				system_parachain_runtime_version,
-				relay_chain.binary.version().expect("expected relay chain to have version"),
+				relay_chain.binary.version(),
 				chain,
 				cache,
 			)
@@ -276,6 +370,118 @@ impl Zombienet {
 		Ok(relay::default(version, runtime_version, chain, cache).await?)
 	}
 
+	/// Opens bidirectional HRMP channels between the specified parachains at genesis, in addition
+	/// to any configured within the network configuration file.
+	///
+	/// # Arguments
+	/// * `channels` - The channels to be opened.
+	pub fn open_hrmp_channels(&mut self, channels: &[HrmpChannel]) -> Result<(), Error> {
+		for channel in channels {
+			self.network_config.open_hrmp_channel(channel.sender, channel.recipient)?;
+			self.network_config.open_hrmp_channel(channel.recipient, channel.sender)?;
+		}
+		Ok(())
+	}
+
+	/// Forks a live chain as a parachain of the network, which is launched from a snapshot of the
+	/// state of the live chain rather than from genesis. See [`Fork::chain_spec`] for details.
+	///
+	/// # Arguments
+	/// * `fork` - The live chain to be forked.
+	/// * `dir` - The directory used to store snapshots.
+	/// * `status` - Used to observe status updates.
+	pub async fn fork(
+		&mut self,
+		fork: &Fork,
+		dir: &Path,
+		status: &impl Status,
+	) -> Result<PathBuf, Error> {
+		let parachain = self.network_config.parachain(fork.para_id).ok_or_else(|| {
+			Error::Config(format!("the forked parachain {} is not configured", fork.para_id))
+		})?;
+		let collators = NetworkConfiguration::collators(parachain);
+		let collators: Vec<_> = collators.iter().filter_map(|c| c.get("name")?.as_str()).collect();
+		let path = fork.chain_spec(&self.relay_chain.chain, &collators, dir, status).await?;
+		let parachain = self.network_config.parachain_mut(fork.para_id).expect("checked above");
+		parachain.insert("chain_spec_path", value(path.display().to_string()));
+		parachain.remove("chain_spec_command");
+		Ok(path)
+	}
+
+	/// Relaunches the network from a snapshot of its state, by launching each chain from the raw
+	/// chain specification and each node from the databases within the snapshot.
+	///
+	/// # Arguments
+	/// * `snapshot` - The snapshot of the network.
+	pub fn restore(&mut self, snapshot: &NetworkSnapshot) -> Result<(), Error> {
+		let info = snapshot.info()?;
+		let mut nodes: Vec<_> = info.nodes().map(|n| n.name.as_str()).collect();
+		nodes.sort();
+		let mut configured = self.network_config.node_names()?;
+		configured.sort();
+		if nodes != configured {
+			return Err(Error::Config(format!(
+				"the nodes of the snapshot ({}) do not match those configured ({})",
+				nodes.join(", "),
+				configured.join(", ")
+			)));
+		}
+		let path = |path: PathBuf| value(path.display().to_string());
+		let db_snapshot = |node: &mut Table| -> Result<(), Error> {
+			let name = node
+				.get("name")
+				.and_then(|n| n.as_str())
+				.ok_or_else(|| Error::Config("expected node to have `name`".into()))?;
+			node.insert("db_snapshot", path(snapshot.node(name)));
+			Ok(())
+		};
+
+		let relay_chain = self.network_config.relay_chain_mut()?;
+		relay_chain
+			.insert("chain_spec_path", path(snapshot.relay_chain_spec(&info.relay_chain.chain)));
+		relay_chain.remove("chain_spec_command");
+		for node in NetworkConfiguration::nodes_mut(relay_chain)
+			.into_iter()
+			.flat_map(|n| n.iter_mut())
+		{
+			db_snapshot(node)?;
+		}
+		for parachain in &info.parachains {
+			let table = self.network_config.parachain_mut(parachain.para_id).ok_or_else(|| {
+				Error::Config(format!("parachain {} is not configured", parachain.para_id))
+			})?;
+			table.insert("chain_spec_path", path(snapshot.parachain_spec(parachain.para_id)));
+			table.remove("chain_spec_command");
+			for collator in NetworkConfiguration::collators_mut(table) {
+				db_snapshot(collator)?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Stops the network and saves a snapshot of its state, from which it can later be relaunched
+	/// via [`Self::restore`].
+	///
+	/// # Arguments
+	/// * `network` - The running network.
+	/// * `snapshot` - The snapshot to be saved.
+	pub async fn snapshot(
+		&self,
+		network: Network<LocalFileSystem>,
+		snapshot: &NetworkSnapshot,
+	) -> Result<(), Error> {
+		let info = NetworkInfo::new(&network);
+		// Stop the nodes so that their databases are consistent
+		network.destroy().await.map_err(|e| Error::OrchestratorError(e.into()))?;
+		snapshot.save(&info, &self.network_config.0.to_string())
+	}
+
+	/// The hooks to be run during the lifecycle of the network, as configured within the
+	/// `[pop.hooks]` table of the network configuration file.
+	pub fn hooks(&self) -> &Hooks {
+		&self.hooks
+	}
+
 	/// Launches the local network.
 	pub async fn spawn(&mut self) -> Result<Network<LocalFileSystem>, Error> {
 		// Symlink polkadot workers
@@ -306,13 +512,54 @@ impl Zombienet {
 		let network_config = NetworkConfig::load_from_toml(path)?;
 		Ok(network_config.spawn_native().await?)
 	}
+
+	/// Waits until a launched network is ready for use, as observed via the first node of each
+	/// chain.
+	///
+	/// # Arguments
+	/// * `network` - The launched network.
+	/// * `readiness` - The condition the network is to satisfy.
+	/// * `timeout` - The maximum time to wait.
+	pub async fn wait_until(
+		network: &Network<LocalFileSystem>,
+		readiness: Readiness,
+		timeout: Duration,
+	) -> Result<(), Error> {
+		let (metric, blocks) = readiness.metric();
+		let mut chains =
+			vec![(network.relaychain().chain().to_string(), network.relaychain().nodes(), blocks)];
+		let mut parachains = network.parachains();
+		parachains.sort_by_key(|p| p.para_id());
+		for parachain in parachains {
+			chains.push((format!("parachain {}", parachain.para_id()), parachain.collators(), 1));
+		}
+
+		let deadline = Instant::now() + timeout;
+		for (chain, nodes, blocks) in chains {
+			let node = first(nodes)
+				.ok_or_else(|| Error::Config(format!("expected {chain} to have nodes")))?;
+			let description = format!("{chain} to reach block {blocks} ({metric})");
+			timeout_at(deadline, node.wait_metric(metric, |b| b >= blocks as f64))
+				.await
+				.map_err(|_| Error::Timeout(description))??;
+		}
+		Ok(())
+	}
+}
+
+/// The first of the specified nodes, ordered by name.
+fn first(mut nodes: Vec<&NetworkNode>) -> Option<&NetworkNode> {
+	nodes.sort_by_key(|n| n.name());
+	nodes.into_iter().next()
 }
 
 /// The network configuration.
 struct NetworkConfiguration(DocumentMut);
 
 impl NetworkConfiguration {
-	/// Initializes the network configuration from the specified file.
+	/// Initializes the network configuration from the specified file. Files with a `.json`,
+	/// `.yaml` or `.yml` extension are parsed as per the zombienet JSON/YAML format and converted
+	/// to TOML, otherwise the file is parsed as TOML.
 	///
 	/// # Arguments
 	/// * `file` - The network configuration file.
@@ -322,12 +569,28 @@ impl NetworkConfiguration {
 			return Err(Error::Config(format!("The {file:?} configuration file was not found")));
 		}
 		let contents = std::fs::read_to_string(file)?;
-		let config = contents.parse::<DocumentMut>().map_err(|err| Error::TomlError(err.into()))?;
-		let network_config = NetworkConfiguration(config);
+		let config = match file.extension().and_then(|e| e.to_str()) {
+			Some("json") => Self::document(serde_json::from_str(&contents)?)?,
+			Some("yaml" | "yml") => Self::document(serde_yaml::from_str(&contents)?)?,
+			_ => contents.parse::<DocumentMut>().map_err(|err| Error::TomlError(err.into()))?,
+		};
+		let mut network_config = NetworkConfiguration(config);
 		network_config.relay_chain()?;
+		network_config.expand_hrmp_channels()?;
 		Ok(network_config)
 	}
 
+	/// Converts a network configuration in the zombienet JSON/YAML format to a TOML document.
+	///
+	/// # Arguments
+	/// * `config` - The network configuration.
+	fn document(config: serde_json::Value) -> Result<DocumentMut, Error> {
+		match to_item(config)? {
+			Some(Item::Table(table)) => Ok(DocumentMut::from(table)),
+			_ => Err(Error::Config("expected network configuration to be an object".into())),
+		}
+	}
+
 	/// Returns the `relaychain` configuration.
 	fn relay_chain(&self) -> Result<&Table, Error> {
 		self.0
@@ -344,6 +607,18 @@ impl NetworkConfiguration {
 			.ok_or_else(|| Error::Config("expected `relaychain`".into()))
 	}
 
+	/// Returns the hooks configured within the `[pop.hooks]` table, if any.
+	fn hooks(&self) -> Result<Hooks, Error> {
+		match self.0.get("pop").and_then(|p| p.get("hooks")) {
+			Some(hooks) => Hooks::from(
+				hooks
+					.as_table()
+					.ok_or_else(|| Error::Config("expected `pop.hooks` to be a table".into()))?,
+			),
+			None => Ok(Hooks::default()),
+		}
+	}
+
 	/// Returns the `parachains` configuration.
 	fn parachains(&self) -> Option<&ArrayOfTables> {
 		self.0.get("parachains").and_then(|p| p.as_array_of_tables())
@@ -354,6 +629,141 @@ impl NetworkConfiguration {
 		self.0.get_mut("parachains").and_then(|p| p.as_array_of_tables_mut())
 	}
 
+	/// Returns the configuration of the specified parachain.
+	fn parachain(&self, id: u32) -> Option<&Table> {
+		self.parachains()?
+			.iter()
+			.find(|p| p.get("id").and_then(|i| i.as_integer()) == Some(id as i64))
+	}
+
+	/// Returns the configuration of the specified parachain.
+	fn parachain_mut(&mut self, id: u32) -> Option<&mut Table> {
+		self.parachains_mut()?
+			.iter_mut()
+			.find(|p| p.get("id").and_then(|i| i.as_integer()) == Some(id as i64))
+	}
+
+	/// Returns the collators of a parachain, configured via either `collators` or `collator`.
+	fn collators(parachain: &Table) -> Vec<&Table> {
+		match parachain.get("collators").and_then(|c| c.as_array_of_tables()) {
+			Some(collators) => collators.iter().collect(),
+			None => parachain.get("collator").and_then(|c| c.as_table()).into_iter().collect(),
+		}
+	}
+
+	/// Returns the collators of a parachain, configured via either `collators` or `collator`.
+	fn collators_mut(parachain: &mut Table) -> Vec<&mut Table> {
+		if parachain.contains_key("collators") {
+			return parachain
+				.get_mut("collators")
+				.and_then(|c| c.as_array_of_tables_mut())
+				.map(|c| c.iter_mut().collect())
+				.unwrap_or_default();
+		}
+		parachain
+			.get_mut("collator")
+			.and_then(|c| c.as_table_mut())
+			.into_iter()
+			.collect()
+	}
+
+	/// Returns the names of all configured nodes.
+	fn node_names(&self) -> Result<Vec<&str>, Error> {
+		let relay_chain = Self::nodes(self.relay_chain()?).into_iter().flat_map(|n| n.iter());
+		let collators =
+			self.parachains().into_iter().flat_map(|p| p.iter()).flat_map(Self::collators);
+		Ok(relay_chain.chain(collators).filter_map(|n| n.get("name")?.as_str()).collect())
+	}
+
+	/// Returns the identifiers of the configured parachains.
+	fn para_ids(&self) -> Vec<u32> {
+		self.parachains()
+			.map(|p| {
+				p.iter()
+					.filter_map(|p| p.get("id").and_then(|i| i.as_integer()))
+					.filter_map(|i| u32::try_from(i).ok())
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Expands any `[[hrmp_channels]]` specified in shorthand, where only `sender` and `recipient`
+	/// are provided: the channel is completed with default limits and the reverse channel opened,
+	/// so that the parachains can communicate bidirectionally. Fully specified channels are left
+	/// unchanged. The parachains of all channels must be configured.
+	fn expand_hrmp_channels(&mut self) -> Result<(), Error> {
+		let para_ids = self.para_ids();
+		// An empty array, as used by the JSON format, is equivalent to no channels
+		if self
+			.0
+			.get("hrmp_channels")
+			.and_then(|c| c.as_array())
+			.is_some_and(|c| c.is_empty())
+		{
+			self.0.remove("hrmp_channels");
+		}
+		let Some(channels) = self.0.get_mut("hrmp_channels") else {
+			return Ok(());
+		};
+		let channels = channels.as_array_of_tables_mut().ok_or_else(|| {
+			Error::Config("expected `hrmp_channels` to be an array of tables".into())
+		})?;
+		let mut reverse = vec![];
+		for channel in channels.iter_mut() {
+			let id = |key: &str| {
+				channel
+					.get(key)
+					.and_then(|i| i.as_integer())
+					.and_then(|i| u32::try_from(i).ok())
+					.ok_or_else(|| {
+						Error::Config(format!("expected HRMP channel to have a `{key}` parachain"))
+					})
+			};
+			let (sender, recipient) = (id("sender")?, id("recipient")?);
+			validate_hrmp_channel(sender, recipient, &para_ids)?;
+			if !(channel.contains_key("max_capacity") && channel.contains_key("max_message_size")) {
+				channel.entry("max_capacity").or_insert(value(HRMP_MAX_CAPACITY));
+				channel.entry("max_message_size").or_insert(value(HRMP_MAX_MESSAGE_SIZE));
+				reverse.push((recipient, sender));
+			}
+		}
+		for (sender, recipient) in reverse {
+			self.open_hrmp_channel(sender, recipient)?;
+		}
+		Ok(())
+	}
+
+	/// Opens an HRMP channel from one parachain to another with default limits, unless already
+	/// configured.
+	///
+	/// # Arguments
+	/// * `sender` - The identifier of the sending parachain.
+	/// * `recipient` - The identifier of the receiving parachain.
+	fn open_hrmp_channel(&mut self, sender: u32, recipient: u32) -> Result<(), Error> {
+		validate_hrmp_channel(sender, recipient, &self.para_ids())?;
+		let channels = self
+			.0
+			.entry("hrmp_channels")
+			.or_insert(Item::ArrayOfTables(ArrayOfTables::new()))
+			.as_array_of_tables_mut()
+			.ok_or_else(|| {
+				Error::Config("expected `hrmp_channels` to be an array of tables".into())
+			})?;
+		let exists = channels.iter().any(|c| {
+			c.get("sender").and_then(|i| i.as_integer()) == Some(sender as i64) &&
+				c.get("recipient").and_then(|i| i.as_integer()) == Some(recipient as i64)
+		});
+		if !exists {
+			let mut channel = Table::new();
+			channel.insert("sender", value(sender as i64));
+			channel.insert("recipient", value(recipient as i64));
+			channel.insert("max_capacity", value(HRMP_MAX_CAPACITY));
+			channel.insert("max_message_size", value(HRMP_MAX_MESSAGE_SIZE));
+			channels.push(channel);
+		}
+		Ok(())
+	}
+
 	/// Returns the `command` configuration.
 	fn command(config: &Table) -> Option<&Item> {
 		config.get("command")
@@ -417,8 +827,13 @@ impl NetworkConfiguration {
 				}
 			}
 		}
-		// Configure chain spec generator
-		if let Some(path) = relay_chain.chain_spec_generator.as_ref().map(|b| b.path()) {
+		// Configure chain spec generator, unless a chain spec is provided
+		if let Some(path) = relay_chain
+			.chain_spec_generator
+			.as_ref()
+			.filter(|_| !relay_chain_config.contains_key("chain_spec_path"))
+			.map(|b| b.path())
+		{
 			let command = format!("{} {}", Self::resolve_path(&path)?, "{{chainName}}");
 			*relay_chain_config.entry("chain_spec_command").or_insert(value(&command)) =
 				value(&command);
@@ -439,8 +854,13 @@ impl NetworkConfiguration {
 				let path = Self::resolve_path(&para.binary.path())?;
 				table.insert("default_command", value(&path));
 
-				// Configure chain spec generator
-				if let Some(path) = para.chain_spec_generator.as_ref().map(|b| b.path()) {
+				// Configure chain spec generator, unless a chain spec is provided
+				if let Some(path) = para
+					.chain_spec_generator
+					.as_ref()
+					.filter(|_| !table.contains_key("chain_spec_path"))
+					.map(|b| b.path())
+				{
 					let command = format!("{} {}", Self::resolve_path(&path)?, "{{chainName}}");
 					*table.entry("chain_spec_command").or_insert(value(&command)) = value(&command);
 				}
@@ -463,6 +883,9 @@ impl NetworkConfiguration {
 			}
 		}
 
+		// Remove pop specific configuration, which is not recognised by zombienet-sdk
+		self.0.remove("pop");
+
 		// Write adapted zombienet config to temp file
 		let network_config_file = Builder::new().suffix(".toml").tempfile()?;
 		let path = network_config_file
@@ -487,6 +910,93 @@ impl NetworkConfiguration {
 	}
 }
 
+/// Ensures an HRMP channel is between two distinct parachains of the network.
+///
+/// # Arguments
+/// * `sender` - The identifier of the sending parachain.
+/// * `recipient` - The identifier of the receiving parachain.
+/// * `para_ids` - The identifiers of the parachains of the network.
+fn validate_hrmp_channel(sender: u32, recipient: u32, para_ids: &[u32]) -> Result<(), Error> {
+	if sender == recipient {
+		return Err(Error::Config(format!(
+			"the HRMP channel from {sender} to {recipient} must be between different parachains"
+		)));
+	}
+	if let Some(id) = [sender, recipient].into_iter().find(|id| !para_ids.contains(id)) {
+		return Err(Error::Config(format!(
+			"the HRMP channel from {sender} to {recipient} references unknown parachain {id}"
+		)));
+	}
+	Ok(())
+}
+
+/// Converts a JSON value to a TOML item, with objects converted to tables and arrays of objects to
+/// arrays of tables. Null values are omitted.
+///
+/// # Arguments
+/// * `value` - The value to be converted.
+fn to_item(value: serde_json::Value) -> Result<Option<Item>, Error> {
+	use serde_json::Value::{Array, Object};
+	Ok(match value {
+		Object(object) => {
+			let mut table = Table::new();
+			table.set_implicit(true);
+			for (key, value) in object {
+				if let Some(item) = to_item(value)? {
+					table.insert(&key, item);
+				}
+			}
+			Some(Item::Table(table))
+		},
+		Array(values) if !values.is_empty() && values.iter().all(|v| v.is_object()) => {
+			let mut tables = ArrayOfTables::new();
+			for value in values {
+				if let Some(Item::Table(table)) = to_item(value)? {
+					tables.push(table);
+				}
+			}
+			Some(Item::ArrayOfTables(tables))
+		},
+		value => to_value(value)?.map(Item::Value),
+	})
+}
+
+/// Converts a JSON value to a TOML value, with objects converted to inline tables. Null values are
+/// omitted. Integers which cannot be represented by TOML (i.e. beyond the range of a 64-bit signed
+/// integer) are rejected, rather than losing precision.
+///
+/// # Arguments
+/// * `value` - The value to be converted.
+fn to_value(value: serde_json::Value) -> Result<Option<Value>, Error> {
+	use serde_json::Value::*;
+	Ok(match value {
+		Null => None,
+		Bool(value) => Some(value.into()),
+		Number(number) => match number.as_i64() {
+			Some(number) => Some(number.into()),
+			None if number.to_string().contains(['.', 'e', 'E']) =>
+				number.as_f64().map(Value::from),
+			None =>
+				return Err(Error::Config(format!(
+					"the integer {number} is too large to be represented within a network configuration"
+				))),
+		},
+		String(value) => Some(value.into()),
+		Array(values) => Some(Value::Array(
+			values
+				.into_iter()
+				.filter_map(|v| to_value(v).transpose())
+				.collect::<Result<_, _>>()?,
+		)),
+		Object(object) => Some(Value::InlineTable(
+			object
+				.into_iter()
+				.filter_map(|(k, v)| to_value(v).transpose().map(|v| v.map(|v| (k, v))))
+				.collect::<Result<_, _>>()?,
+		)),
+	})
+}
+
 /// The configuration required to launch the relay chain.
 struct RelayChain {
 	/// The binary used to launch a relay chain node.
@@ -494,7 +1004,6 @@ struct RelayChain {
 	/// The additional workers required by the relay chain node.
 	workers: [&'static str; 2],
 	/// The name of the chain.
-	#[allow(dead_code)]
 	chain: String,
 	/// If applicable, the binary used to generate a chain specification.
 	chain_spec_generator: Option<Binary>,
@@ -662,7 +1171,10 @@ mod tests {
 
 	mod zombienet {
 		use super::*;
-		use pop_common::Status;
+		use pop_common::{
+			test_utils::{isolated, Offline},
+			Status,
+		};
 
 		pub(crate) struct Output;
 		impl Status for Output {
@@ -709,6 +1221,62 @@ chain = "paseo-local"
 			Ok(())
 		}
 
+		#[tokio::test]
+		async fn new_fails_when_offline_with_missing_artifacts() -> Result<()> {
+			if !isolated("up::tests::zombienet::new_fails_when_offline_with_missing_artifacts") {
+				return Ok(());
+			}
+			let _offline = Offline::enable();
+			let temp_dir = tempdir()?;
+			let cache = PathBuf::from(temp_dir.path());
+			let config = Builder::new().suffix(".toml").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"
+[relaychain]
+chain = "paseo-local"
+"#
+			)?;
+			let version = "v1.12.0";
+			let new = || {
+				Zombienet::new(
+					&cache,
+					config.path().to_str().unwrap(),
+					Some(version),
+					Some(version),
+					None,
+					None,
+					None,
+				)
+			};
+
+			let missing: Vec<_> = [
+				"polkadot",
+				"polkadot-execute-worker",
+				"polkadot-prepare-worker",
+				"paseo-chain-spec-generator",
+			]
+			.iter()
+			.map(|name| cache.join(format!("{name}-{version}")).display().to_string())
+			.collect();
+			let result = new().await;
+			assert!(
+				matches!(
+					result,
+					Err(Error::SourcingError(sourcing::Error::Offline(ref m))) if *m == missing
+				),
+				"{:?}",
+				result.err()
+			);
+
+			// Launching is possible once all artifacts are cached
+			for path in &missing {
+				File::create(path)?;
+			}
+			new().await?;
+			Ok(())
+		}
+
 		#[tokio::test]
 		async fn new_with_relay_chain_spec_generator_works() -> Result<()> {
 			let temp_dir = tempdir()?;
@@ -1144,6 +1712,100 @@ command = "./target/release/parachain-template-node"
 			Ok(())
 		}
 
+		#[tokio::test]
+		async fn restore_works() -> Result<()> {
+			let temp_dir = tempdir()?;
+			let cache = PathBuf::from(temp_dir.path());
+			let config = Builder::new().suffix(".toml").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"
+[relaychain]
+chain = "paseo-local"
+
+[[relaychain.nodes]]
+name = "alice"
+
+[[parachains]]
+id = 2000
+
+[parachains.collator]
+name = "collator-01"
+command = "./target/release/parachain-template-node"
+"#
+			)?;
+			let mut zombienet = Zombienet::new(
+				&cache,
+				config.path().to_str().unwrap(),
+				Some("v1.12.0"),
+				None,
+				None,
+				None,
+				None,
+			)
+			.await?;
+
+			// Describe the network within a snapshot
+			let snapshot = NetworkSnapshot::new(&cache.join("snapshots"), "pop")?;
+			std::fs::create_dir_all(snapshot.path())?;
+			let node = |name: &str| NodeInfo {
+				name: name.to_string(),
+				ws_uri: "ws://127.0.0.1:9944".to_string(),
+				rpc_port: None,
+				prometheus_port: None,
+				p2p_port: None,
+				log_path: PathBuf::new(),
+				command: String::new(),
+				args: vec![],
+			};
+			let mut info = NetworkInfo {
+				base_dir: snapshot.path().to_path_buf(),
+				relay_chain: RelayChainInfo {
+					chain: "paseo-local".to_string(),
+					nodes: vec![node("alice"), node("bob")],
+				},
+				parachains: vec![ParachainInfo {
+					para_id: 2000,
+					chain_id: None,
+					collators: vec![node("collator-01")],
+				}],
+			};
+			info.save()?;
+			assert!(matches!(
+				zombienet.restore(&snapshot),
+				Err(Error::Config(message))
+				if message == "the nodes of the snapshot (alice, bob, collator-01) do not match those configured (alice, collator-01)"
+			));
+
+			info.relay_chain.nodes.pop();
+			info.save()?;
+			zombienet.restore(&snapshot)?;
+			let path = |path: PathBuf| path.display().to_string();
+			let config = &zombienet.network_config;
+			let relay_chain = config.relay_chain()?;
+			assert_eq!(
+				relay_chain.get("chain_spec_path").and_then(|p| p.as_str()),
+				Some(path(snapshot.relay_chain_spec("paseo-local")).as_str())
+			);
+			let alice = &NetworkConfiguration::nodes(relay_chain).unwrap().get(0).unwrap();
+			assert_eq!(
+				alice.get("db_snapshot").and_then(|p| p.as_str()),
+				Some(path(snapshot.node("alice")).as_str())
+			);
+			let parachain = config.parachain(2000).unwrap();
+			assert_eq!(
+				parachain.get("chain_spec_path").and_then(|p| p.as_str()),
+				Some(path(snapshot.parachain_spec(2000)).as_str())
+			);
+			assert_eq!(
+				NetworkConfiguration::collators(parachain)[0]
+					.get("db_snapshot")
+					.and_then(|p| p.as_str()),
+				Some(path(snapshot.node("collator-01")).as_str())
+			);
+			Ok(())
+		}
+
 		#[tokio::test]
 		async fn new_with_moonbeam_works() -> Result<()> {
 			let temp_dir = tempdir()?;
@@ -1523,6 +2185,321 @@ validator = true
 			Ok(())
 		}
 
+		#[test]
+		fn initializes_hooks_from_file() -> Result<(), Error> {
+			let config = Builder::new().suffix(".toml").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"
+[relaychain]
+chain = "paseo-local"
+
+[pop.hooks]
+on_ready = "./seed.sh"
+"#
+			)?;
+			let mut network_config = NetworkConfiguration::from(config.path())?;
+			assert_eq!(network_config.hooks()?.on_ready, ["./seed.sh"]);
+
+			// Hooks are not passed through to zombienet-sdk
+			let relay_chain_binary = Builder::new().tempfile()?;
+			let mut configured = network_config.configure(
+				&RelayChain {
+					binary: Binary::Local {
+						name: "polkadot".to_string(),
+						path: relay_chain_binary.path().to_path_buf(),
+						manifest: None,
+					},
+					workers: ["polkadot-execute-worker", ""],
+					chain: "paseo-local".to_string(),
+					chain_spec_generator: None,
+				},
+				&IndexMap::new(),
+			)?;
+			let mut contents = String::new();
+			configured.read_to_string(&mut contents)?;
+			assert!(!contents.contains("pop"));
+			Ok(())
+		}
+
+		#[test]
+		fn initializes_from_json_file() -> Result<(), Error> {
+			let config = Builder::new().suffix(".json").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"{{
+	"settings": {{ "timeout": 1000, "provider": null }},
+	"relaychain": {{
+		"chain": "paseo-local",
+		"default_command": "polkadot",
+		"default_args": ["-lparachain=debug"],
+		"nodes": [{{ "name": "alice", "validator": true }}, {{ "name": "bob" }}]
+	}},
+	"parachains": [{{
+		"id": 2000,
+		"collators": [{{ "name": "collator", "command": "./target/release/node" }}]
+	}}],
+	"hrmp_channels": [],
+	"pop": {{ "hooks": {{ "on_parachain_producing": {{ "2000": "./seed.sh" }} }} }}
+}}"#
+			)?;
+			let network_config = NetworkConfiguration::from(config.path())?;
+			let relay_chain = network_config.relay_chain()?;
+			assert_eq!("paseo-local", relay_chain["chain"].as_str().unwrap());
+			assert_eq!(
+				"polkadot",
+				NetworkConfiguration::default_command(relay_chain).unwrap().as_str().unwrap()
+			);
+			let nodes = NetworkConfiguration::nodes(relay_chain).unwrap();
+			assert_eq!(
+				nodes.iter().map(|n| n["name"].as_str().unwrap()).collect::<Vec<_>>(),
+				["alice", "bob"]
+			);
+			let para_2000 = network_config.parachains().unwrap().get(0).unwrap();
+			assert_eq!(2000, para_2000["id"].as_integer().unwrap());
+			let collator = para_2000["collators"].as_array_of_tables().unwrap().get(0).unwrap();
+			assert_eq!(
+				"./target/release/node",
+				NetworkConfiguration::command(collator).unwrap().as_str().unwrap()
+			);
+			assert_eq!(
+				network_config.hooks()?.on_parachain_producing.get(&2000).unwrap(),
+				&["./seed.sh"]
+			);
+			assert_eq!(
+				network_config.0.to_string(),
+				r#"[settings]
+timeout = 1000
+
+[relaychain]
+chain = "paseo-local"
+default_command = "polkadot"
+default_args = ["-lparachain=debug"]
+
+[[relaychain.nodes]]
+name = "alice"
+validator = true
+
+[[relaychain.nodes]]
+name = "bob"
+
+[[parachains]]
+id = 2000
+
+[[parachains.collators]]
+name = "collator"
+command = "./target/release/node"
+
+[pop.hooks.on_parachain_producing]
+2000 = "./seed.sh"
+"#
+			);
+			Ok(())
+		}
+
+		#[test]
+		fn initializes_from_yaml_file() -> Result<(), Error> {
+			let config = Builder::new().suffix(".yaml").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"
+relaychain:
+  chain: paseo-local
+  nodes:
+    - name: alice
+parachains:
+  - id: 2000
+    default_command: node
+    collator:
+      name: collator
+"#
+			)?;
+			let network_config = NetworkConfiguration::from(config.path())?;
+			let relay_chain = network_config.relay_chain()?;
+			assert_eq!("paseo-local", relay_chain["chain"].as_str().unwrap());
+			let nodes = NetworkConfiguration::nodes(relay_chain).unwrap();
+			assert_eq!("alice", nodes.get(0).unwrap()["name"].as_str().unwrap());
+			let para_2000 = network_config.parachains().unwrap().get(0).unwrap();
+			assert_eq!(2000, para_2000["id"].as_integer().unwrap());
+			assert_eq!(
+				"node",
+				NetworkConfiguration::default_command(para_2000).unwrap().as_str().unwrap()
+			);
+			assert_eq!("collator", para_2000["collator"]["name"].as_str().unwrap());
+			Ok(())
+		}
+
+		#[test]
+		fn initialising_from_json_file_fails_when_malformed() -> Result<(), Error> {
+			let config = Builder::new().suffix(".json").tempfile()?;
+			writeln!(config.as_file(), "[")?;
+			assert!(matches!(NetworkConfiguration::from(config.path()), Err(Error::JsonError(..))));
+			let config = Builder::new().suffix(".json").tempfile()?;
+			writeln!(config.as_file(), "[]")?;
+			assert!(matches!(NetworkConfiguration::from(config.path()), Err(Error::Config(..))));
+			Ok(())
+		}
+
+		#[test]
+		fn initialising_from_json_file_fails_when_losing_precision() -> Result<(), Error> {
+			let config = Builder::new().suffix(".json").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"{{ "relaychain": {{ "chain": "paseo-local", "nodes": [{{ "name": "alice", "balance": 100000000000000000000 }}] }} }}"#
+			)?;
+			assert!(matches!(
+				NetworkConfiguration::from(config.path()),
+				Err(Error::Config(e)) if e == "the integer 100000000000000000000 is too large to be represented within a network configuration"
+			));
+			let config = Builder::new().suffix(".json").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"{{ "relaychain": {{ "chain": "paseo-local", "nodes": [{{ "name": "alice", "balance": 9223372036854775807 }}] }}, "settings": {{ "ratio": 0.5 }} }}"#
+			)?;
+			let network_config = NetworkConfiguration::from(config.path())?;
+			let alice = NetworkConfiguration::nodes(network_config.relay_chain()?).unwrap();
+			assert_eq!(alice.get(0).unwrap()["balance"].as_integer(), Some(i64::MAX));
+			assert_eq!(network_config.0["settings"]["ratio"].as_float(), Some(0.5));
+			Ok(())
+		}
+
+		#[test]
+		fn resolves_parachain_collators() -> Result<(), Error> {
+			let config = Builder::new().suffix(".toml").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"
+[relaychain]
+chain = "paseo-local"
+
+[[parachains]]
+id = 1000
+
+[[parachains.collators]]
+name = "asset-hub-01"
+
+[[parachains.collators]]
+name = "asset-hub-02"
+
+[[parachains]]
+id = 2000
+
+[parachains.collator]
+name = "pop"
+"#
+			)?;
+			let network_config = NetworkConfiguration::from(config.path())?;
+			let names = |id| {
+				NetworkConfiguration::collators(network_config.parachain(id).unwrap())
+					.iter()
+					.map(|c| c["name"].as_str().unwrap().to_string())
+					.collect::<Vec<_>>()
+			};
+			assert_eq!(names(1000), ["asset-hub-01", "asset-hub-02"]);
+			assert_eq!(names(2000), ["pop"]);
+			assert!(network_config.parachain(3000).is_none());
+			Ok(())
+		}
+
+		#[test]
+		fn expands_hrmp_channels() -> Result<(), Error> {
+			let config = Builder::new().suffix(".toml").tempfile()?;
+			write!(
+				config.as_file(),
+				r#"[relaychain]
+chain = "paseo-local"
+
+[[parachains]]
+id = 1000
+
+[[parachains]]
+id = 2000
+
+[[parachains]]
+id = 4001
+
+[[hrmp_channels]]
+sender = 2000
+recipient = 1000
+
+[[hrmp_channels]]
+sender = 2000
+recipient = 4001
+max_capacity = 1000
+max_message_size = 8000
+"#
+			)?;
+			let mut network_config = NetworkConfiguration::from(config.path())?;
+			// Channels already configured are not duplicated
+			network_config.open_hrmp_channel(1000, 2000)?;
+			network_config.open_hrmp_channel(4001, 1000)?;
+			assert_eq!(
+				network_config.0.to_string(),
+				r#"[relaychain]
+chain = "paseo-local"
+
+[[parachains]]
+id = 1000
+
+[[parachains]]
+id = 2000
+
+[[parachains]]
+id = 4001
+
+[[hrmp_channels]]
+sender = 2000
+recipient = 1000
+max_capacity = 8
+max_message_size = 512
+
+[[hrmp_channels]]
+sender = 2000
+recipient = 4001
+max_capacity = 1000
+max_message_size = 8000
+
+[[hrmp_channels]]
+sender = 1000
+recipient = 2000
+max_capacity = 8
+max_message_size = 512
+
+[[hrmp_channels]]
+sender = 4001
+recipient = 1000
+max_capacity = 8
+max_message_size = 512
+"#
+			);
+			Ok(())
+		}
+
+		#[test]
+		fn expanding_hrmp_channels_fails_when_invalid() -> Result<(), Error> {
+			for (channel, message) in [
+				(
+					"sender = 2000\nrecipient = 1000",
+					"the HRMP channel from 2000 to 1000 references unknown parachain 1000",
+				),
+				(
+					"sender = 2000\nrecipient = 2000",
+					"the HRMP channel from 2000 to 2000 must be between different parachains",
+				),
+				("sender = 2000", "expected HRMP channel to have a `recipient` parachain"),
+			] {
+				let config = Builder::new().suffix(".toml").tempfile()?;
+				writeln!(
+					config.as_file(),
+					"[relaychain]\nchain = \"paseo-local\"\n\n[[parachains]]\nid = 2000\n\n[[hrmp_channels]]\n{channel}"
+				)?;
+				assert!(
+					matches!(NetworkConfiguration::from(config.path()), Err(Error::Config(e)) if e == message)
+				);
+			}
+			Ok(())
+		}
+
 		#[test]
 		fn configure_works() -> Result<(), Error> {
 			let config = Builder::new().suffix(".toml").tempfile()?;
@@ -1918,6 +2895,23 @@ node_spawn_timeout = 300
 		}
 	}
 
+	#[test]
+	fn hrmp_channel_from_str_works() {
+		assert_eq!(
+			"2000:1000".parse::<HrmpChannel>().unwrap(),
+			HrmpChannel { sender: 2000, recipient: 1000 }
+		);
+		for channel in ["2000", "2000:", "a:1000", "2000:1000:1"] {
+			assert!(matches!(channel.parse::<HrmpChannel>(), Err(Error::Config(..))));
+		}
+	}
+
+	#[test]
+	fn readiness_metric_works() {
+		assert_eq!(Readiness::Best(2).metric(), ("block_height{status=\"best\"}", 2));
+		assert_eq!(Readiness::Finalized(1).metric(), ("block_height{status=\"finalized\"}", 1));
+	}
+
 	#[test]
 	fn resolve_manifest_works() -> Result<()> {
 		let current_dir = current_dir()?;
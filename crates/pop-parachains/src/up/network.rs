@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::errors::Error;
+use serde::{Deserialize, Serialize};
+use std::{
+	fs::{read_to_string, write},
+	path::{Path, PathBuf},
+};
+use zombienet_sdk::{LocalFileSystem, Network, NetworkNode};
+
+/// The name of the file, within the base directory of a network, describing the network.
+pub const NETWORK_JSON: &str = "network.json";
+
+/// A machine-readable description of a launched network.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NetworkInfo {
+	/// The base directory of the network, containing the data and logs of each node.
+	pub base_dir: PathBuf,
+	/// The relay chain of the network.
+	pub relay_chain: RelayChainInfo,
+	/// The parachains of the network, ordered by identifier.
+	pub parachains: Vec<ParachainInfo>,
+}
+
+impl NetworkInfo {
+	/// Describes a launched network.
+	///
+	/// # Arguments
+	/// * `network` - The running network.
+	pub fn new(network: &Network<LocalFileSystem>) -> Self {
+		let base_dir = PathBuf::from(network.base_dir().unwrap_or_default());
+		let nodes = |nodes: Vec<&NetworkNode>| {
+			let mut nodes: Vec<_> =
+				nodes.into_iter().map(|n| NodeInfo::new(n, &base_dir)).collect();
+			nodes.sort_by(|a, b| a.name.cmp(&b.name));
+			nodes
+		};
+		let relay_chain = network.relaychain();
+		let relay_chain = RelayChainInfo {
+			chain: relay_chain.chain().to_string(),
+			nodes: nodes(relay_chain.nodes()),
+		};
+		let mut parachains: Vec<_> = network
+			.parachains()
+			.into_iter()
+			.map(|p| ParachainInfo {
+				para_id: p.para_id(),
+				chain_id: p.chain_id().map(String::from),
+				collators: nodes(p.collators()),
+			})
+			.collect();
+		parachains.sort_by_key(|p| p.para_id);
+		Self { base_dir, relay_chain, parachains }
+	}
+
+	/// Loads the description of a network from its base directory.
+	///
+	/// # Arguments
+	/// * `base_dir` - The base directory of the network.
+	pub fn load(base_dir: &Path) -> Result<Self, Error> {
+		Ok(serde_json::from_str(&read_to_string(base_dir.join(NETWORK_JSON))?)?)
+	}
+
+	/// Writes the description of the network to its base directory, returning the path of the
+	/// file written.
+	pub fn save(&self) -> Result<PathBuf, Error> {
+		let path = self.base_dir.join(NETWORK_JSON);
+		write(&path, serde_json::to_string_pretty(self)?)?;
+		Ok(path)
+	}
+
+	/// All nodes of the network.
+	pub fn nodes(&self) -> impl Iterator<Item = &NodeInfo> {
+		self.relay_chain
+			.nodes
+			.iter()
+			.chain(self.parachains.iter().flat_map(|p| p.collators.iter()))
+	}
+}
+
+/// A description of the relay chain of a launched network.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RelayChainInfo {
+	/// The chain of the relay chain.
+	pub chain: String,
+	/// The nodes of the relay chain.
+	pub nodes: Vec<NodeInfo>,
+}
+
+/// A description of a parachain of a launched network.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ParachainInfo {
+	/// The identifier of the parachain.
+	pub para_id: u32,
+	/// The chain identifier of the parachain, if specified.
+	pub chain_id: Option<String>,
+	/// The collators of the parachain.
+	pub collators: Vec<NodeInfo>,
+}
+
+/// A description of a node of a launched network.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NodeInfo {
+	/// The name of the node.
+	pub name: String,
+	/// The websocket endpoint of the node.
+	pub ws_uri: String,
+	/// The RPC port of the node.
+	pub rpc_port: Option<u16>,
+	/// The Prometheus port of the node.
+	pub prometheus_port: Option<u16>,
+	/// The peer-to-peer port of the node.
+	pub p2p_port: Option<u16>,
+	/// The path of the log file of the node.
+	pub log_path: PathBuf,
+	/// The command used to launch the node.
+	pub command: String,
+	/// The arguments used to launch the node.
+	pub args: Vec<String>,
+}
+
+impl NodeInfo {
+	fn new(node: &NetworkNode, base_dir: &Path) -> Self {
+		let name = node.name();
+		let args = node.args();
+		let port = |name: &str| arg(&args, name).and_then(|p| p.parse().ok());
+		Self {
+			name: name.to_string(),
+			ws_uri: node.ws_uri().to_string(),
+			rpc_port: port("--rpc-port"),
+			prometheus_port: port("--prometheus-port"),
+			p2p_port: port("--port"),
+			log_path: base_dir.join(name).join(format!("{name}.log")),
+			command: node.spec().command().to_string(),
+			args: args.iter().map(|a| a.to_string()).collect(),
+		}
+	}
+}
+
+/// The value of the specified argument within a node command line, if present.
+///
+/// # Arguments
+/// * `args` - The arguments of the node.
+/// * `name` - The name of the argument.
+pub(super) fn arg<'a>(args: &[&'a str], name: &str) -> Option<&'a str> {
+	args.iter().position(|a| *a == name).and_then(|i| args.get(i + 1)).copied()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use tempfile::tempdir;
+
+	fn node(name: &str, base_dir: &Path) -> NodeInfo {
+		NodeInfo {
+			name: name.to_string(),
+			ws_uri: "ws://127.0.0.1:9944".to_string(),
+			rpc_port: Some(9944),
+			prometheus_port: Some(9615),
+			p2p_port: Some(30333),
+			log_path: base_dir.join(name).join(format!("{name}.log")),
+			command: "polkadot".to_string(),
+			args: vec!["--rpc-port".to_string(), "9944".to_string()],
+		}
+	}
+
+	#[test]
+	fn arg_works() {
+		let args = ["--chain", "paseo-local", "--rpc-port", "9944", "--validator"];
+		assert_eq!(arg(&args, "--rpc-port"), Some("9944"));
+		assert_eq!(arg(&args, "--chain"), Some("paseo-local"));
+		assert_eq!(arg(&args, "--validator"), None);
+		assert_eq!(arg(&args, "--port"), None);
+	}
+
+	#[test]
+	fn save_and_load_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let base_dir = temp_dir.path();
+		let info = NetworkInfo {
+			base_dir: base_dir.to_path_buf(),
+			relay_chain: RelayChainInfo {
+				chain: "paseo-local".to_string(),
+				nodes: vec![node("alice", base_dir), node("bob", base_dir)],
+			},
+			parachains: vec![ParachainInfo {
+				para_id: 2000,
+				chain_id: Some("pop-devnet".to_string()),
+				collators: vec![node("collator-01", base_dir)],
+			}],
+		};
+		assert_eq!(info.save()?, base_dir.join(NETWORK_JSON));
+		assert_eq!(NetworkInfo::load(base_dir)?, info);
+		assert_eq!(
+			info.nodes().map(|n| n.name.as_str()).collect::<Vec<_>>(),
+			["alice", "bob", "collator-01"]
+		);
+		Ok(())
+	}
+}
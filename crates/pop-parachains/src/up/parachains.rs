@@ -22,7 +22,8 @@ pub(super) enum Parachain {
 		Repository = "https://github.com/r0gue-io/polkadot",
 		Binary = "polkadot-parachain",
 		TagFormat = "polkadot-{tag}",
-		Fallback = "v1.12.0"
+		Fallback = "v1.12.0",
+		Checksum = "sha256"
 	))]
 	System,
 	/// Pop Network makes it easy for smart contract developers to use the power of Polkadot.
@@ -30,7 +31,8 @@ pub(super) enum Parachain {
 		Repository = "https://github.com/r0gue-io/pop-node",
 		Binary = "pop-node",
 		Prerelease = "false",
-		Fallback = "testnet-v0.4.1"
+		Fallback = "testnet-v0.4.1",
+		Checksum = "sha256"
 	))]
 	Pop,
 }
@@ -53,6 +55,7 @@ impl TryInto for Parachain {
 					tag_format: self.tag_format().map(|t| t.into()),
 					archive: format!("{}-{}.tar.gz", self.binary(), target()?),
 					contents: vec![(self.binary(), None)],
+					checksum: self.checksum(),
 					latest,
 				})
 			},
@@ -69,7 +72,7 @@ impl pop_common::sourcing::traits::Source for Parachain {}
 /// * `command` - The command specified.
 /// * `version` - The version of the parachain binary to be used.
 /// * `runtime_version` - The version of the runtime to be used.
-/// * `relay_chain_version` - The version of the relay chain binary being used.
+/// * `relay_chain_version` - If known, the version of the relay chain binary being used.
 /// * `chain` - The chain specified.
 /// * `cache` - The cache to be used.
 pub(super) async fn system(
@@ -77,7 +80,7 @@ pub(super) async fn system(
 	command: &str,
 	version: Option<&str>,
 	runtime_version: Option<&str>,
-	relay_chain_version: &str,
+	relay_chain_version: Option<&str>,
 	chain: Option<&str>,
 	cache: &Path,
 ) -> Result<Option<super::Parachain>, Error> {
@@ -92,7 +95,7 @@ pub(super) async fn system(
 			// Default to same version as relay chain when not explicitly specified
 			// Only set latest when caller has not explicitly specified a version to use
 			(
-				Some(relay_chain_version.to_string()),
+				relay_chain_version.map(|v| v.to_string()),
 				parse_latest_tag(para.releases().await?.iter().map(|s| s.as_str()).collect()),
 			)
 		},
@@ -149,6 +152,7 @@ pub(super) async fn from(
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use pop_common::sourcing::Checksum;
 	use std::path::PathBuf;
 	use tempfile::tempdir;
 
@@ -159,7 +163,7 @@ mod tests {
 			"polkadot",
 			None,
 			None,
-			"v1.12.0",
+			Some("v1.12.0"),
 			Some("asset-hub-paseo-local"),
 			tempdir()?.path()
 		)
@@ -176,7 +180,7 @@ mod tests {
 
 		let temp_dir = tempdir()?;
 		let parachain =
-			system(para_id, expected.binary(), None, None, version, None, temp_dir.path())
+			system(para_id, expected.binary(), None, None, Some(version), None, temp_dir.path())
 				.await?
 				.unwrap();
 		assert_eq!(para_id, parachain.id);
@@ -188,6 +192,7 @@ mod tests {
 					tag_format: Some("polkadot-{tag}".to_string()),
 					archive: format!("{name}-{}.tar.gz", target()?),
 					contents: vec![(expected.binary(), None)],
+					checksum: Some(Checksum::Sha256File),
 					latest: parachain.binary.latest().map(|l| l.to_string()),
 				}) && cache == temp_dir.path()
 		));
@@ -201,10 +206,17 @@ mod tests {
 		let para_id = 1000;
 
 		let temp_dir = tempdir()?;
-		let parachain =
-			system(para_id, expected.binary(), Some(version), None, version, None, temp_dir.path())
-				.await?
-				.unwrap();
+		let parachain = system(
+			para_id,
+			expected.binary(),
+			Some(version),
+			None,
+			Some(version),
+			None,
+			temp_dir.path(),
+		)
+		.await?
+		.unwrap();
 		assert_eq!(para_id, parachain.id);
 		assert!(matches!(parachain.binary, Binary::Source { name, source, cache }
 			if name == expected.binary() && source == Source::GitHub(ReleaseArchive {
@@ -214,6 +226,7 @@ mod tests {
 					tag_format: Some("polkadot-{tag}".to_string()),
 					archive: format!("{name}-{}.tar.gz", target()?),
 					contents: vec![(expected.binary(), None)],
+					checksum: Some(Checksum::Sha256File),
 					latest: parachain.binary.latest().map(|l| l.to_string()),
 				}) && cache == temp_dir.path()
 		));
@@ -232,7 +245,7 @@ mod tests {
 			expected.binary(),
 			None,
 			Some(runtime_version),
-			"v.13.0",
+			Some("v.13.0"),
 			Some("asset-hub-paseo-local"),
 			temp_dir.path(),
 		)
@@ -249,6 +262,7 @@ mod tests {
 					tag_format: None,
 					archive: format!("chain-spec-generator-{}.tar.gz", target()?),
 					contents: [("chain-spec-generator", Some("paseo-chain-spec-generator".to_string()))].to_vec(),
+					checksum: Some(Checksum::Sha256File),
 					latest: chain_spec_generator.latest().map(|l| l.to_string()),
 				}) && cache == temp_dir.path()
 		));
@@ -274,6 +288,7 @@ mod tests {
 					tag_format: None,
 					archive: format!("{name}-{}.tar.gz", target()?),
 					contents: vec![(expected.binary(), None)],
+					checksum: Some(Checksum::Sha256File),
 					latest: parachain.binary.latest().map(|l| l.to_string()),
 				}) && cache == temp_dir.path()
 		));
@@ -0,0 +1,187 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::check::{relay_chains, valid_relay_chain};
+use crate::{binary_path, errors::Error, generate_plain_chain_spec, ChainSpec};
+use pop_common::Profile;
+use std::{
+	fs::write,
+	path::{Path, PathBuf},
+};
+use tempfile::NamedTempFile;
+use toml_edit::Value;
+
+/// The parachain identifier used when none is specified by the chain specification.
+const DEFAULT_PARA_ID: u32 = 2000;
+/// The names of the relay chain validators, for which development keys are well-known.
+const VALIDATORS: [&str; 6] = ["alice", "bob", "charlie", "dave", "eve", "ferdie"];
+
+/// Generates a default network configuration for a parachain project, launching the node built
+/// from the project with a local relay chain. The configuration is written to the target
+/// directory containing the node binary, with its path returned.
+///
+/// # Arguments
+/// * `project` - The path to the parachain project.
+/// * `relay_chain` - The relay chain to be launched (e.g. "paseo-local").
+/// * `validators` - The number of relay chain validators.
+/// * `collators` - The number of parachain collators.
+pub fn generate_network_config(
+	project: &Path,
+	relay_chain: &str,
+	validators: u8,
+	collators: u8,
+) -> Result<PathBuf, Error> {
+	if !valid_relay_chain(relay_chain) {
+		return Err(Error::Config(format!(
+			"unknown relay chain `{relay_chain}`: expected one of {}",
+			relay_chains().join(", ")
+		)));
+	}
+	// Locate the node binary, preferring a release build
+	let node_path = project.join("node");
+	let binary = match binary_path(&Profile::Release.target_directory(project), &node_path) {
+		Ok(binary) => binary,
+		Err(_) => binary_path(&Profile::Debug.target_directory(project), &node_path)?,
+	};
+	let para_id = para_id(&binary)?;
+	let path = binary.with_file_name("network.toml");
+	write(&path, network_config(&binary, para_id, relay_chain, validators, collators))?;
+	Ok(path)
+}
+
+/// Reads the parachain identifier from the chain specification of a node, defaulting to 2000 if
+/// not specified.
+///
+/// # Arguments
+/// * `binary` - The path to the node binary.
+fn para_id(binary: &Path) -> Result<u32, Error> {
+	let chain_spec = NamedTempFile::new()?;
+	generate_plain_chain_spec(binary, chain_spec.path(), false)?;
+	Ok(ChainSpec::from(chain_spec.path())?
+		.get_parachain_id()
+		.and_then(|id| u32::try_from(id).ok())
+		.unwrap_or(DEFAULT_PARA_ID))
+}
+
+/// Renders a network configuration launching a parachain with a local relay chain.
+///
+/// # Arguments
+/// * `command` - The path to the parachain node binary.
+/// * `para_id` - The parachain identifier.
+/// * `relay_chain` - The relay chain to be launched.
+/// * `validators` - The number of relay chain validators.
+/// * `collators` - The number of parachain collators.
+fn network_config(
+	command: &Path,
+	para_id: u32,
+	relay_chain: &str,
+	validators: u8,
+	collators: u8,
+) -> String {
+	let mut config = format!("[relaychain]\nchain = {}\n", Value::from(relay_chain));
+	for i in 0..validators as usize {
+		let name = VALIDATORS.get(i).map_or(format!("validator-{:02}", i + 1), |n| n.to_string());
+		config.push_str(&format!("\n[[relaychain.nodes]]\nname = \"{name}\"\nvalidator = true\n"));
+	}
+	config.push_str(&format!(
+		"\n[[parachains]]\nid = {para_id}\ndefault_command = {}\n",
+		Value::from(command.display().to_string())
+	));
+	for i in 1..=collators {
+		config.push_str(&format!("\n[[parachains.collators]]\nname = \"collator-{i:02}\"\n"));
+	}
+	config
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use std::{fs, os::unix::fs::PermissionsExt};
+	use tempfile::tempdir;
+
+	// Creates a project with a mock node binary, which outputs a chain specification.
+	fn project(path: &Path, profile: Profile, para_id: Option<u32>) -> Result<PathBuf> {
+		fs::create_dir_all(path.join("node"))?;
+		fs::write(
+			path.join("node/Cargo.toml"),
+			"[package]\nname = \"parachain-template-node\"\nversion = \"0.1.0\"\n",
+		)?;
+		let target_dir = profile.target_directory(path);
+		fs::create_dir_all(&target_dir)?;
+		let binary = target_dir.join("parachain-template-node");
+		let chain_spec = para_id.map_or("{}".to_string(), |id| format!("{{\"para_id\": {id}}}"));
+		fs::write(
+			&binary,
+			format!("#!/bin/sh\n[ \"$2\" = \"--help\" ] && exit 0\necho '{chain_spec}'\n"),
+		)?;
+		fs::set_permissions(&binary, fs::Permissions::from_mode(0o755))?;
+		Ok(binary)
+	}
+
+	#[test]
+	fn generate_network_config_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let binary = project(temp_dir.path(), Profile::Release, Some(3000))?;
+		let path = generate_network_config(temp_dir.path(), "paseo-local", 2, 1)?;
+		assert_eq!(path, binary.with_file_name("network.toml"));
+		assert_eq!(
+			fs::read_to_string(path)?,
+			format!(
+				r#"[relaychain]
+chain = "paseo-local"
+
+[[relaychain.nodes]]
+name = "alice"
+validator = true
+
+[[relaychain.nodes]]
+name = "bob"
+validator = true
+
+[[parachains]]
+id = 3000
+default_command = "{}"
+
+[[parachains.collators]]
+name = "collator-01"
+"#,
+				binary.display()
+			)
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn generate_network_config_uses_debug_binary_and_default_para_id() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let binary = project(temp_dir.path(), Profile::Debug, None)?;
+		let path = generate_network_config(temp_dir.path(), "kusama-local", 1, 1)?;
+		let config = fs::read_to_string(path)?;
+		assert!(config.contains("chain = \"kusama-local\""));
+		assert!(config.contains(&format!("id = 2000\ndefault_command = \"{}\"", binary.display())));
+		Ok(())
+	}
+
+	#[test]
+	fn generate_network_config_fails_when_invalid() -> Result<()> {
+		let temp_dir = tempdir()?;
+		assert!(matches!(
+			generate_network_config(temp_dir.path(), "paseo", 2, 1),
+			Err(Error::Config(..))
+		));
+		assert!(generate_network_config(temp_dir.path(), "paseo-local", 2, 1).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn network_config_names_nodes() {
+		let config = network_config(Path::new("./node"), 2000, "paseo-local", 7, 3);
+		for name in VALIDATORS.iter().chain(&["validator-07"]) {
+			assert!(config.contains(&format!("name = \"{name}\"\nvalidator = true")));
+		}
+		for name in ["collator-01", "collator-02", "collator-03"] {
+			assert!(config.contains(&format!("name = \"{name}\"")));
+		}
+		assert!(!config.contains("validator-08"));
+	}
+}
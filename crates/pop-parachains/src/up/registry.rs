@@ -0,0 +1,434 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::network::arg;
+use crate::errors::Error;
+use duct::cmd;
+use serde::{Deserialize, Serialize};
+use std::{
+	fs::{create_dir_all, read_dir, read_to_string, remove_file, rename, write},
+	path::{Path, PathBuf},
+	time::{SystemTime, UNIX_EPOCH},
+};
+use zombienet_sdk::{LocalFileSystem, Network, NetworkNode};
+
+/// The name of the directory, within the pop data directory, containing the registry.
+const NETWORKS: &str = "networks";
+
+/// A registry of the networks left running in the background, persisted as a file per network.
+pub struct Registry {
+	/// The location of the registry.
+	path: PathBuf,
+}
+
+impl Registry {
+	/// Opens the registry within the specified data directory.
+	///
+	/// # Arguments
+	/// * `data_dir` - The pop data directory.
+	pub fn new(data_dir: &Path) -> Self {
+		Self { path: data_dir.join(NETWORKS) }
+	}
+
+	/// The location of the registry.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	/// The registered network with the specified name, if any.
+	///
+	/// # Arguments
+	/// * `name` - The name of the network.
+	pub fn get(&self, name: &str) -> Result<Option<NetworkEntry>, Error> {
+		let path = self.entry(name)?;
+		if !path.exists() {
+			return Ok(None);
+		}
+		Ok(Some(serde_json::from_str(&read_to_string(path)?)?))
+	}
+
+	/// All registered networks, ordered by name.
+	pub fn list(&self) -> Result<Vec<NetworkEntry>, Error> {
+		if !self.path.exists() {
+			return Ok(vec![]);
+		}
+		let mut networks = Vec::new();
+		for entry in read_dir(&self.path)? {
+			let path = entry?.path();
+			if path.extension().is_some_and(|e| e == "json") {
+				networks.push(serde_json::from_str::<NetworkEntry>(&read_to_string(path)?)?);
+			}
+		}
+		networks.sort_by(|a, b| a.name.cmp(&b.name));
+		Ok(networks)
+	}
+
+	/// Registers a network, replacing any existing registration with the same name.
+	///
+	/// # Arguments
+	/// * `network` - The network to be registered.
+	pub fn register(&self, network: &NetworkEntry) -> Result<(), Error> {
+		let path = self.entry(&network.name)?;
+		create_dir_all(&self.path)?;
+		// Write atomically, as the registry may be read concurrently
+		let temp = path.with_extension("tmp");
+		write(&temp, serde_json::to_string_pretty(network)?)?;
+		rename(temp, path)?;
+		Ok(())
+	}
+
+	/// Removes the registration of a network, returning whether it was registered.
+	///
+	/// # Arguments
+	/// * `name` - The name of the network.
+	pub fn remove(&self, name: &str) -> Result<bool, Error> {
+		let path = self.entry(name)?;
+		if !path.exists() {
+			return Ok(false);
+		}
+		remove_file(path)?;
+		Ok(true)
+	}
+
+	/// The location of the log file of the process supervising the specified network.
+	///
+	/// # Arguments
+	/// * `name` - The name of the network.
+	pub fn log(&self, name: &str) -> Result<PathBuf, Error> {
+		Ok(self.entry(name)?.with_extension("log"))
+	}
+
+	// The location of the registration of the specified network.
+	fn entry(&self, name: &str) -> Result<PathBuf, Error> {
+		validate_name("network", name)?;
+		Ok(self.path.join(format!("{name}.json")))
+	}
+}
+
+/// Ensures a name can be safely used as a file name within a directory: it must be non-empty,
+/// contain only alphanumeric characters, `-`, `_` and `.`, and not start with `.`.
+///
+/// # Arguments
+/// * `kind` - The kind of item being named, used within the error.
+/// * `name` - The name.
+pub(super) fn validate_name(kind: &str, name: &str) -> Result<(), Error> {
+	if name.is_empty() ||
+		!name
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.') ||
+		name.starts_with('.')
+	{
+		return Err(Error::Config(format!(
+			"invalid {kind} name `{name}`: only alphanumeric characters, `-`, `_` and `.` are supported"
+		)));
+	}
+	Ok(())
+}
+
+/// A network running in the background.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NetworkEntry {
+	/// The name of the network.
+	pub name: String,
+	/// The identifier of the process supervising the network.
+	pub pid: u32,
+	/// The network configuration file used to launch the network.
+	pub config: PathBuf,
+	/// The base directory of the network, containing the data and logs of each node.
+	pub base_dir: PathBuf,
+	/// When the network was launched, in seconds since the Unix epoch.
+	pub started_at: u64,
+	/// The nodes of the network.
+	pub nodes: Vec<NodeEntry>,
+}
+
+impl NetworkEntry {
+	/// Describes a network supervised by the current process.
+	///
+	/// # Arguments
+	/// * `name` - The name of the network.
+	/// * `config` - The network configuration file used to launch the network.
+	/// * `network` - The running network.
+	pub fn new(name: &str, config: &Path, network: &Network<LocalFileSystem>) -> Self {
+		let processes = processes();
+		let relay_chain = network.relaychain();
+		let mut nodes: Vec<_> = relay_chain
+			.nodes()
+			.into_iter()
+			.map(|node| NodeEntry::new(node, relay_chain.chain(), &processes))
+			.collect();
+		let mut parachains = network.parachains();
+		parachains.sort_by_key(|p| p.para_id());
+		for parachain in parachains {
+			let chain = parachain
+				.chain_id()
+				.map_or_else(|| parachain.para_id().to_string(), String::from);
+			nodes.extend(
+				parachain.collators().into_iter().map(|n| NodeEntry::new(n, &chain, &processes)),
+			);
+		}
+		Self {
+			name: name.to_string(),
+			pid: std::process::id(),
+			config: config.to_path_buf(),
+			base_dir: network.base_dir().map(PathBuf::from).unwrap_or_default(),
+			started_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+			nodes,
+		}
+	}
+
+	/// Whether the process supervising the network is still running. The process is identified
+	/// by both its identifier and its command line, which must be that of the current executable
+	/// supervising the network, so that a reused identifier is not mistaken for the supervisor.
+	pub fn running(&self) -> bool {
+		let Ok(exe) = std::env::current_exe() else {
+			return false;
+		};
+		processes()
+			.iter()
+			.any(|(pid, command)| *pid == self.pid && supervises(command, &exe, &self.name))
+	}
+
+	/// Signals the process supervising the network to tear it down, provided it is still running.
+	pub fn stop(&self) -> Result<(), Error> {
+		if self.running() {
+			terminate(self.pid)?;
+		}
+		Ok(())
+	}
+
+	/// Terminates any nodes of the network which are still running, for use when the supervising
+	/// process is no longer able to do so. A node is only terminated when the command line of the
+	/// process still specifies its recorded base path, otherwise the node is considered stale.
+	pub fn kill(&self) -> Result<(), Error> {
+		let processes = processes();
+		for node in &self.nodes {
+			let (Some(pid), Some(base_path)) = (node.pid, &node.base_path) else {
+				continue;
+			};
+			let base_path = base_path.to_string_lossy();
+			if processes
+				.iter()
+				.any(|(p, command)| *p == pid && has_arg(command, "--base-path", &base_path))
+			{
+				terminate(pid)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// A node of a network running in the background.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NodeEntry {
+	/// The name of the node.
+	pub name: String,
+	/// The chain of the node - i.e. the relay chain, or the chain/identifier of a parachain.
+	pub chain: String,
+	/// The websocket endpoint of the node.
+	pub ws_uri: String,
+	/// If known, the identifier of the node process.
+	pub pid: Option<u32>,
+	/// If known, the base path of the node, used to identify the node process.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub base_path: Option<PathBuf>,
+}
+
+impl NodeEntry {
+	fn new(node: &NetworkNode, chain: &str, processes: &[(u32, String)]) -> Self {
+		// Nodes are identified by their unique base path
+		let base_path = arg(&node.args(), "--base-path");
+		let pid = base_path.and_then(|base_path| {
+			processes
+				.iter()
+				.find(|(_, command)| has_arg(command, "--base-path", base_path))
+				.map(|(p, _)| *p)
+		});
+		Self {
+			name: node.name().to_string(),
+			chain: chain.to_string(),
+			ws_uri: node.ws_uri().to_string(),
+			pid,
+			base_path: base_path.map(PathBuf::from),
+		}
+	}
+}
+
+// The identifiers and command lines of all running processes.
+fn processes() -> Vec<(u32, String)> {
+	let Ok(output) = cmd("ps", ["-A", "-o", "pid=", "-o", "args="]).stderr_null().read() else {
+		return vec![];
+	};
+	parse_processes(&output)
+}
+
+// Parses the output of `ps`, listing process identifiers and command lines.
+fn parse_processes(output: &str) -> Vec<(u32, String)> {
+	output
+		.lines()
+		.filter_map(|line| {
+			let (pid, command) = line.trim().split_once(char::is_whitespace)?;
+			Some((pid.parse().ok()?, command.trim().to_string()))
+		})
+		.collect()
+}
+
+// Whether a command line is that of the specified executable supervising the named network.
+fn supervises(command: &str, exe: &Path, name: &str) -> bool {
+	let Some(args) = exe.to_str().and_then(|exe| command.strip_prefix(exe)) else {
+		return false;
+	};
+	(args.is_empty() || args.starts_with(char::is_whitespace)) &&
+		args.split_whitespace().any(|a| a == "--supervise") &&
+		has_arg(args, "--name", name)
+}
+
+// Whether a command line specifies an argument with exactly the specified value, either as
+// `<name> <value>` or `<name>=<value>`.
+fn has_arg(command: &str, name: &str, value: &str) -> bool {
+	let args: Vec<_> = command.split_whitespace().collect();
+	args.windows(2).any(|a| a[0] == name && a[1] == value) ||
+		args.iter()
+			.any(|a| a.strip_prefix(name).and_then(|a| a.strip_prefix('=')) == Some(value))
+}
+
+// Signals the process with the specified identifier to terminate.
+fn terminate(pid: u32) -> Result<(), Error> {
+	cmd("kill", ["-TERM", &pid.to_string()]).stderr_null().run()?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use tempfile::tempdir;
+
+	fn entry(name: &str) -> NetworkEntry {
+		NetworkEntry {
+			name: name.to_string(),
+			pid: u32::MAX >> 1,
+			config: PathBuf::from("network.toml"),
+			base_dir: PathBuf::from("/tmp/zombie-1"),
+			started_at: 0,
+			nodes: vec![NodeEntry {
+				name: "alice".to_string(),
+				chain: "paseo-local".to_string(),
+				ws_uri: "ws://127.0.0.1:9944".to_string(),
+				pid: None,
+				base_path: None,
+			}],
+		}
+	}
+
+	// Simulates the process supervising a network, with the command line of the current
+	// executable.
+	fn supervisor(name: &str) -> Result<std::process::Child> {
+		use std::os::unix::process::CommandExt;
+		Ok(std::process::Command::new("sh")
+			.arg0(std::env::current_exe()?)
+			.args(["-c", "while :; do sleep 1; done", "--supervise", "--name", name])
+			.spawn()?)
+	}
+
+	#[test]
+	fn registry_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let registry = Registry::new(temp_dir.path());
+		assert_eq!(registry.path(), temp_dir.path().join("networks"));
+		assert!(registry.list()?.is_empty());
+		assert_eq!(registry.get("pop")?, None);
+
+		registry.register(&entry("pop"))?;
+		registry.register(&entry("asset-hub"))?;
+		assert_eq!(registry.get("pop")?, Some(entry("pop")));
+		assert_eq!(registry.list()?, [entry("asset-hub"), entry("pop")]);
+		assert_eq!(registry.log("pop")?, registry.path().join("pop.log"));
+
+		assert!(registry.remove("pop")?);
+		assert!(!registry.remove("pop")?);
+		assert_eq!(registry.list()?, [entry("asset-hub")]);
+		Ok(())
+	}
+
+	#[test]
+	fn registry_rejects_invalid_names() {
+		let registry = Registry::new(Path::new("."));
+		for name in ["", "../pop", ".pop", "pop network"] {
+			assert!(matches!(registry.get(name), Err(Error::Config(..))), "{name}");
+		}
+	}
+
+	#[test]
+	fn running_works() -> Result<()> {
+		assert!(!entry("pop").running());
+		// A process is only considered the supervisor when its command line matches
+		let mut supervisor = supervisor("pop")?;
+		let network = NetworkEntry { pid: supervisor.id(), ..entry("pop") };
+		assert!(network.running());
+		assert!(!NetworkEntry { name: "asset-hub".into(), ..network.clone() }.running());
+		let mut other = std::process::Command::new("sleep").arg("60").spawn()?;
+		assert!(!NetworkEntry { pid: other.id(), ..network.clone() }.running());
+		// Only a running supervisor is signalled
+		entry("pop").stop()?;
+		network.stop()?;
+		assert!(!supervisor.wait()?.success());
+		other.kill()?;
+		other.wait()?;
+		Ok(())
+	}
+
+	#[test]
+	fn kill_ignores_stale_nodes() -> Result<()> {
+		let mut other = std::process::Command::new("sleep").arg("60").spawn()?;
+		let mut network = entry("pop");
+		network.nodes[0].pid = Some(other.id());
+		network.nodes[0].base_path = Some(PathBuf::from("/tmp/zombie-1/alice"));
+		network.kill()?;
+		assert!(other.try_wait()?.is_none());
+		other.kill()?;
+		other.wait()?;
+		Ok(())
+	}
+
+	#[test]
+	fn supervises_works() {
+		let exe = Path::new("/usr/bin/pop");
+		for command in [
+			"/usr/bin/pop up parachain -f network.toml --detach --supervise --name pop",
+			"/usr/bin/pop up parachain --name=pop -f network.toml --supervise",
+		] {
+			assert!(supervises(command, exe, "pop"), "{command}");
+		}
+		for command in [
+			"/usr/bin/pop up parachain -f network.toml --detach --name pop",
+			"/usr/bin/pop up parachain --supervise --name pop2",
+			"/usr/bin/pop2 up parachain --supervise --name pop",
+			"/opt/pop up parachain --supervise --name pop",
+			"sleep 60",
+		] {
+			assert!(!supervises(command, exe, "pop"), "{command}");
+		}
+	}
+
+	#[test]
+	fn has_arg_works() {
+		let command = "polkadot --base-path /tmp/zombie-1/alice2 --chain=paseo-local";
+		assert!(has_arg(command, "--base-path", "/tmp/zombie-1/alice2"));
+		assert!(!has_arg(command, "--base-path", "/tmp/zombie-1/alice"));
+		assert!(has_arg(command, "--chain", "paseo-local"));
+		assert!(!has_arg(command, "--chain", "paseo"));
+	}
+
+	#[test]
+	fn parse_processes_works() {
+		let output =
+			"    1 /sbin/init\n  42 polkadot --base-path /tmp/zombie-1/alice/data\ninvalid\n";
+		assert_eq!(
+			parse_processes(output),
+			[
+				(1, "/sbin/init".to_string()),
+				(42, "polkadot --base-path /tmp/zombie-1/alice/data".to_string())
+			]
+		);
+	}
+}
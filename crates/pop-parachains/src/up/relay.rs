@@ -24,7 +24,8 @@ pub(super) enum RelayChain {
 		Repository = "https://github.com/r0gue-io/polkadot",
 		Binary = "polkadot",
 		TagFormat = "polkadot-{tag}",
-		Fallback = "v1.12.0"
+		Fallback = "v1.12.0",
+		Checksum = "sha256"
 	))]
 	Polkadot,
 }
@@ -50,6 +51,7 @@ impl TryInto for &RelayChain {
 						.chain(self.workers())
 						.map(|n| (n, None))
 						.collect(),
+					checksum: self.checksum(),
 					latest,
 				})
 			},
@@ -129,6 +131,7 @@ pub(super) async fn from(
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use pop_common::sourcing::Checksum;
 	use tempfile::tempdir;
 
 	#[tokio::test]
@@ -145,6 +148,7 @@ mod tests {
 					tag_format: Some("polkadot-{tag}".to_string()),
 					archive: format!("{name}-{}.tar.gz", target()?),
 					contents: ["polkadot", "polkadot-execute-worker", "polkadot-prepare-worker"].map(|b| (b, None)).to_vec(),
+					checksum: Some(Checksum::Sha256File),
 					latest: relay.binary.latest().map(|l| l.to_string()),
 				}) && cache == temp_dir.path()
 		));
@@ -168,6 +172,7 @@ mod tests {
 					tag_format: None,
 					archive: format!("chain-spec-generator-{}.tar.gz", target()?),
 					contents: [("chain-spec-generator", Some("paseo-chain-spec-generator".to_string()))].to_vec(),
+					checksum: Some(Checksum::Sha256File),
 					latest: chain_spec_generator.latest().map(|l| l.to_string()),
 				}) && cache == temp_dir.path()
 		));
@@ -198,6 +203,7 @@ mod tests {
 					tag_format: Some("polkadot-{tag}".to_string()),
 					archive: format!("{name}-{}.tar.gz", target()?),
 					contents: ["polkadot", "polkadot-execute-worker", "polkadot-prepare-worker"].map(|b| (b, None)).to_vec(),
+					checksum: Some(Checksum::Sha256File),
 					latest: relay.binary.latest().map(|l| l.to_string()),
 				}) && cache == temp_dir.path()
 		));
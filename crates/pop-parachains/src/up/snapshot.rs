@@ -0,0 +1,299 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{
+	network::{arg, NetworkInfo, NodeInfo, NETWORK_JSON},
+	registry::validate_name,
+};
+use crate::errors::Error;
+use flate2::{write::GzEncoder, Compression};
+use std::{
+	fs::{copy, create_dir_all, remove_dir_all, rename, write, File},
+	path::{Path, PathBuf},
+};
+
+/// The name of the file, within a snapshot, containing the adapted network configuration.
+const NETWORK_TOML: &str = "network.toml";
+/// The directories, within the base directory of a node, containing its databases.
+const DATA_DIRS: [&str; 2] = ["data", "relay-data"];
+
+/// A snapshot of the state of a local network, from which the network can later be relaunched.
+///
+/// A snapshot is a directory containing the adapted network configuration, a description of
+/// the network, the raw chain specifications of the relay chain and each parachain, and an
+/// archive of the databases of each node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkSnapshot {
+	path: PathBuf,
+}
+
+impl NetworkSnapshot {
+	/// A named snapshot within the specified directory. The name must be non-empty, contain only
+	/// alphanumeric characters, `-`, `_` and `.`, and not start with `.`, so that the snapshot
+	/// cannot resolve to a location outside of the directory.
+	///
+	/// # Arguments
+	/// * `dir` - The directory used to store snapshots.
+	/// * `name` - The name of the snapshot.
+	pub fn new(dir: &Path, name: &str) -> Result<Self, Error> {
+		validate_name("snapshot", name)?;
+		Ok(Self { path: dir.join(name) })
+	}
+
+	/// The location of the snapshot.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	/// Whether the snapshot has been saved.
+	pub fn exists(&self) -> bool {
+		self.path.join(NETWORK_JSON).exists()
+	}
+
+	/// The description of the network at the time of the snapshot.
+	pub fn info(&self) -> Result<NetworkInfo, Error> {
+		NetworkInfo::load(&self.path)
+	}
+
+	/// The adapted network configuration at the time of the snapshot.
+	pub fn config(&self) -> PathBuf {
+		self.path.join(NETWORK_TOML)
+	}
+
+	/// The raw chain specification of the relay chain.
+	pub fn relay_chain_spec(&self, chain: &str) -> PathBuf {
+		self.path.join("specs").join(format!("{chain}.json"))
+	}
+
+	/// The raw chain specification of a parachain.
+	///
+	/// # Arguments
+	/// * `para_id` - The identifier of the parachain.
+	pub fn parachain_spec(&self, para_id: u32) -> PathBuf {
+		self.path.join("specs").join(format!("{para_id}.json"))
+	}
+
+	/// The archive of the databases of a node, in the format expected by `db_snapshot`.
+	///
+	/// # Arguments
+	/// * `name` - The name of the node.
+	pub fn node(&self, name: &str) -> PathBuf {
+		self.path.join("nodes").join(format!("{name}.tgz"))
+	}
+
+	/// Saves a snapshot of a network, replacing any existing snapshot of the same name. The nodes
+	/// of the network are expected to have been stopped, so that their databases are consistent.
+	///
+	/// The snapshot is first written to a sibling directory, so that any existing snapshot is
+	/// only replaced once the new snapshot has been saved successfully.
+	///
+	/// # Arguments
+	/// * `info` - The description of the network.
+	/// * `config` - The adapted network configuration used to launch the network.
+	pub fn save(&self, info: &NetworkInfo, config: &str) -> Result<(), Error> {
+		let staged = self.sibling("tmp");
+		if staged.path.exists() {
+			remove_dir_all(&staged.path)?;
+		}
+		if let Err(e) = staged.write(info, config, &self.path) {
+			let _ = remove_dir_all(&staged.path);
+			return Err(e);
+		}
+		if self.path.exists() {
+			let previous = self.sibling("old");
+			if previous.path.exists() {
+				remove_dir_all(&previous.path)?;
+			}
+			rename(&self.path, &previous.path)?;
+			rename(&staged.path, &self.path)?;
+			remove_dir_all(&previous.path)?;
+		} else {
+			rename(&staged.path, &self.path)?;
+		}
+		Ok(())
+	}
+
+	/// A hidden sibling of the snapshot, used whilst saving. Snapshot names cannot start with `.`,
+	/// so a sibling never clashes with another snapshot.
+	///
+	/// # Arguments
+	/// * `suffix` - The suffix of the sibling.
+	fn sibling(&self, suffix: &str) -> Self {
+		let name = self.path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+		Self { path: self.path.with_file_name(format!(".{name}.{suffix}")) }
+	}
+
+	/// Writes the contents of a snapshot of a network to the location of this snapshot.
+	///
+	/// # Arguments
+	/// * `info` - The description of the network.
+	/// * `config` - The adapted network configuration used to launch the network.
+	/// * `base_dir` - The location from which the snapshot will ultimately be used.
+	fn write(&self, info: &NetworkInfo, config: &str, base_dir: &Path) -> Result<(), Error> {
+		for dir in ["specs", "nodes"] {
+			create_dir_all(self.path.join(dir))?;
+		}
+		write(self.config(), config)?;
+		let info_at_base_dir = NetworkInfo { base_dir: base_dir.to_path_buf(), ..info.clone() };
+		write(self.path.join(NETWORK_JSON), serde_json::to_string_pretty(&info_at_base_dir)?)?;
+
+		// Chain specifications, as used by the nodes of each chain
+		let relay_chain = &info.relay_chain;
+		copy(chain_spec(&relay_chain.nodes)?, self.relay_chain_spec(&relay_chain.chain))?;
+		for parachain in &info.parachains {
+			copy(chain_spec(&parachain.collators)?, self.parachain_spec(parachain.para_id))?;
+		}
+
+		// Databases of each node
+		for node in info.nodes() {
+			let base_dir = info.base_dir.join(&node.name);
+			let archive = GzEncoder::new(File::create(self.node(&node.name))?, Compression::fast());
+			let mut archive = tar::Builder::new(archive);
+			for dir in DATA_DIRS.iter().filter(|d| base_dir.join(d).is_dir()) {
+				archive.append_dir_all(dir, base_dir.join(dir))?;
+			}
+			archive.into_inner()?.finish()?;
+		}
+		Ok(())
+	}
+}
+
+/// The chain specification used by a set of nodes, being the first specified by their command
+/// lines.
+///
+/// # Arguments
+/// * `nodes` - The nodes of a chain.
+fn chain_spec(nodes: &[NodeInfo]) -> Result<PathBuf, Error> {
+	nodes
+		.iter()
+		.find_map(|n| {
+			let args: Vec<_> = n.args.iter().map(String::as_str).collect();
+			arg(&args, "--chain").map(PathBuf::from)
+		})
+		.ok_or_else(|| {
+			Error::Config("the chain specification of a chain could not be found".into())
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::up::{ParachainInfo, RelayChainInfo};
+	use anyhow::Result;
+	use flate2::read::GzDecoder;
+	use std::fs::read_to_string;
+	use tempfile::tempdir;
+
+	fn node(name: &str, base_dir: &Path, chain_specs: &[&Path]) -> Result<NodeInfo> {
+		// Simulate the databases of the node
+		let data = base_dir.join(name).join("data/chains/local_testnet/db");
+		create_dir_all(&data)?;
+		write(data.join("CURRENT"), name)?;
+		let mut args = vec![];
+		for spec in chain_specs {
+			args.extend(["--chain".to_string(), spec.display().to_string()]);
+		}
+		Ok(NodeInfo {
+			name: name.to_string(),
+			ws_uri: "ws://127.0.0.1:9944".to_string(),
+			rpc_port: None,
+			prometheus_port: None,
+			p2p_port: None,
+			log_path: base_dir.join(name).join(format!("{name}.log")),
+			command: "polkadot".to_string(),
+			args,
+		})
+	}
+
+	#[test]
+	fn save_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let base_dir = temp_dir.path().join("network");
+		create_dir_all(&base_dir)?;
+		let relay_chain_spec = base_dir.join("paseo-local.json");
+		write(&relay_chain_spec, "relay")?;
+		let parachain_spec = base_dir.join("2000.json");
+		write(&parachain_spec, "para")?;
+		let info = NetworkInfo {
+			base_dir: base_dir.clone(),
+			relay_chain: RelayChainInfo {
+				chain: "paseo-local".to_string(),
+				nodes: vec![node("alice", &base_dir, &[&relay_chain_spec])?],
+			},
+			parachains: vec![ParachainInfo {
+				para_id: 2000,
+				chain_id: None,
+				collators: vec![node(
+					"collator-01",
+					&base_dir,
+					&[&parachain_spec, &relay_chain_spec],
+				)?],
+			}],
+		};
+
+		let snapshot = NetworkSnapshot::new(&temp_dir.path().join("snapshots"), "pop")?;
+		assert!(!snapshot.exists());
+		snapshot.save(&info, "[relaychain]")?;
+		assert!(snapshot.exists());
+		assert_eq!(read_to_string(snapshot.config())?, "[relaychain]");
+		assert_eq!(snapshot.info()?.nodes().collect::<Vec<_>>(), info.nodes().collect::<Vec<_>>());
+		assert_eq!(read_to_string(snapshot.relay_chain_spec("paseo-local"))?, "relay");
+		assert_eq!(read_to_string(snapshot.parachain_spec(2000))?, "para");
+		for name in ["alice", "collator-01"] {
+			let unpacked = temp_dir.path().join("unpacked").join(name);
+			tar::Archive::new(GzDecoder::new(File::open(snapshot.node(name))?))
+				.unpack(&unpacked)?;
+			assert_eq!(
+				read_to_string(unpacked.join("data/chains/local_testnet/db/CURRENT"))?,
+				name
+			);
+		}
+
+		assert_eq!(snapshot.info()?.base_dir, snapshot.path());
+
+		// Saving again replaces the existing snapshot
+		snapshot.save(&info, "[settings]")?;
+		assert_eq!(read_to_string(snapshot.config())?, "[settings]");
+
+		// A failure to save leaves the existing snapshot intact
+		let mut invalid = info.clone();
+		invalid.parachains[0].collators[0].args.clear();
+		assert!(matches!(snapshot.save(&invalid, "[invalid]"), Err(Error::Config(..))));
+		assert_eq!(read_to_string(snapshot.config())?, "[settings]");
+		assert!(snapshot.info().is_ok());
+		let snapshots: Vec<_> = std::fs::read_dir(temp_dir.path().join("snapshots"))?
+			.map(|e| e.unwrap().file_name())
+			.collect();
+		assert_eq!(snapshots, ["pop"]);
+		Ok(())
+	}
+
+	#[test]
+	fn new_fails_with_invalid_name() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let dir = temp_dir.path().join("snapshots");
+		for name in ["", "..", ".pop", "/tmp/x", "a/b"] {
+			assert!(matches!(
+				NetworkSnapshot::new(&dir, name),
+				Err(Error::Config(e)) if e.starts_with(&format!("invalid snapshot name `{name}`"))
+			));
+		}
+		assert_eq!(NetworkSnapshot::new(&dir, "pop-1.0_a")?.path(), dir.join("pop-1.0_a"));
+		Ok(())
+	}
+
+	#[test]
+	fn save_fails_without_chain_specs() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let info = NetworkInfo {
+			base_dir: temp_dir.path().to_path_buf(),
+			relay_chain: RelayChainInfo {
+				chain: "paseo-local".to_string(),
+				nodes: vec![node("alice", temp_dir.path(), &[])?],
+			},
+			parachains: vec![],
+		};
+		let snapshot = NetworkSnapshot::new(&temp_dir.path().join("snapshots"), "pop")?;
+		assert!(matches!(snapshot.save(&info, ""), Err(Error::Config(..))));
+		Ok(())
+	}
+}
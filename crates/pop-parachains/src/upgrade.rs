@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	utils::extrinsics::{signer, submit, sudo},
+};
+use pop_common::Status;
+use sp_crypto_hashing::blake2_256;
+use std::{
+	path::{Path, PathBuf},
+	time::Duration,
+};
+use subxt::{
+	backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+	dynamic::{tx, Value},
+	OnlineClient, PolkadotConfig,
+};
+use tokio::time::{timeout_at, Instant};
+use url::Url;
+
+/// The method used to upgrade the runtime of a chain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UpgradeMethod {
+	/// The upgrade is authorized via `Sudo`, using `System::authorize_upgrade`, and then applied
+	/// via `System::apply_authorized_upgrade`.
+	Authorized,
+	/// The code is set directly via `Sudo`, using `System::set_code`.
+	SetCode,
+}
+
+/// The outcome of a runtime upgrade.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpgradeOutcome {
+	/// The block at which the `CodeUpdated` event was observed.
+	pub updated_at: u32,
+	/// The spec version of the runtime after the upgrade.
+	pub spec_version: u32,
+	/// Whether block production continued after the upgrade.
+	pub continued: bool,
+}
+
+/// A runtime upgrade of a running chain, such as one launched via `pop up parachain`.
+pub struct RuntimeUpgrade {
+	client: OnlineClient<PolkadotConfig>,
+	rpc: LegacyRpcMethods<PolkadotConfig>,
+	code: Vec<u8>,
+}
+
+impl RuntimeUpgrade {
+	/// Connects to a running chain in preparation for upgrading its runtime.
+	///
+	/// # Arguments
+	/// * `url` - The websocket endpoint of a node of the chain.
+	/// * `code` - The runtime code (WASM) to be applied.
+	pub async fn new(url: &Url, code: Vec<u8>) -> Result<Self, Error> {
+		let rpc = RpcClient::from_insecure_url(url.as_str()).await?;
+		let client = OnlineClient::from_rpc_client(rpc.clone()).await?;
+		Ok(Self { client, rpc: LegacyRpcMethods::new(rpc), code })
+	}
+
+	/// The spec version of the current runtime of the chain.
+	pub fn spec_version(&self) -> u32 {
+		self.client.runtime_version().spec_version
+	}
+
+	/// The method used to upgrade the runtime, based on the calls available within the current
+	/// runtime of the chain.
+	pub fn method(&self) -> Result<UpgradeMethod, Error> {
+		let metadata = self.client.metadata();
+		if metadata.pallet_by_name("Sudo").is_none() {
+			return Err(Error::UpgradeError("the chain does not include the `Sudo` pallet".into()));
+		}
+		let system = metadata.pallet_by_name("System").ok_or_else(|| {
+			Error::UpgradeError("the chain does not include the `System` pallet".into())
+		})?;
+		let authorized = ["authorize_upgrade", "apply_authorized_upgrade"]
+			.iter()
+			.all(|call| system.call_variant_by_name(call).is_some());
+		Ok(if authorized { UpgradeMethod::Authorized } else { UpgradeMethod::SetCode })
+	}
+
+	/// Upgrades the runtime of the chain, waiting for the resulting `CodeUpdated` event and then
+	/// for the specified number of subsequent blocks to be produced.
+	///
+	/// # Arguments
+	/// * `suri` - The secret URI of the sudo account (e.g. "//Alice").
+	/// * `blocks` - The number of blocks expected to be produced after the upgrade.
+	/// * `timeout` - The maximum time to wait for the upgrade, and then for the subsequent blocks.
+	/// * `status` - Used to observe status updates.
+	pub async fn apply(
+		&self,
+		suri: &str,
+		blocks: u32,
+		timeout: Duration,
+		status: &impl Status,
+	) -> Result<UpgradeOutcome, Error> {
+		let signer = signer(suri)?;
+		// Subscribe before submitting, so that the `CodeUpdated` event cannot be missed
+		let mut best = self.client.blocks().subscribe_best().await?;
+
+		match self.method()? {
+			UpgradeMethod::Authorized => {
+				status.update("Authorizing upgrade via `System::authorize_upgrade`...");
+				let authorize = tx(
+					"System",
+					"authorize_upgrade",
+					vec![Value::from_bytes(blake2_256(&self.code))],
+				);
+				submit(&self.client, &sudo(authorize), &signer).await?;
+				status.update("Applying upgrade via `System::apply_authorized_upgrade`...");
+				let apply =
+					tx("System", "apply_authorized_upgrade", vec![Value::from_bytes(&self.code)]);
+				submit(&self.client, &apply, &signer).await?;
+			},
+			UpgradeMethod::SetCode => {
+				status.update("Setting code via `System::set_code`...");
+				let set_code = tx("System", "set_code", vec![Value::from_bytes(&self.code)]);
+				let weight = Value::named_composite([
+					("ref_time", Value::u128(0)),
+					("proof_size", Value::u128(0)),
+				]);
+				let call = tx("Sudo", "sudo_unchecked_weight", vec![set_code.into_value(), weight]);
+				submit(&self.client, &call, &signer).await?;
+			},
+		}
+
+		// Wait for the code to be updated, which may take some time for parachains
+		status.update("Waiting for the `CodeUpdated` event...");
+		let deadline = Instant::now() + timeout;
+		let updated_at = loop {
+			let block = timeout_at(deadline, best.next())
+				.await
+				.map_err(|_| Error::Timeout("the `CodeUpdated` event".into()))?
+				.ok_or_else(|| Error::UpgradeError("the block subscription ended".into()))??;
+			let events = block.events().await?;
+			if events
+				.iter()
+				.flatten()
+				.any(|e| e.pallet_name() == "System" && e.variant_name() == "CodeUpdated")
+			{
+				break block.number();
+			}
+		};
+
+		// Wait for subsequent blocks to be produced using the upgraded runtime
+		status.update(&format!(
+			"Code updated at block {updated_at}, waiting for {blocks} more block(s)..."
+		));
+		let deadline = Instant::now() + timeout;
+		let continued = loop {
+			match timeout_at(deadline, best.next()).await {
+				Ok(Some(block)) =>
+					if block?.number() >= updated_at + blocks {
+						break true;
+					},
+				Ok(None) | Err(_) => break false,
+			}
+		};
+		let spec_version = self.rpc.state_get_runtime_version(None).await?.spec_version;
+		Ok(UpgradeOutcome { updated_at, spec_version, continued })
+	}
+}
+
+/// Locates the runtime (WASM) built within a project, as output by the build of its runtime
+/// crate at `target/release/wbuild`.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+pub fn runtime_wasm(project: &Path) -> Result<PathBuf, Error> {
+	let pattern = project.join("target/release/wbuild/*/*.compact.compressed.wasm");
+	let mut paths: Vec<_> = glob::glob(&pattern.to_string_lossy())
+		.map_err(|e| Error::Config(e.to_string()))?
+		.flatten()
+		.collect();
+	match paths.len() {
+		0 => Err(Error::Config(format!(
+			"no runtime was found at {}, please build the runtime or specify it",
+			pattern.display()
+		))),
+		1 => Ok(paths.remove(0)),
+		_ => Err(Error::Config(format!(
+			"multiple runtimes were found at {}, please specify one",
+			pattern.display()
+		))),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use std::fs::{create_dir_all, write};
+	use tempfile::tempdir;
+
+	#[test]
+	fn runtime_wasm_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let project = temp_dir.path();
+		assert!(
+			matches!(runtime_wasm(project), Err(Error::Config(e)) if e.starts_with("no runtime"))
+		);
+
+		let wbuild = project.join("target/release/wbuild");
+		create_dir_all(wbuild.join("parachain-template-runtime"))?;
+		let wasm = wbuild
+			.join("parachain-template-runtime/parachain_template_runtime.compact.compressed.wasm");
+		write(&wasm, [0u8])?;
+		write(wbuild.join("parachain-template-runtime/parachain_template_runtime.wasm"), [0u8])?;
+		assert_eq!(runtime_wasm(project)?, wasm);
+
+		create_dir_all(wbuild.join("other-runtime"))?;
+		write(wbuild.join("other-runtime/other_runtime.compact.compressed.wasm"), [0u8])?;
+		assert!(
+			matches!(runtime_wasm(project), Err(Error::Config(e)) if e.starts_with("multiple"))
+		);
+		Ok(())
+	}
+}
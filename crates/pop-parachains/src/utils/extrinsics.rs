@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::errors::Error;
+use std::str::FromStr;
+use subxt::{
+	blocks::ExtrinsicEvents,
+	dynamic::tx,
+	ext::scale_value::{Composite, ValueDef},
+	tx::{DynamicPayload, Payload},
+	OnlineClient, PolkadotConfig,
+};
+use subxt_signer::{sr25519::Keypair, SecretUri};
+
+/// Creates a signer from a secret URI.
+///
+/// # Arguments
+/// * `suri` - The secret URI (e.g. "//Alice").
+pub(crate) fn signer(suri: &str) -> Result<Keypair, Error> {
+	let uri =
+		SecretUri::from_str(suri).map_err(|e| Error::Config(format!("invalid secret uri: {e}")))?;
+	Keypair::from_uri(&uri).map_err(|e| Error::Config(format!("invalid secret uri: {e}")))
+}
+
+/// Wraps a call so that it is dispatched via `Sudo`.
+///
+/// # Arguments
+/// * `call` - The call to be dispatched.
+pub(crate) fn sudo(call: DynamicPayload) -> DynamicPayload {
+	tx("Sudo", "sudo", vec![call.into_value()])
+}
+
+/// Submits a transaction, waiting for it to be finalized successfully.
+///
+/// # Arguments
+/// * `client` - The client of the chain.
+/// * `call` - The call to be submitted.
+/// * `signer` - The signer of the transaction.
+pub(crate) async fn submit(
+	client: &OnlineClient<PolkadotConfig>,
+	call: &impl Payload,
+	signer: &Keypair,
+) -> Result<(), Error> {
+	let events = client
+		.tx()
+		.sign_and_submit_then_watch_default(call, signer)
+		.await?
+		.wait_for_finalized_success()
+		.await?;
+	sudo_result(&events)
+}
+
+/// Checks the result of any call dispatched via `Sudo`, as the transaction itself succeeds
+/// regardless.
+///
+/// # Arguments
+/// * `events` - The events of the transaction.
+fn sudo_result(events: &ExtrinsicEvents<PolkadotConfig>) -> Result<(), Error> {
+	for event in events.iter() {
+		let event = event?;
+		if event.pallet_name() != "Sudo" || event.variant_name() != "Sudid" {
+			continue;
+		}
+		if let Composite::Named(fields) = event.field_values().map_err(subxt::Error::from)? {
+			for (name, value) in fields {
+				if let ("sudo_result", ValueDef::Variant(variant)) = (name.as_str(), &value.value) {
+					if variant.name == "Err" {
+						return Err(Error::TransactionError(format!(
+							"the sudo call failed: {}",
+							variant.values
+						)));
+					}
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use subxt::dynamic::Value;
+
+	#[test]
+	fn signer_works() -> Result<()> {
+		assert_eq!(
+			signer("//Alice")?.public_key().to_account_id().to_string(),
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+		);
+		assert!(matches!(signer("11111"), Err(Error::Config(..))));
+		Ok(())
+	}
+
+	#[test]
+	fn sudo_works() {
+		let call = sudo(tx("System", "remark", vec![Value::from_bytes([0u8])]));
+		assert_eq!((call.pallet_name(), call.call_name()), ("Sudo", "sudo"));
+	}
+}
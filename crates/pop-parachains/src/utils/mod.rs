@@ -1,3 +1,4 @@
 // SPDX-License-Identifier: GPL-3.0
 
+pub(crate) mod extrinsics;
 pub mod helpers;
@@ -162,13 +162,19 @@ pub fn config_file_path() -> Result<PathBuf> {
 }
 
 /// Writes opt-out to the configuration file at the specified path.
-/// opt-out is currently the only config type. Hence, if the file exists, it will be overwritten.
+/// Any other configuration within an existing file, such as a binary mirror, is preserved.
 ///
 /// parameters:
 /// `config_path`: the path to write the config file to
 pub fn write_config_opt_out(config_path: &PathBuf) -> Result<()> {
-	let config = Config { opt_out: OptOut { version: CARGO_PKG_VERSION.to_string() } };
+	let opt_out = OptOut { version: CARGO_PKG_VERSION.to_string() };
 
+	let mut config = read_json_file::<Value>(config_path)
+		.ok()
+		.filter(Value::is_object)
+		.unwrap_or_else(|| json!({}));
+	config["opt_out"] = serde_json::to_value(opt_out)
+		.map_err(|err| TelemetryError::SerializeFailed(err.to_string()))?;
 	let config_json = serde_json::to_string_pretty(&config)
 		.map_err(|err| TelemetryError::SerializeFailed(err.to_string()))?;
 
@@ -248,6 +254,20 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn write_config_opt_out_preserves_config() -> Result<()> {
+		let temp_dir = TempDir::new().unwrap();
+		let config_path = temp_dir.path().join("config.json");
+		std::fs::write(&config_path, r#"{"mirror":"https://mirror.example.com"}"#).unwrap();
+
+		write_config_opt_out(&config_path)?;
+
+		let config: Value = read_json_file(&config_path).unwrap();
+		assert_eq!(config["mirror"], "https://mirror.example.com");
+		assert_eq!(config["opt_out"]["version"], CARGO_PKG_VERSION);
+		Ok(())
+	}
+
 	#[tokio::test]
 	async fn new_telemetry_works() -> Result<()> {
 		let _ = env_logger::try_init();